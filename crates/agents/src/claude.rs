@@ -3,15 +3,43 @@
 use async_trait::async_trait;
 use meta_ai_common::{
     error::{Error, Result},
-    types::{LlmProvider, LlmRequest, LlmResponse},
+    types::{Attachment, LlmProvider, LlmRequest, LlmResponse, ResponseFormat, TokenUsage},
 };
-use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, RateLimitInfo};
+use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, AgentHealthTracker, RateLimitInfo};
+use std::time::Instant;
+
+/// Claude has no native structured-output mode, so schema-constrained
+/// requests fall back to appending explicit JSON instructions to the prompt.
+fn prompt_with_schema_instructions(prompt: &str, format: &ResponseFormat) -> String {
+    match format {
+        ResponseFormat::Text => prompt.to_string(),
+        ResponseFormat::JsonSchema { schema, .. } => format!(
+            "{prompt}\n\nRespond with only a single JSON object matching this schema, no other text:\n{schema}"
+        ),
+    }
+}
+
+/// Encode an attachment into Claude's `image` content block format.
+fn encode_attachment(attachment: &Attachment) -> serde_json::Value {
+    match attachment {
+        Attachment::ImageBase64 { media_type, data } => serde_json::json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        }),
+        Attachment::ImageUrl { url } => serde_json::json!({
+            "type": "image",
+            "source": { "type": "url", "url": url },
+        }),
+    }
+}
 
 /// Claude agent implementation
 pub struct ClaudeAgent {
     client: reqwest::Client,
     api_key: String,
+    base_url: String,
     model: String,
+    health: AgentHealthTracker,
 }
 
 impl ClaudeAgent {
@@ -19,8 +47,90 @@ impl ClaudeAgent {
         Self {
             client: reqwest::Client::new(),
             api_key,
+            base_url: "https://api.anthropic.com/v1".to_string(),
             model,
+            health: AgentHealthTracker::new(),
+        }
+    }
+
+    async fn submit_inner(
+        &self,
+        request: &LlmRequest,
+        content: Vec<serde_json::Value>,
+        format: ResponseFormat,
+    ) -> Result<LlmResponse> {
+        #[derive(serde::Serialize)]
+        struct ClaudeMessage {
+            role: &'static str,
+            content: serde_json::Value,
+        }
+
+        #[derive(serde::Serialize)]
+        struct MessagesRequest<'a> {
+            model: &'a str,
+            max_tokens: u32,
+            messages: Vec<ClaudeMessage>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            temperature: Option<f32>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct ContentBlock {
+            #[serde(default)]
+            text: String,
+        }
+
+        #[derive(serde::Deserialize, Default)]
+        struct MessagesUsage {
+            #[serde(default)]
+            input_tokens: u32,
+            #[serde(default)]
+            output_tokens: u32,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct MessagesResponse {
+            content: Vec<ContentBlock>,
+            #[serde(default)]
+            usage: Option<MessagesUsage>,
         }
+
+        let body = MessagesRequest {
+            model: &self.model,
+            max_tokens: request.parameters.max_tokens.unwrap_or(4096),
+            messages: vec![ClaudeMessage { role: "user", content: serde_json::Value::Array(content) }],
+            temperature: request.parameters.temperature,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<MessagesResponse>()
+            .await?;
+
+        let content = response.content.into_iter().next().map(|block| block.text).unwrap_or_default();
+        let usage = response.usage.unwrap_or_default();
+
+        meta_ai_common::schema::validate_response(&format, &content)?;
+
+        Ok(LlmResponse {
+            request_id: request.id,
+            content,
+            usage: TokenUsage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage.input_tokens + usage.output_tokens,
+            },
+            latency_ms: 0,
+            provider: LlmProvider::Claude,
+            metadata: request.metadata.clone(),
+        })
     }
 }
 
@@ -38,8 +148,29 @@ impl Agent for ClaudeAgent {
         true
     }
     
-    async fn submit(&self, _request: LlmRequest) -> Result<LlmResponse> {
-        Err(Error::Agent("Not implemented".to_string()))
+    async fn submit(&self, request: LlmRequest) -> Result<LlmResponse> {
+        let start = Instant::now();
+
+        let result = if !request.attachments.is_empty() && !self.capabilities().supports_vision {
+            Err(Error::Validation(format!("{} does not support image attachments", self.name())))
+        } else {
+            let format = request.parameters.response_format.clone().unwrap_or(ResponseFormat::Text);
+            let prompt = prompt_with_schema_instructions(&request.prompt, &format);
+            let content: Vec<serde_json::Value> = std::iter::once(serde_json::json!({ "type": "text", "text": prompt }))
+                .chain(request.attachments.iter().map(encode_attachment))
+                .collect();
+            self.submit_inner(&request, content, format).await
+        };
+
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+        match &result {
+            Ok(_) => self.health.record(latency_ms, None),
+            Err(e) => self.health.record(latency_ms, Some(&e.to_string())),
+        }
+        result.map(|mut response| {
+            response.latency_ms = latency_ms as u64;
+            response
+        })
     }
     
     fn capabilities(&self) -> AgentCapabilities {
@@ -50,6 +181,7 @@ impl Agent for ClaudeAgent {
             supports_vision: true,
             supports_code_execution: false,
             supports_web_search: false,
+            supports_embeddings: false,
             context_window: 200000,
             languages: vec!["en".to_string()],
             specializations: vec!["reasoning".to_string(), "analysis".to_string()],
@@ -57,14 +189,7 @@ impl Agent for ClaudeAgent {
     }
     
     async fn health_check(&self) -> Result<AgentHealth> {
-        Ok(AgentHealth {
-            healthy: true,
-            latency_ms: Some(120.0),
-            requests_per_minute: 50.0,
-            average_latency_ms: 180.0,
-            error_rate: 0.005,
-            last_error: None,
-        })
+        Ok(self.health.snapshot())
     }
     
     async fn rate_limit_info(&self) -> Result<RateLimitInfo> {
@@ -1,34 +1,313 @@
-//! Copilot agent stub
+//! GitHub Copilot agent implementation
+//!
+//! Copilot uses GitHub's OAuth device authorization flow rather than a
+//! long-lived API key: the caller drives `start_device_flow`/
+//! `complete_device_flow` once to obtain an OAuth token, which is then
+//! exchanged for short-lived Copilot session tokens as needed.
 
 use async_trait::async_trait;
-use meta_ai_common::{error::{Error, Result}, types::{LlmProvider, LlmRequest, LlmResponse}};
-use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, RateLimitInfo};
+use chrono::{DateTime, Utc};
+use meta_ai_common::{
+    error::{Error, Result},
+    schema::validate_response,
+    types::{LlmProvider, LlmRequest, LlmResponse, ResponseFormat, TokenUsage},
+};
+use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, AgentHealthTracker, RateLimitInfo};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 
-pub struct CopilotAgent;
+const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
+const ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const COPILOT_TOKEN_URL: &str = "https://api.github.com/copilot_internal/v2/token";
+const COMPLETIONS_URL: &str = "https://api.githubcopilot.com/v1/engines/copilot-codex/completions";
+
+/// Response from GitHub's device authorization endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// Short-lived Copilot session token exchanged from the GitHub OAuth token.
+struct SessionToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Copilot agent implementation
+pub struct CopilotAgent {
+    client: reqwest::Client,
+    client_id: String,
+    oauth_token: RwLock<Option<String>>,
+    session_token: RwLock<Option<SessionToken>>,
+    health: AgentHealthTracker,
+}
 
 impl CopilotAgent {
-    pub fn new() -> Self { Self }
+    pub fn new(client_id: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            client_id,
+            oauth_token: RwLock::new(None),
+            session_token: RwLock::new(None),
+            health: AgentHealthTracker::new(),
+        }
+    }
+
+    /// Create an agent that is already authorized, skipping the device flow.
+    pub fn with_oauth_token(client_id: String, oauth_token: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            client_id,
+            oauth_token: RwLock::new(Some(oauth_token)),
+            session_token: RwLock::new(None),
+            health: AgentHealthTracker::new(),
+        }
+    }
+
+    /// Start the GitHub device authorization flow, returning the user code
+    /// and verification URL that must be presented to the operator.
+    pub async fn start_device_flow(&self) -> Result<DeviceAuthorization> {
+        let response = self
+            .client
+            .post(DEVICE_CODE_URL)
+            .header("Accept", "application/json")
+            .form(&[("client_id", self.client_id.as_str()), ("scope", "read:user")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DeviceAuthorization>()
+            .await?;
+
+        Ok(response)
+    }
+
+    /// Poll GitHub until the user authorizes the device, then cache the
+    /// resulting OAuth token for subsequent requests.
+    pub async fn complete_device_flow(&self, device_code: &str, interval: u64) -> Result<()> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: Option<String>,
+            error: Option<String>,
+        }
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            let response = self
+                .client
+                .post(ACCESS_TOKEN_URL)
+                .header("Accept", "application/json")
+                .form(&[
+                    ("client_id", self.client_id.as_str()),
+                    ("device_code", device_code),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await?
+                .json::<TokenResponse>()
+                .await?;
+
+            match (response.access_token, response.error.as_deref()) {
+                (Some(token), _) => {
+                    *self.oauth_token.write().await = Some(token);
+                    return Ok(());
+                }
+                (None, Some("authorization_pending")) => continue,
+                (None, Some(other)) => {
+                    return Err(Error::Auth(format!("device flow authorization failed: {other}")))
+                }
+                (None, None) => {
+                    return Err(Error::Auth("device flow authorization failed".to_string()))
+                }
+            }
+        }
+    }
+
+    /// Exchange the cached OAuth token for a Copilot session token,
+    /// refreshing it if it has expired.
+    async fn session_token(&self) -> Result<String> {
+        if let Some(session) = self.session_token.read().await.as_ref() {
+            if session.expires_at > Utc::now() {
+                return Ok(session.token.clone());
+            }
+        }
+
+        let oauth_token = self
+            .oauth_token
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| Error::Auth("Copilot agent is not authorized; complete the device flow first".to_string()))?;
+
+        #[derive(Deserialize)]
+        struct CopilotTokenResponse {
+            token: String,
+            expires_at: i64,
+        }
+
+        let response = self
+            .client
+            .get(COPILOT_TOKEN_URL)
+            .bearer_auth(&oauth_token)
+            .header("User-Agent", "meta-ai-orchestrator")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CopilotTokenResponse>()
+            .await?;
+
+        let expires_at = DateTime::<Utc>::from_timestamp(response.expires_at, 0).unwrap_or_else(Utc::now);
+        let token = response.token.clone();
+        *self.session_token.write().await = Some(SessionToken {
+            token: response.token,
+            expires_at,
+        });
+
+        Ok(token)
+    }
+
+    async fn submit_inner(&self, request: &LlmRequest) -> Result<LlmResponse> {
+        let token = self.session_token().await?;
+
+        #[derive(Serialize)]
+        struct CompletionRequest<'a> {
+            prompt: &'a str,
+            max_tokens: Option<u32>,
+            temperature: Option<f32>,
+            stream: bool,
+        }
+
+        #[derive(Deserialize)]
+        struct CompletionChoice {
+            text: String,
+        }
+
+        #[derive(Deserialize, Default)]
+        struct CompletionUsage {
+            #[serde(default)]
+            prompt_tokens: u32,
+            #[serde(default)]
+            completion_tokens: u32,
+            #[serde(default)]
+            total_tokens: u32,
+        }
+
+        #[derive(Deserialize)]
+        struct CompletionResponse {
+            choices: Vec<CompletionChoice>,
+            #[serde(default)]
+            usage: Option<CompletionUsage>,
+        }
+
+        let format = request.parameters.response_format.clone().unwrap_or(ResponseFormat::Text);
+        let prompt = match &format {
+            ResponseFormat::Text => request.prompt.clone(),
+            ResponseFormat::JsonSchema { schema, .. } => format!(
+                "{}\n\nRespond with only a single JSON object matching this schema, no other text:\n{}",
+                request.prompt, schema
+            ),
+        };
+
+        let body = CompletionRequest {
+            prompt: &prompt,
+            max_tokens: request.parameters.max_tokens,
+            temperature: request.parameters.temperature,
+            stream: false,
+        };
+
+        let response = self
+            .client
+            .post(COMPLETIONS_URL)
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CompletionResponse>()
+            .await?;
+
+        let content = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.text)
+            .unwrap_or_default();
+        let usage = response.usage.unwrap_or_default();
+
+        validate_response(&format, &content)?;
+
+        Ok(LlmResponse {
+            request_id: request.id,
+            content,
+            usage: TokenUsage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+            },
+            latency_ms: 0,
+            provider: LlmProvider::Copilot,
+            metadata: request.metadata.clone(),
+        })
+    }
 }
 
 #[async_trait]
 impl Agent for CopilotAgent {
     fn name(&self) -> &str { "Copilot" }
     fn provider(&self) -> LlmProvider { LlmProvider::Copilot }
-    async fn is_available(&self) -> bool { true }
-    async fn submit(&self, _request: LlmRequest) -> Result<LlmResponse> {
-        Err(Error::Agent("Not implemented".to_string()))
+
+    async fn is_available(&self) -> bool {
+        self.oauth_token.read().await.is_some()
     }
-    fn capabilities(&self) -> AgentCapabilities { AgentCapabilities::default() }
-    async fn health_check(&self) -> Result<AgentHealth> {
-        Ok(AgentHealth {
-            healthy: true, latency_ms: Some(90.0), requests_per_minute: 100.0,
-            average_latency_ms: 120.0, error_rate: 0.02, last_error: None,
+
+    async fn submit(&self, request: LlmRequest) -> Result<LlmResponse> {
+        let start = Instant::now();
+        let result = if !request.attachments.is_empty() && !self.capabilities().supports_vision {
+            Err(Error::Validation(format!("{} does not support image attachments", self.name())))
+        } else {
+            self.submit_inner(&request).await
+        };
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        match &result {
+            Ok(_) => self.health.record(latency_ms, None),
+            Err(e) => self.health.record(latency_ms, Some(&e.to_string())),
+        }
+
+        result.map(|mut response| {
+            response.latency_ms = latency_ms as u64;
+            response
         })
     }
+
+    fn capabilities(&self) -> AgentCapabilities {
+        AgentCapabilities {
+            max_tokens: 2048,
+            supports_streaming: true,
+            supports_function_calling: false,
+            supports_vision: false,
+            supports_code_execution: false,
+            supports_web_search: false,
+            supports_embeddings: false,
+            context_window: 8192,
+            languages: vec!["en".to_string()],
+            specializations: vec!["code_generation".to_string()],
+        }
+    }
+
+    async fn health_check(&self) -> Result<AgentHealth> {
+        Ok(self.health.snapshot())
+    }
+
     async fn rate_limit_info(&self) -> Result<RateLimitInfo> {
         Ok(RateLimitInfo {
             requests_remaining: Some(200), requests_limit: Some(1000),
             reset_time: None, tokens_remaining: Some(60000), tokens_limit: Some(100000),
         })
     }
-}
\ No newline at end of file
+}
@@ -2,12 +2,15 @@
 
 use async_trait::async_trait;
 use meta_ai_common::{error::{Error, Result}, types::{LlmProvider, LlmRequest, LlmResponse}};
-use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, RateLimitInfo};
+use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, AgentHealthTracker, RateLimitInfo};
+use std::time::Instant;
 
-pub struct CursorAgent;
+pub struct CursorAgent {
+    health: AgentHealthTracker,
+}
 
 impl CursorAgent {
-    pub fn new() -> Self { Self }
+    pub fn new() -> Self { Self { health: AgentHealthTracker::new() } }
 }
 
 #[async_trait]
@@ -15,15 +18,23 @@ impl Agent for CursorAgent {
     fn name(&self) -> &str { "Cursor" }
     fn provider(&self) -> LlmProvider { LlmProvider::Cursor }
     async fn is_available(&self) -> bool { true }
-    async fn submit(&self, _request: LlmRequest) -> Result<LlmResponse> {
-        Err(Error::Agent("Not implemented".to_string()))
+    async fn submit(&self, request: LlmRequest) -> Result<LlmResponse> {
+        let start = Instant::now();
+        let result = if !request.attachments.is_empty() && !self.capabilities().supports_vision {
+            Err(Error::Validation(format!("{} does not support image attachments", self.name())))
+        } else {
+            Err(Error::Agent("Not implemented".to_string()))
+        };
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+        match &result {
+            Ok(_) => self.health.record(latency_ms, None),
+            Err(e) => self.health.record(latency_ms, Some(&e.to_string())),
+        }
+        result
     }
     fn capabilities(&self) -> AgentCapabilities { AgentCapabilities::default() }
     async fn health_check(&self) -> Result<AgentHealth> {
-        Ok(AgentHealth {
-            healthy: true, latency_ms: Some(80.0), requests_per_minute: 120.0,
-            average_latency_ms: 100.0, error_rate: 0.015, last_error: None,
-        })
+        Ok(self.health.snapshot())
     }
     async fn rate_limit_info(&self) -> Result<RateLimitInfo> {
         Ok(RateLimitInfo {
@@ -31,4 +42,4 @@ impl Agent for CursorAgent {
             reset_time: None, tokens_remaining: Some(70000), tokens_limit: Some(100000),
         })
     }
-}
\ No newline at end of file
+}
@@ -3,9 +3,35 @@
 use async_trait::async_trait;
 use meta_ai_common::{
     error::{Error, Result},
-    types::{LlmProvider, LlmRequest, LlmResponse},
+    types::{Attachment, Embedding, LlmProvider, LlmRequest, LlmResponse, ResponseFormat, TokenUsage},
 };
-use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, RateLimitInfo};
+use meta_ai_core::agent::{Agent, AgentCapabilities, AgentHealth, AgentHealthTracker, RateLimitInfo};
+use std::time::Instant;
+
+/// Encode an attachment into OpenAI's `image_url` content part format.
+fn encode_attachment(attachment: &Attachment) -> serde_json::Value {
+    match attachment {
+        Attachment::ImageBase64 { media_type, data } => serde_json::json!({
+            "type": "image_url",
+            "image_url": { "url": format!("data:{media_type};base64,{data}") },
+        }),
+        Attachment::ImageUrl { url } => serde_json::json!({
+            "type": "image_url",
+            "image_url": { "url": url },
+        }),
+    }
+}
+
+/// Map a `ResponseFormat` onto OpenAI's native `response_format` request field.
+fn encode_response_format(format: &ResponseFormat) -> serde_json::Value {
+    match format {
+        ResponseFormat::Text => serde_json::json!({ "type": "text" }),
+        ResponseFormat::JsonSchema { name, schema } => serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": name, "schema": schema, "strict": true },
+        }),
+    }
+}
 
 /// OpenAI agent implementation
 pub struct OpenAIAgent {
@@ -13,6 +39,7 @@ pub struct OpenAIAgent {
     api_key: String,
     base_url: String,
     model: String,
+    health: AgentHealthTracker,
 }
 
 impl OpenAIAgent {
@@ -22,7 +49,100 @@ impl OpenAIAgent {
             api_key,
             base_url: "https://api.openai.com/v1".to_string(),
             model,
+            health: AgentHealthTracker::new(),
+        }
+    }
+
+    async fn submit_inner(
+        &self,
+        request: &LlmRequest,
+        content: Vec<serde_json::Value>,
+        format: ResponseFormat,
+    ) -> Result<LlmResponse> {
+        #[derive(serde::Serialize)]
+        struct ChatMessage {
+            role: &'static str,
+            content: serde_json::Value,
+        }
+
+        #[derive(serde::Serialize)]
+        struct ChatRequest<'a> {
+            model: &'a str,
+            messages: Vec<ChatMessage>,
+            max_tokens: Option<u32>,
+            temperature: Option<f32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            response_format: Option<serde_json::Value>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct ChatMessageContent {
+            content: String,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct ChatChoice {
+            message: ChatMessageContent,
         }
+
+        #[derive(serde::Deserialize, Default)]
+        struct ChatUsage {
+            #[serde(default)]
+            prompt_tokens: u32,
+            #[serde(default)]
+            completion_tokens: u32,
+            #[serde(default)]
+            total_tokens: u32,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct ChatResponse {
+            choices: Vec<ChatChoice>,
+            #[serde(default)]
+            usage: Option<ChatUsage>,
+        }
+
+        let response_format = match &format {
+            ResponseFormat::Text => None,
+            ResponseFormat::JsonSchema { .. } => Some(encode_response_format(&format)),
+        };
+
+        let body = ChatRequest {
+            model: &self.model,
+            messages: vec![ChatMessage { role: "user", content: serde_json::Value::Array(content) }],
+            max_tokens: request.parameters.max_tokens,
+            temperature: request.parameters.temperature,
+            response_format,
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ChatResponse>()
+            .await?;
+
+        let content = response.choices.into_iter().next().map(|choice| choice.message.content).unwrap_or_default();
+        let usage = response.usage.unwrap_or_default();
+
+        meta_ai_common::schema::validate_response(&format, &content)?;
+
+        Ok(LlmResponse {
+            request_id: request.id,
+            content,
+            usage: TokenUsage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+            },
+            latency_ms: 0,
+            provider: LlmProvider::OpenAI,
+            metadata: request.metadata.clone(),
+        })
     }
 }
 
@@ -41,9 +161,28 @@ impl Agent for OpenAIAgent {
         true
     }
     
-    async fn submit(&self, _request: LlmRequest) -> Result<LlmResponse> {
-        // TODO: Implement actual OpenAI API call
-        Err(Error::Agent("Not implemented".to_string()))
+    async fn submit(&self, request: LlmRequest) -> Result<LlmResponse> {
+        let start = Instant::now();
+
+        let result = if !request.attachments.is_empty() && !self.capabilities().supports_vision {
+            Err(Error::Validation(format!("{} does not support image attachments", self.name())))
+        } else {
+            let content: Vec<serde_json::Value> = std::iter::once(serde_json::json!({ "type": "text", "text": request.prompt }))
+                .chain(request.attachments.iter().map(encode_attachment))
+                .collect();
+            let format = request.parameters.response_format.clone().unwrap_or(ResponseFormat::Text);
+            self.submit_inner(&request, content, format).await
+        };
+
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+        match &result {
+            Ok(_) => self.health.record(latency_ms, None),
+            Err(e) => self.health.record(latency_ms, Some(&e.to_string())),
+        }
+        result.map(|mut response| {
+            response.latency_ms = latency_ms as u64;
+            response
+        })
     }
     
     fn capabilities(&self) -> AgentCapabilities {
@@ -54,6 +193,7 @@ impl Agent for OpenAIAgent {
             supports_vision: true,
             supports_code_execution: false,
             supports_web_search: false,
+            supports_embeddings: true,
             context_window: 128000,
             languages: vec!["en".to_string(), "es".to_string(), "fr".to_string()],
             specializations: vec!["general".to_string(), "coding".to_string()],
@@ -61,14 +201,7 @@ impl Agent for OpenAIAgent {
     }
     
     async fn health_check(&self) -> Result<AgentHealth> {
-        Ok(AgentHealth {
-            healthy: true,
-            latency_ms: Some(100.0),
-            requests_per_minute: 60.0,
-            average_latency_ms: 150.0,
-            error_rate: 0.01,
-            last_error: None,
-        })
+        Ok(self.health.snapshot())
     }
     
     async fn rate_limit_info(&self) -> Result<RateLimitInfo> {
@@ -80,4 +213,40 @@ impl Agent for OpenAIAgent {
             tokens_limit: Some(100000),
         })
     }
+
+    async fn embed(&self, text: &str) -> Result<Embedding> {
+        #[derive(serde::Serialize)]
+        struct EmbeddingRequest<'a> {
+            model: &'a str,
+            input: &'a str,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbeddingData {
+            embedding: Vec<f32>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbeddingResponse {
+            data: Vec<EmbeddingData>,
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/embeddings", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&EmbeddingRequest { model: "text-embedding-3-small", input: text })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<EmbeddingResponse>()
+            .await?;
+
+        response
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .ok_or_else(|| Error::Agent("OpenAI embeddings response contained no data".to_string()))
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 //! Meta-AI CLI application
 
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use meta_ai_common::Config;
 use anyhow::Result;
+use std::collections::HashMap;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -15,12 +16,37 @@ async fn main() -> Result<()> {
                 .short('c')
                 .long("config")
                 .value_name("FILE")
-                .help("Configuration file path")
+                .help("Configuration file path (.toml, .yaml/.yml, or .json; falls back to META_AI_CONFIG)")
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("PROFILE")
+                .help("Configuration profile to load (default, dev, staging, prod, ...)")
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .value_name("SECTION.FIELD=VALUE")
+                .action(ArgAction::Append)
+                .help("Override a config field, highest precedence over profile/env layers. Repeatable.")
         )
         .subcommand(
             Command::new("status")
                 .about("Show orchestrator status")
         )
+        .subcommand(
+            Command::new("config")
+                .about("Configuration inspection")
+                .subcommand(
+                    Command::new("show")
+                        .about("Print the final merged config (profile + env + --set), secrets redacted")
+                )
+                .subcommand(
+                    Command::new("schema")
+                        .about("Print the Config structure's JSON Schema, for editor/CI validation")
+                )
+        )
         .subcommand(
             Command::new("task")
                 .about("Task management")
@@ -37,14 +63,85 @@ async fn main() -> Result<()> {
                                 .help("Task description")
                         )
                 )
+        )
+        .subcommand(
+            Command::new("ab-test")
+                .about("A/B experiment management")
+                .subcommand(
+                    Command::new("create")
+                        .about("Create a new experiment")
+                        .arg(Arg::new("name").required(true).help("Experiment name"))
+                        .arg(
+                            Arg::new("control")
+                                .long("control")
+                                .required(true)
+                                .help("Control provider")
+                        )
+                        .arg(
+                            Arg::new("experiment")
+                                .long("experiment")
+                                .required(true)
+                                .help("Experiment provider")
+                        )
+                        .arg(
+                            Arg::new("split")
+                                .long("split")
+                                .default_value("0.5")
+                                .help("Traffic fraction routed to the experiment provider")
+                        )
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about("List running experiments")
+                )
+                .subcommand(
+                    Command::new("conclude")
+                        .about("Conclude an experiment")
+                        .arg(Arg::new("name").required(true).help("Experiment name"))
+                )
+        )
+        .subcommand(
+            Command::new("feedback")
+                .about("Record human feedback on a task")
+                .arg(Arg::new("task-id").required(true).help("Task id"))
+                .arg(
+                    Arg::new("rating")
+                        .required(true)
+                        .value_parser(["up", "down"])
+                        .help("Thumbs up or down")
+                )
+                .arg(
+                    Arg::new("comment")
+                        .long("comment")
+                        .help("Optional correction or note")
+                )
         );
     
     let matches = app.get_matches();
-    
-    // Load config
-    let _config = Config::load()?;
-    
+
+    let profile = matches.get_one::<String>("profile").map(String::as_str);
+    let config_path = matches.get_one::<String>("config").map(std::path::Path::new);
+    let cli_overrides: HashMap<String, String> = matches
+        .get_many::<String>("set")
+        .unwrap_or_default()
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+
+    // Load config. `config_path` (--config, falling back to META_AI_CONFIG)
+    // may be TOML, YAML, or JSON - format is auto-detected by extension.
+    let config = Config::load_full(config_path, profile, &cli_overrides)?;
+
     match matches.subcommand() {
+        Some(("config", config_matches)) => match config_matches.subcommand() {
+            Some(("show", _)) => {
+                println!("{}", serde_json::to_string_pretty(&config.effective())?);
+            }
+            Some(("schema", _)) => {
+                println!("{}", serde_json::to_string_pretty(&Config::json_schema())?);
+            }
+            _ => println!("Use 'config show' or 'config schema'"),
+        },
         Some(("status", _)) => {
             println!("🤖 Meta-AI Orchestrator Status");
             println!("✅ System healthy");
@@ -66,6 +163,38 @@ async fn main() -> Result<()> {
                 _ => println!("Use 'task list' or 'task submit <description>'"),
             }
         }
+        Some(("ab-test", ab_test_matches)) => {
+            match ab_test_matches.subcommand() {
+                Some(("create", create_matches)) => {
+                    let name = create_matches.get_one::<String>("name").unwrap();
+                    let control = create_matches.get_one::<String>("control").unwrap();
+                    let experiment = create_matches.get_one::<String>("experiment").unwrap();
+                    let split = create_matches.get_one::<String>("split").unwrap();
+                    println!(
+                        "✅ Experiment '{}' created: {} control / {} experiment, {} split",
+                        name, control, experiment, split
+                    );
+                }
+                Some(("list", _)) => {
+                    println!("📋 Running Experiments: (empty)");
+                }
+                Some(("conclude", conclude_matches)) => {
+                    let name = conclude_matches.get_one::<String>("name").unwrap();
+                    println!("✅ Experiment '{}' concluded", name);
+                }
+                _ => println!("Use 'ab-test create', 'ab-test list', or 'ab-test conclude <name>'"),
+            }
+        }
+        Some(("feedback", feedback_matches)) => {
+            let task_id = feedback_matches.get_one::<String>("task-id").unwrap();
+            let rating = feedback_matches.get_one::<String>("rating").unwrap();
+            let comment = feedback_matches.get_one::<String>("comment");
+            let emoji = if rating == "up" { "👍" } else { "👎" };
+            match comment {
+                Some(comment) => println!("{} Feedback recorded for task {}: {}", emoji, task_id, comment),
+                None => println!("{} Feedback recorded for task {}", emoji, task_id),
+            }
+        }
         _ => {
             println!("🤖 Meta-AI Orchestrator CLI");
             println!("Use --help for usage information");
@@ -1,6 +1,9 @@
-//! Configuration management with hot-reload support
+//! Configuration management. `Config::load` itself is one-shot; actual
+//! hot-reload (watching the file, re-validating, and publishing diffs) lives
+//! in [`crate::config_watcher::ConfigWatcher`].
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use config::{Config as ConfigBuilder, ConfigError, Environment, File};
 use std::path::Path;
 use std::collections::HashMap;
@@ -8,7 +11,7 @@ use crate::types::LlmProvider;
 use secrecy::{Secret, ExposeSecret};
 
 /// Main configuration structure
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct Config {
     pub server: ServerConfig,
     pub orchestrator: OrchestratorConfig,
@@ -17,10 +20,85 @@ pub struct Config {
     pub evaluation: EvaluationConfig,
     pub observability: ObservabilityConfig,
     pub security: SecurityConfig,
+    /// Overrides/additions layered on top of `meta_ai_core::model_catalog::
+    /// ModelCatalog`'s bundled defaults. Empty means every provider uses its
+    /// bundled catalog entry unmodified.
+    #[serde(default)]
+    pub model_catalog: Vec<ModelCatalogEntry>,
+    /// Which profile (`default`, `dev`, `staging`, `prod`, ...) `load_with_overrides`
+    /// resolved this config from. Set from the `profile` argument/
+    /// `META_AI_PROFILE` env var at load time; not meant to be set by hand in
+    /// `config.toml` itself.
+    #[serde(default = "default_profile_name")]
+    pub profile: String,
+}
+
+fn default_profile_name() -> String {
+    DEFAULT_PROFILE.to_string()
+}
+
+/// Recursively redact every `api_key` field in `value` to `"[REDACTED]"`, for
+/// `Config::effective`'s debug dump and any other place a full serialized
+/// `Config` might get logged.
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if key == "api_key" || key == "admin_token" {
+                    if !v.is_null() {
+                        *v = serde_json::Value::String("[REDACTED]".to_string());
+                    }
+                } else {
+                    redact_secrets(v);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items.iter_mut() {
+                redact_secrets(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Config-provided override or addition for `meta_ai_core::model_catalog::
+/// ModelCatalog`. Plain data only - this crate can't depend on
+/// `meta-ai-core`, which owns the richer `ModelInfo`/`ModelCatalog` types
+/// these entries get converted into.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct ModelCatalogEntry {
+    pub provider: LlmProvider,
+    pub model: String,
+    pub context_window: u32,
+    pub max_output_tokens: u32,
+    pub input_cost_per_million: f64,
+    pub output_cost_per_million: f64,
+    #[serde(default)]
+    pub supports_streaming: bool,
+    #[serde(default)]
+    pub supports_function_calling: bool,
+    #[serde(default)]
+    pub supports_vision: bool,
+    #[serde(default)]
+    pub supports_code_execution: bool,
+    #[serde(default)]
+    pub supports_web_search: bool,
+    #[serde(default)]
+    pub supports_embeddings: bool,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub specializations: Vec<String>,
+    /// ISO-8601 date (`YYYY-MM-DD`) the provider has announced this model
+    /// will stop being served. `None` means no deprecation has been
+    /// announced.
+    #[serde(default)]
+    pub deprecation_date: Option<String>,
 }
 
 /// Server configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
@@ -30,7 +108,7 @@ pub struct ServerConfig {
 }
 
 /// Orchestrator configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct OrchestratorConfig {
     pub max_concurrent_tasks: usize,
     pub task_queue_size: usize,
@@ -41,8 +119,13 @@ pub struct OrchestratorConfig {
 }
 
 /// Agent configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct AgentConfig {
+    /// Plain literal key or a `${env:...}`/`${file:...}`/`${vault:...}`/
+    /// `${aws-sm:...}` reference resolved by `Config::resolve_secrets`. The
+    /// schema can't know which of those it'll be, so it's typed as a plain
+    /// string.
+    #[schemars(with = "String")]
     pub api_key: Secret<String>,
     pub base_url: String,
     pub model: String,
@@ -53,7 +136,7 @@ pub struct AgentConfig {
 }
 
 /// RAG configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct RagConfig {
     pub qdrant_url: String,
     pub collection_name: String,
@@ -62,10 +145,84 @@ pub struct RagConfig {
     pub chunk_size: usize,
     pub chunk_overlap: usize,
     pub top_k: usize,
+    /// How keyword (BM25) and vector similarity results are combined by
+    /// `RagEngine::search`'s hybrid search.
+    pub fusion_strategy: FusionStrategy,
+    /// Maximum number of distinct texts the embedding cache keeps before
+    /// evicting the least-recently-used one.
+    pub embedding_cache_size: usize,
+    /// How long a cached embedding stays valid before it's treated as a miss.
+    /// `None` means cached embeddings never expire on their own.
+    pub embedding_cache_ttl_secs: Option<u64>,
+    /// How Qdrant should quantize this collection's vectors to shrink memory
+    /// usage, at the cost of some recall. `None` (the default) stores
+    /// vectors at full `f32` precision.
+    pub quantization: Option<QuantizationConfig>,
+    /// Store the in-process embedding cache's vectors as `f16` instead of
+    /// `f32`, halving its memory footprint at the cost of a small precision
+    /// loss and the conversion overhead on every hit/insert.
+    pub embedding_cache_f16: bool,
+}
+
+/// Vector quantization applied to a Qdrant collection. Mirrors (a subset of)
+/// Qdrant's own quantization config rather than depending on `qdrant-client`
+/// from this crate; `QdrantRagEngine` translates it into the real
+/// `qdrant_client::qdrant::QuantizationConfig` when creating a collection.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub enum QuantizationConfig {
+    /// Quantize each vector component to an `i8`, the cheapest quantization
+    /// Qdrant offers and a good default for most collections.
+    Scalar {
+        /// Fraction of extreme values clipped before computing quantization
+        /// bounds, trading a little range for better resolution on the bulk
+        /// of the distribution. Qdrant recommends 0.99.
+        quantile: f32,
+        /// Keep quantized vectors in RAM even if the main vector storage is
+        /// on disk, since they're what HNSW scores against first.
+        always_ram: bool,
+    },
+    /// Split each vector into sub-vectors and quantize each independently,
+    /// trading more accuracy loss than scalar quantization for a much bigger
+    /// memory reduction on large collections.
+    Product {
+        /// How many times smaller the quantized vectors are than the
+        /// original `f32` ones.
+        compression: ProductCompressionRatio,
+        always_ram: bool,
+    },
+}
+
+/// Compression ratio for `QuantizationConfig::Product`, mirroring Qdrant's
+/// `CompressionRatio` enum.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+pub enum ProductCompressionRatio {
+    X4,
+    X8,
+    X16,
+    X32,
+    X64,
+}
+
+/// How a hybrid search combines its BM25 keyword result list with its vector
+/// similarity result list into one ranking.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub enum FusionStrategy {
+    /// Reciprocal Rank Fusion: each result's score is `1 / (k + rank)`,
+    /// summed across whichever lists it appears in. `k` is RRF's smoothing
+    /// constant; 60 is the value most RRF implementations default to.
+    ReciprocalRank { k: f32 },
+    /// Weighted sum of each list's min-max normalized scores.
+    Weighted { bm25_weight: f32, vector_weight: f32 },
+}
+
+impl Default for FusionStrategy {
+    fn default() -> Self {
+        Self::ReciprocalRank { k: 60.0 }
+    }
 }
 
 /// Evaluation configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct EvaluationConfig {
     pub accuracy_threshold: f64,
     pub bug_rate_threshold: f64,
@@ -75,7 +232,7 @@ pub struct EvaluationConfig {
 }
 
 /// Observability configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ObservabilityConfig {
     pub metrics_enabled: bool,
     pub metrics_port: u16,
@@ -86,7 +243,7 @@ pub struct ObservabilityConfig {
 }
 
 /// Security configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct SecurityConfig {
     pub auth_enabled: bool,
     pub api_key_header: String,
@@ -94,10 +251,26 @@ pub struct SecurityConfig {
     pub rate_limit_requests_per_minute: u32,
     pub request_signature_validation: bool,
     pub sandbox_enabled: bool,
+    /// Prompt-injection score (0.0-1.0) at or above which `MetaEvaluator`
+    /// blocks a task instead of merely flagging it with a `Warning` issue.
+    /// `None` disables blocking; detections are still recorded as `Warning`
+    /// issues either way.
+    pub prompt_injection_block_threshold: Option<f64>,
+    /// Whether to serve `admin_api::admin_router` (config inspection/patch
+    /// surface) alongside the Prometheus exporter. Off by default - only
+    /// enable once `admin_token` is set, since an admin surface with no auth
+    /// is only appropriate for local development.
+    pub admin_api_enabled: bool,
+    pub admin_api_port: u16,
+    /// Bearer token `admin_api::authorized` requires on every admin request.
+    /// Like `AgentConfig.api_key`, may be a `${env:...}`/`${file:...}`/
+    /// `${vault:...}` reference, resolved by `resolve_secrets`. `None` (the
+    /// default) disables auth entirely.
+    pub admin_token: Option<String>,
 }
 
 /// Log format
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
     Json,
@@ -105,9 +278,63 @@ pub enum LogFormat {
     Compact,
 }
 
+/// Name of the implicit profile that has no dedicated `config.{profile}.toml`
+/// override file - just the base `config.toml` plus environment/CLI layers.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Extensions `File::with_name`/`File::from` auto-detect by content format,
+/// in the order we probe for a bare `config`/`config.{profile}` basename.
+/// Covers plain `config.toml` plus the YAML/JSON config maps k8s deployments
+/// commonly mount.
+const CONFIG_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json"];
+
+/// Whether `{basename}.{ext}` exists on disk for any of `CONFIG_EXTENSIONS`.
+fn basename_file_exists(basename: &str) -> bool {
+    CONFIG_EXTENSIONS
+        .iter()
+        .any(|ext| Path::new(&format!("{basename}.{ext}")).exists())
+}
+
 impl Config {
-    /// Load configuration from files and environment
+    /// Load configuration from files and environment, using whichever
+    /// profile `META_AI_PROFILE` names (or `DEFAULT_PROFILE` if unset) and no
+    /// CLI overrides. Equivalent to `load_with_overrides(None, &HashMap::new())`.
     pub fn load() -> Result<Self, ConfigError> {
+        Self::load_with_overrides(None, &HashMap::new())
+    }
+
+    /// Load configuration, layering (lowest to highest precedence): the base
+    /// `config.toml`, a profile-specific `config.{profile}.toml` (`profile`
+    /// if given, else `META_AI_PROFILE`, else `DEFAULT_PROFILE` - which has
+    /// no dedicated file), environment variables (`META_AI__SECTION__FIELD`),
+    /// and finally `cli_overrides` (e.g. parsed `--set section.field=value`
+    /// flags). Each layer overrides the ones before it field-by-field.
+    /// Equivalent to `load_full(None, profile, cli_overrides)`.
+    pub fn load_with_overrides(profile: Option<&str>, cli_overrides: &HashMap<String, String>) -> Result<Self, ConfigError> {
+        Self::load_full(None, profile, cli_overrides)
+    }
+
+    /// Load configuration exactly as `load_with_overrides` does, except the
+    /// base config file is resolved from an explicit path rather than a bare
+    /// `config` basename search. `config_path` (if given, else
+    /// `META_AI_CONFIG`, else the usual `config.{toml,yaml,yml,json}`
+    /// basename search) may point at a `.toml`, `.yaml`/`.yml`, or `.json`
+    /// file - format is auto-detected from its extension, so a k8s
+    /// `ConfigMap` mounted as YAML or JSON works without renaming it.
+    pub fn load_full(
+        config_path: Option<&Path>,
+        profile: Option<&str>,
+        cli_overrides: &HashMap<String, String>,
+    ) -> Result<Self, ConfigError> {
+        let profile = profile
+            .map(str::to_string)
+            .or_else(|| std::env::var("META_AI_PROFILE").ok())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+        let explicit_path = config_path
+            .map(std::path::PathBuf::from)
+            .or_else(|| std::env::var_os("META_AI_CONFIG").map(std::path::PathBuf::from));
+
         let mut builder = ConfigBuilder::builder()
             .set_default("server.host", "0.0.0.0")?
             .set_default("server.port", 8080)?
@@ -116,48 +343,141 @@ impl Config {
             .set_default("orchestrator.task_queue_size", 1000)?
             .set_default("evaluation.accuracy_threshold", 0.9999)?
             .set_default("evaluation.bug_rate_threshold", 0.0005)?
-            .set_default("security.rate_limit_requests_per_minute", 60)?;
-        
-        // Load from config file if exists
-        if Path::new("config.toml").exists() {
+            .set_default("security.rate_limit_requests_per_minute", 60)?
+            .set_default("profile", profile.clone())?;
+
+        // Base config file: an explicit path (CLI `--config` or
+        // `META_AI_CONFIG`) if given, else the usual basename search.
+        if let Some(path) = &explicit_path {
+            builder = builder.add_source(File::from(path.as_path()));
+        } else if basename_file_exists("config") {
             builder = builder.add_source(File::with_name("config"));
         }
-        
-        // Override with environment variables
+
+        // Profile-specific overrides layered on top of the base file.
+        if profile != DEFAULT_PROFILE && basename_file_exists(&format!("config.{profile}")) {
+            builder = builder.add_source(File::with_name(&format!("config.{profile}")).required(false));
+        }
+
+        // Override with environment variables.
         builder = builder.add_source(
             Environment::with_prefix("META_AI")
                 .separator("__")
                 .try_parsing(true)
         );
-        
-        builder.build()?.try_deserialize()
+
+        // CLI flags take precedence over everything else.
+        for (key, value) in cli_overrides {
+            builder = builder.set_override(key.as_str(), value.as_str())?;
+        }
+
+        let mut config: Self = builder.build()?.try_deserialize()?;
+        config
+            .resolve_secrets()
+            .map_err(|e| ConfigError::Message(e.to_string()))?;
+        Ok(config)
     }
-    
-    /// Validate configuration
-    pub fn validate(&self) -> Result<(), Vec<String>> {
+
+    /// The JSON Schema `Config` itself (every section, field, and the
+    /// `model_catalog`/`agents` maps) must satisfy. Editors and CI can
+    /// validate a `config.toml`/`.yaml`/`.json` file against this before
+    /// deploying it, catching typos and type mismatches with a precise field
+    /// path instead of a runtime deserialize error.
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(Self)
+    }
+
+    /// The final merged config as JSON, with every `api_key` redacted to
+    /// `"[REDACTED]"`. Intended for debugging "where did this value come
+    /// from": diff this against another profile's `effective()` output, or
+    /// just read `profile` to confirm which layer actually won.
+    pub fn effective(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or(serde_json::Value::Null);
+        redact_secrets(&mut value);
+        value
+    }
+
+    /// Resolve every agent's `api_key` through `crate::secrets::resolve`,
+    /// replacing `${env:...}`/`${file:...}`/`${vault:...}`/`${aws-sm:...}`
+    /// references with the secret they point at. Plain literal keys pass
+    /// through unchanged. Called by `load` on first read, and again by
+    /// `crate::config_watcher::ConfigWatcher` on every reload so a rotated
+    /// secret (a new file's contents, a new Vault version) takes effect
+    /// without a restart.
+    pub fn resolve_secrets(&mut self) -> crate::error::Result<()> {
+        for agent in self.agents.values_mut() {
+            let resolved = crate::secrets::resolve(agent.api_key.expose_secret())?;
+            agent.api_key = Secret::new(resolved);
+        }
+        if let Some(admin_token) = &self.security.admin_token {
+            self.security.admin_token = Some(crate::secrets::resolve(admin_token)?);
+        }
+        Ok(())
+    }
+
+    /// Validate configuration. Checks every field path independently and
+    /// collects all failures rather than stopping at the first one, so a
+    /// misconfigured deployment can be fixed in one pass instead of
+    /// discovering each problem one `validate` call at a time.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
         let mut errors = Vec::new();
-        
-        // Validate accuracy threshold
-        if self.evaluation.accuracy_threshold < 0.0 || self.evaluation.accuracy_threshold > 1.0 {
-            errors.push("Accuracy threshold must be between 0.0 and 1.0".to_string());
-        }
-        
-        // Validate bug rate threshold
-        if self.evaluation.bug_rate_threshold < 0.0 || self.evaluation.bug_rate_threshold > 1.0 {
-            errors.push("Bug rate threshold must be between 0.0 and 1.0".to_string());
-        }
-        
-        // Validate at least one agent is enabled
+
+        if !(0.0..=1.0).contains(&self.evaluation.accuracy_threshold) {
+            invalid(&mut errors, "evaluation.accuracy_threshold", "must be between 0.0 and 1.0");
+        }
+        if !(0.0..=1.0).contains(&self.evaluation.bug_rate_threshold) {
+            invalid(&mut errors, "evaluation.bug_rate_threshold", "must be between 0.0 and 1.0");
+        }
+
         let enabled_agents = self.agents.values().filter(|a| a.enabled).count();
         if enabled_agents == 0 {
-            errors.push("At least one agent must be enabled".to_string());
+            invalid(&mut errors, "agents", "at least one agent must be enabled");
         }
-        
-        // Validate server configuration
+
         if self.server.port == 0 {
-            errors.push("Server port must be greater than 0".to_string());
+            invalid(&mut errors, "server.port", "must be greater than 0");
+        }
+        if self.server.request_timeout_ms == 0 {
+            invalid(&mut errors, "server.request_timeout_ms", "must be greater than 0");
+        }
+
+        if self.orchestrator.max_concurrent_tasks == 0 {
+            invalid(&mut errors, "orchestrator.max_concurrent_tasks", "must be greater than 0");
+        }
+        if self.orchestrator.task_queue_size == 0 {
+            invalid(&mut errors, "orchestrator.task_queue_size", "must be greater than 0");
+        }
+        if self.orchestrator.default_timeout_ms == 0 {
+            invalid(&mut errors, "orchestrator.default_timeout_ms", "must be greater than 0");
+        }
+        if self.orchestrator.retry_delay_ms >= self.orchestrator.default_timeout_ms {
+            invalid(
+                &mut errors,
+                "orchestrator.retry_delay_ms",
+                "must be less than orchestrator.default_timeout_ms, or a retry can never complete before the task times out",
+            );
         }
-        
+
+        validate_url(&mut errors, "rag.qdrant_url", &self.rag.qdrant_url);
+        if self.rag.embedding_dimension == 0 {
+            invalid(&mut errors, "rag.embedding_dimension", "must be greater than 0");
+        }
+        if self.rag.chunk_overlap >= self.rag.chunk_size {
+            invalid(&mut errors, "rag.chunk_overlap", "must be less than rag.chunk_size");
+        }
+
+        if let Some(otlp_endpoint) = &self.observability.otlp_endpoint {
+            validate_url(&mut errors, "observability.otlp_endpoint", otlp_endpoint);
+        }
+
+        for (provider, agent) in &self.agents {
+            let prefix = format!("agents.{}", provider.as_str());
+            validate_url(&mut errors, &format!("{prefix}.base_url"), &agent.base_url);
+            if agent.timeout_ms == 0 {
+                invalid(&mut errors, &format!("{prefix}.timeout_ms"), "must be greater than 0");
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -166,6 +486,32 @@ impl Config {
     }
 }
 
+/// One `Config::validate` failure: the dotted path of the invalid field and
+/// why it failed, so a caller can point an operator at the exact setting to
+/// fix instead of parsing a sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+fn invalid(errors: &mut Vec<ValidationError>, field: &str, message: impl Into<String>) {
+    errors.push(ValidationError { field: field.to_string(), message: message.into() });
+}
+
+/// Record a `ValidationError` at `field` if `value` doesn't parse as a URL.
+fn validate_url(errors: &mut Vec<ValidationError>, field: &str, value: &str) {
+    if let Err(e) = reqwest::Url::parse(value) {
+        invalid(errors, field, format!("not a valid URL: {e}"));
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -193,6 +539,11 @@ impl Default for Config {
                 chunk_size: 512,
                 chunk_overlap: 128,
                 top_k: 5,
+                fusion_strategy: FusionStrategy::default(),
+                embedding_cache_size: 10_000,
+                embedding_cache_ttl_secs: Some(3600),
+                quantization: None,
+                embedding_cache_f16: false,
             },
             evaluation: EvaluationConfig {
                 accuracy_threshold: 0.9999,
@@ -216,7 +567,13 @@ impl Default for Config {
                 rate_limit_requests_per_minute: 60,
                 request_signature_validation: true,
                 sandbox_enabled: true,
+                prompt_injection_block_threshold: Some(0.7),
+                admin_api_enabled: false,
+                admin_api_port: 9091,
+                admin_token: None,
             },
+            model_catalog: Vec::new(),
+            profile: DEFAULT_PROFILE.to_string(),
         }
     }
 }
\ No newline at end of file
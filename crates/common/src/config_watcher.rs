@@ -0,0 +1,148 @@
+//! Actual hot-reload for `Config`: `ConfigWatcher::spawn` watches a config
+//! file (via `notify`) and, on every change, reloads and re-validates it,
+//! publishing the new `Config` alongside a field-level diff on a
+//! `tokio::sync::watch` channel so subsystems (dispatcher limits, agent
+//! enablement, log level, ...) can apply the new values without a restart.
+//! A reload that fails to parse or fails `Config::validate` is logged and
+//! dropped, so subscribers never observe an invalid config.
+
+use crate::config::Config;
+use notify::{RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+
+/// A single field-level difference between two successive reloads. `path`
+/// is a dotted JSON field path (e.g. `"orchestrator.max_concurrent_tasks"`).
+/// `agents.*.api_key` values are always redacted, never the raw secret.
+#[derive(Debug, Clone)]
+pub struct ConfigDiff {
+    pub path: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Published on `ConfigWatcher::subscribe`'s channel every time a valid
+/// reload changes at least one field.
+#[derive(Debug, Clone)]
+pub struct ConfigUpdate {
+    pub config: Config,
+    pub diffs: Vec<ConfigDiff>,
+}
+
+/// Watches a config file for changes and republishes a freshly reloaded,
+/// re-validated `Config` (plus what changed) to every subscriber.
+pub struct ConfigWatcher {
+    sender: watch::Sender<ConfigUpdate>,
+}
+
+impl ConfigWatcher {
+    /// Spawn a background watcher over `path` (normally `"config.toml"`),
+    /// seeded with `initial` (normally the result of the `Config::load` that
+    /// ran at startup). Returns the watcher, for `subscribe`, paired with
+    /// the underlying `notify::RecommendedWatcher` - which must be kept
+    /// alive for as long as watching should continue, since dropping it
+    /// stops the watch.
+    pub fn spawn(
+        path: impl Into<PathBuf>,
+        initial: Config,
+    ) -> notify::Result<(Self, notify::RecommendedWatcher)> {
+        let path = path.into();
+        let (sender, _receiver) = watch::channel(ConfigUpdate { config: initial.clone(), diffs: Vec::new() });
+
+        let (fs_tx, fs_rx) = channel();
+        let mut watcher = notify::recommended_watcher(fs_tx)?;
+        watcher.watch(&path, RecursiveMode::NonRecursive)?;
+
+        let reload_sender = sender.clone();
+        let watched_path = path.clone();
+        std::thread::spawn(move || {
+            let mut previous = initial;
+            for event in fs_rx {
+                let Ok(event) = event else { continue };
+                if !matches!(event.kind, notify::EventKind::Modify(_) | notify::EventKind::Create(_)) {
+                    continue;
+                }
+
+                // `Config::load` already re-resolves every agent's `api_key`
+                // (env/file/vault/aws-sm reference) as part of deserializing,
+                // so a rotated secret takes effect on this same reload.
+                let reloaded = match Config::load() {
+                    Ok(reloaded) => reloaded,
+                    Err(e) => {
+                        error!("failed to reload config from {watched_path:?}: {e}");
+                        continue;
+                    }
+                };
+
+                if let Err(errors) = reloaded.validate() {
+                    let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+                    warn!("config reload from {watched_path:?} failed validation, keeping previous config: {errors}");
+                    continue;
+                }
+
+                let diffs = diff(&previous, &reloaded);
+                if diffs.is_empty() {
+                    continue;
+                }
+
+                info!("config reloaded from {watched_path:?}: {} field(s) changed", diffs.len());
+                previous = reloaded.clone();
+                if reload_sender.send(ConfigUpdate { config: reloaded, diffs }).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok((Self { sender }, watcher))
+    }
+
+    /// Subscribe to config changes: the returned receiver always holds the
+    /// most recently published valid `ConfigUpdate`, and `changed()`
+    /// resolves whenever a new one lands.
+    pub fn subscribe(&self) -> watch::Receiver<ConfigUpdate> {
+        self.sender.subscribe()
+    }
+}
+
+/// Field that, if it appears as the last segment of a diff path, is always
+/// redacted rather than compared/printed in plain text.
+const REDACTED_FIELD: &str = "api_key";
+
+/// Structural diff between `old` and `new`, via their JSON representations
+/// (both derive `Serialize`), rather than hand-listing every `Config` field
+/// - the same "lean on serde instead of a bespoke per-field comparator"
+/// tradeoff the rest of this config module already makes.
+fn diff(old: &Config, new: &Config) -> Vec<ConfigDiff> {
+    let old_value = serde_json::to_value(old).unwrap_or(serde_json::Value::Null);
+    let new_value = serde_json::to_value(new).unwrap_or(serde_json::Value::Null);
+    let mut diffs = Vec::new();
+    diff_values(String::new(), &old_value, &new_value, &mut diffs);
+    diffs
+}
+
+fn diff_values(path: String, old: &serde_json::Value, new: &serde_json::Value, diffs: &mut Vec<ConfigDiff>) {
+    if let (serde_json::Value::Object(old_map), serde_json::Value::Object(new_map)) = (old, new) {
+        let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let child_path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+            let null = serde_json::Value::Null;
+            diff_values(child_path, old_map.get(key).unwrap_or(&null), new_map.get(key).unwrap_or(&null), diffs);
+        }
+        return;
+    }
+
+    if old == new {
+        return;
+    }
+
+    let (old_str, new_str) = if path.ends_with(REDACTED_FIELD) {
+        ("[REDACTED]".to_string(), "[REDACTED]".to_string())
+    } else {
+        (old.to_string(), new.to_string())
+    };
+    diffs.push(ConfigDiff { path, old: old_str, new: new_str });
+}
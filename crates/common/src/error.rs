@@ -52,11 +52,28 @@ pub enum Error {
     /// IO errors
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    
+
+    /// Metrics registration errors (e.g. `Registry::register` called twice
+    /// for the same metric name).
+    #[error("Metrics error: {0}")]
+    Metrics(#[from] prometheus::Error),
+
     /// Generic errors
     #[error("Internal error: {0}")]
     Internal(String),
-    
+
+    /// A scheduler's queue is at capacity. Carries the queue depth observed
+    /// at rejection time so callers can decide how hard to back off.
+    #[error("Task queue is full ({depth}/{capacity})")]
+    QueueFull { depth: usize, capacity: usize },
+
+    /// A dispatcher is at capacity and the caller waited longer than its
+    /// configured acquisition timeout for a free slot. Carries the position
+    /// the request held in the wait queue so API layers can translate this
+    /// into a 429/503 with a `Retry-After`-style hint.
+    #[error("Dispatcher overloaded: waited at queue position {queue_position} of {capacity}")]
+    Overloaded { queue_position: usize, capacity: usize },
+
     /// Unknown errors
     #[error("Unknown error: {0}")]
     Unknown(#[from] anyhow::Error),
@@ -70,17 +87,21 @@ impl Error {
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            Error::Network(_) | Error::Timeout(_) | Error::RateLimit(_)
+            Error::Network(_)
+                | Error::Timeout(_)
+                | Error::RateLimit(_)
+                | Error::QueueFull { .. }
+                | Error::Overloaded { .. }
         )
     }
-    
+
     /// Get error severity for metrics
     pub fn severity(&self) -> ErrorSeverity {
         match self {
             Error::Config(_) | Error::Auth(_) => ErrorSeverity::Critical,
             Error::Agent(_) | Error::Orchestration(_) => ErrorSeverity::High,
             Error::Timeout(_) | Error::RateLimit(_) => ErrorSeverity::Medium,
-            Error::Validation(_) => ErrorSeverity::Low,
+            Error::Validation(_) | Error::QueueFull { .. } | Error::Overloaded { .. } => ErrorSeverity::Low,
             _ => ErrorSeverity::Medium,
         }
     }
@@ -8,13 +8,18 @@
 
 pub mod error;
 pub mod metrics;
+pub mod metrics_server;
+pub mod schema;
 pub mod types;
 pub mod config;
+pub mod config_watcher;
+pub mod secrets;
 pub mod telemetry;
 
 pub use error::{Error, Result};
 pub use types::*;
 pub use config::Config;
+pub use config_watcher::{ConfigDiff, ConfigUpdate, ConfigWatcher};
 
 /// Re-export commonly used external types
 pub use uuid::Uuid;
@@ -74,6 +74,81 @@ pub static BUG_RATE_GAUGE: Lazy<GaugeVec> = Lazy::new(|| {
     ).unwrap()
 });
 
+/// Whether dispatch is currently being throttled by `ResourceConstraints`
+/// (1.0 = throttled, 0.0 = not), by the resource that triggered it.
+pub static THROTTLE_GAUGE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "meta_ai_dispatch_throttled",
+        "Whether dispatch is currently throttled by a resource constraint",
+        &["resource"]
+    ).unwrap()
+});
+
+/// Utilization (`active_requests / total_capacity`, `0.0..=1.0`) of a
+/// dispatcher shard, by shard index. See `MetaAIOrchestrator::dispatch_stats`.
+pub static DISPATCH_SHARD_UTILIZATION: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "meta_ai_dispatch_shard_utilization",
+        "Utilization of a dispatcher shard",
+        &["shard"]
+    ).unwrap()
+});
+
+/// Number of tasks currently sitting in a scheduler's queue, by priority.
+/// Lets an operator alert on saturation before `PriorityScheduler::schedule_task`
+/// starts rejecting work with `Error::QueueFull`.
+pub static QUEUE_DEPTH: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "meta_ai_queue_depth",
+        "Number of tasks currently queued, by priority",
+        &["priority"]
+    ).unwrap()
+});
+
+/// Time a task spent queued before a scheduler's `next_task` dequeued it, by
+/// priority.
+pub static SCHEDULING_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "meta_ai_scheduling_latency_seconds",
+        "Time a task spent queued before being dequeued",
+        &["priority"],
+        vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0]
+    ).unwrap()
+});
+
+/// Total number of times a task has been handed back to a scheduler via
+/// `TaskScheduler::requeue_task`, by priority.
+pub static REQUEUE_COUNTER: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "meta_ai_task_requeues_total",
+        "Total number of task requeues",
+        &["priority"]
+    ).unwrap()
+});
+
+/// Cumulative USD cost of completed requests, by provider, model, and task
+/// type. Computed from the model catalog's per-model pricing where one is
+/// registered, otherwise the coarser per-provider pricing (see
+/// `TaskDispatcher::estimate_cost`).
+pub static COST_COUNTER: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "meta_ai_cost_usd_total",
+        "Total USD cost of completed requests",
+        &["provider", "model", "task_type"]
+    ).unwrap()
+});
+
+/// Cumulative USD cost of completed requests, by tenant (`Task.tenant`,
+/// `"none"` for tasks submitted without one). A gauge rather than a counter
+/// since a caller may also want to reset it between billing periods.
+pub static TENANT_COST_GAUGE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "meta_ai_tenant_cost_usd_total",
+        "Total USD cost of completed requests, by tenant",
+        &["tenant"]
+    ).unwrap()
+});
+
 /// Initialize all metrics
 pub fn init_metrics() -> Result<()> {
     METRICS_REGISTRY.register(Box::new(REQUEST_COUNTER.clone()))?;
@@ -83,7 +158,14 @@ pub fn init_metrics() -> Result<()> {
     METRICS_REGISTRY.register(Box::new(ERROR_COUNTER.clone()))?;
     METRICS_REGISTRY.register(Box::new(ACCURACY_GAUGE.clone()))?;
     METRICS_REGISTRY.register(Box::new(BUG_RATE_GAUGE.clone()))?;
-    
+    METRICS_REGISTRY.register(Box::new(THROTTLE_GAUGE.clone()))?;
+    METRICS_REGISTRY.register(Box::new(DISPATCH_SHARD_UTILIZATION.clone()))?;
+    METRICS_REGISTRY.register(Box::new(QUEUE_DEPTH.clone()))?;
+    METRICS_REGISTRY.register(Box::new(SCHEDULING_LATENCY.clone()))?;
+    METRICS_REGISTRY.register(Box::new(REQUEUE_COUNTER.clone()))?;
+    METRICS_REGISTRY.register(Box::new(COST_COUNTER.clone()))?;
+    METRICS_REGISTRY.register(Box::new(TENANT_COST_GAUGE.clone()))?;
+
     Ok(())
 }
 
@@ -103,6 +185,21 @@ pub trait MetricsCollector {
     
     /// Update bug rate
     fn update_bug_rate(&self, provider: &str, error_type: &str, rate: f64);
+
+    /// Record whether dispatch is currently throttled by a resource
+    /// constraint (e.g. `"tokens_per_minute"`, `"memory"`).
+    fn record_throttle(&self, resource: &str, throttled: bool);
+
+    /// Record a dispatcher shard's utilization (`0.0..=1.0`), by its index
+    /// in `LoadBalancer`.
+    fn record_dispatch_shard_utilization(&self, shard: usize, utilization: f64);
+
+    /// Record a completed request's USD cost, by provider, model, and task
+    /// type.
+    fn record_cost(&self, provider: &str, model: &str, task_type: &str, cost_usd: f64);
+
+    /// Add a completed request's USD cost to `tenant`'s running total.
+    fn record_tenant_cost(&self, tenant: &str, cost_usd: f64);
 }
 
 /// Default metrics collector implementation
@@ -147,4 +244,28 @@ impl MetricsCollector for DefaultMetricsCollector {
             .with_label_values(&[provider, error_type])
             .set(rate);
     }
+
+    fn record_throttle(&self, resource: &str, throttled: bool) {
+        THROTTLE_GAUGE
+            .with_label_values(&[resource])
+            .set(if throttled { 1.0 } else { 0.0 });
+    }
+
+    fn record_dispatch_shard_utilization(&self, shard: usize, utilization: f64) {
+        DISPATCH_SHARD_UTILIZATION
+            .with_label_values(&[&shard.to_string()])
+            .set(utilization);
+    }
+
+    fn record_cost(&self, provider: &str, model: &str, task_type: &str, cost_usd: f64) {
+        COST_COUNTER
+            .with_label_values(&[provider, model, task_type])
+            .inc_by(cost_usd);
+    }
+
+    fn record_tenant_cost(&self, tenant: &str, cost_usd: f64) {
+        TENANT_COST_GAUGE
+            .with_label_values(&[tenant])
+            .add(cost_usd);
+    }
 }
\ No newline at end of file
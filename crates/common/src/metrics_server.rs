@@ -0,0 +1,41 @@
+//! Lightweight HTTP exporter for `METRICS_REGISTRY`. Binds
+//! `ObservabilityConfig.metrics_port` and serves `GET /metrics` in the
+//! standard Prometheus text exposition format, including process metrics
+//! (CPU, memory, file descriptors, ...) alongside the application's own.
+//! Started from the main binary once telemetry is initialized.
+
+use crate::error::Result;
+use crate::metrics::METRICS_REGISTRY;
+use axum::{http::StatusCode, routing::get, Router};
+use prometheus::{process_collector::ProcessCollector, Encoder, TextEncoder};
+use std::net::SocketAddr;
+
+/// Register the process collector with `METRICS_REGISTRY` alongside
+/// `crate::metrics::init_metrics`'s application metrics. A registry only
+/// accepts one collector of a given name, so this is safe to call more than
+/// once - a duplicate registration is silently ignored.
+fn register_process_metrics() {
+    let _ = METRICS_REGISTRY.register(Box::new(ProcessCollector::for_self()));
+}
+
+async fn metrics_handler() -> (StatusCode, String) {
+    let metric_families = METRICS_REGISTRY.gather();
+    let mut buffer = Vec::new();
+    match TextEncoder::new().encode(&metric_families, &mut buffer) {
+        Ok(()) => (StatusCode::OK, String::from_utf8_lossy(&buffer).into_owned()),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("failed to encode metrics: {e}")),
+    }
+}
+
+/// Serve `METRICS_REGISTRY` as `GET /metrics` on `0.0.0.0:{port}` until the
+/// process exits or the returned future is dropped. Call
+/// `crate::metrics::init_metrics` first so the application's own metrics are
+/// registered before this adds process metrics on top.
+pub async fn serve_metrics(port: u16) -> Result<()> {
+    register_process_metrics();
+    let app = Router::new().route("/metrics", get(metrics_handler));
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
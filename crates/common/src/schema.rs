@@ -0,0 +1,55 @@
+//! JSON schema validation for structured agent output
+
+use crate::error::{Error, Result};
+use crate::types::ResponseFormat;
+
+/// Validate `content` against `format`, returning the parsed JSON value when
+/// the format requires one. Text format is always valid.
+pub fn validate_response(format: &ResponseFormat, content: &str) -> Result<()> {
+    match format {
+        ResponseFormat::Text => Ok(()),
+        ResponseFormat::JsonSchema { schema, .. } => {
+            let value: serde_json::Value = serde_json::from_str(content)
+                .map_err(|e| Error::Validation(format!("response is not valid JSON: {e}")))?;
+
+            let compiled = jsonschema::JSONSchema::compile(schema)
+                .map_err(|e| Error::Validation(format!("invalid response schema: {e}")))?;
+
+            if let Err(errors) = compiled.validate(&value) {
+                let messages: Vec<String> = errors.map(|e| e.to_string()).collect();
+                return Err(Error::Validation(format!(
+                    "response does not match schema: {}",
+                    messages.join("; ")
+                )));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_format_always_valid() {
+        assert!(validate_response(&ResponseFormat::Text, "anything").is_ok());
+    }
+
+    #[test]
+    fn json_schema_rejects_mismatched_content() {
+        let format = ResponseFormat::JsonSchema {
+            name: "answer".to_string(),
+            schema: serde_json::json!({
+                "type": "object",
+                "required": ["answer"],
+                "properties": { "answer": { "type": "string" } }
+            }),
+        };
+
+        assert!(validate_response(&format, r#"{"answer": "42"}"#).is_ok());
+        assert!(validate_response(&format, r#"{"wrong": "field"}"#).is_err());
+        assert!(validate_response(&format, "not json").is_err());
+    }
+}
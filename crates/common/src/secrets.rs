@@ -0,0 +1,145 @@
+//! Indirection for secret-valued config fields (currently just
+//! `AgentConfig.api_key`): instead of a literal key sitting in `config.toml`,
+//! the field can hold a reference like `${env:OPENAI_API_KEY}`,
+//! `${file:/run/secrets/openai}`, `${vault:secret/data/openai#api_key}`, or
+//! `${aws-sm:prod/openai-api-key}`, resolved by [`resolve`] at load/reload
+//! time. Because resolution re-runs on every `Config::load`, rotating the
+//! underlying secret (a new file contents, a new Vault version) takes effect
+//! on the next reload picked up by `crate::config_watcher::ConfigWatcher`
+//! without restarting the process - the literal `${...}` reference in
+//! `config.toml` never needs to change.
+
+use crate::error::{Error, Result};
+use std::path::PathBuf;
+
+/// A parsed `${backend:locator}` secret reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SecretRef {
+    Env(String),
+    File(PathBuf),
+    /// `path` is the Vault KV path (e.g. `secret/data/openai`), `field` the
+    /// key to read out of that path's data map (e.g. `api_key`).
+    Vault { path: String, field: String },
+    AwsSecretsManager(String),
+}
+
+/// Parse `raw` as a `${backend:locator}` reference. Anything else (including
+/// a plain literal key with no `${...}` wrapper, for backward compatibility
+/// with existing `config.toml` files) returns `None`.
+fn parse(raw: &str) -> Option<SecretRef> {
+    let inner = raw.strip_prefix("${")?.strip_suffix('}')?;
+    let (backend, locator) = inner.split_once(':')?;
+    match backend {
+        "env" => Some(SecretRef::Env(locator.to_string())),
+        "file" => Some(SecretRef::File(PathBuf::from(locator))),
+        "vault" => {
+            let (path, field) = locator.split_once('#')?;
+            Some(SecretRef::Vault { path: path.to_string(), field: field.to_string() })
+        }
+        "aws-sm" => Some(SecretRef::AwsSecretsManager(locator.to_string())),
+        _ => None,
+    }
+}
+
+/// Resolve a single `${backend:locator}` reference to its plaintext value.
+/// A plain literal (no `${...}` wrapper) is returned unchanged, so existing
+/// configs that embed a literal key keep working.
+pub fn resolve(raw: &str) -> Result<String> {
+    let Some(secret_ref) = parse(raw) else {
+        return Ok(raw.to_string());
+    };
+
+    match secret_ref {
+        SecretRef::Env(name) => std::env::var(&name)
+            .map_err(|_| Error::Config(format!("secret env var {name} is not set"))),
+        SecretRef::File(path) => std::fs::read_to_string(&path)
+            .map(|contents| contents.trim().to_string())
+            .map_err(|e| Error::Config(format!("failed to read secret file {path:?}: {e}"))),
+        SecretRef::Vault { path, field } => resolve_vault(&path, &field),
+        SecretRef::AwsSecretsManager(secret_id) => Err(Error::Config(format!(
+            "secret {secret_id} requires the aws-sm backend, which needs the AWS Secrets Manager \
+             SDK; this workspace does not vendor it, so aws-sm references cannot be resolved yet - \
+             use env, file, or vault instead"
+        ))),
+    }
+}
+
+/// Read `field` out of a Vault KV v2 secret at `path`, using the standard
+/// `VAULT_ADDR`/`VAULT_TOKEN` environment variables the Vault CLI itself
+/// reads. Falls back to a KV v1 response shape (`data.{field}` rather than
+/// `data.data.{field}`) if the v2 shape isn't present.
+///
+/// Issues the request with `reqwest::blocking`, since `resolve`/`resolve_vault`
+/// are called synchronously from `Config::load` (including from plain, non-Tokio
+/// threads such as `ConfigWatcher`'s reload loop). When a Tokio runtime happens
+/// to already be running on the calling thread (e.g. `Config::load` from
+/// `#[tokio::main] async fn main`), building and driving a blocking client
+/// directly would panic, so that case goes through `tokio::task::block_in_place`
+/// instead, which lets the blocking call run without stalling the runtime.
+fn resolve_vault(path: &str, field: &str) -> Result<String> {
+    let addr = std::env::var("VAULT_ADDR")
+        .map_err(|_| Error::Config("VAULT_ADDR must be set to resolve vault secrets".to_string()))?;
+    let token = std::env::var("VAULT_TOKEN")
+        .map_err(|_| Error::Config("VAULT_TOKEN must be set to resolve vault secrets".to_string()))?;
+
+    let url = format!("{}/v1/{}", addr.trim_end_matches('/'), path);
+    let do_request = || -> Result<serde_json::Value> {
+        reqwest::blocking::Client::new()
+            .get(&url)
+            .header("X-Vault-Token", token)
+            .send()
+            .map_err(|e| Error::Config(format!("vault request to {path} failed: {e}")))?
+            .error_for_status()
+            .map_err(|e| Error::Config(format!("vault returned an error for {path}: {e}")))?
+            .json()
+            .map_err(|e| Error::Config(format!("vault response for {path} was not valid JSON: {e}")))
+    };
+
+    let response: serde_json::Value = if tokio::runtime::Handle::try_current().is_ok() {
+        tokio::task::block_in_place(do_request)?
+    } else {
+        do_request()?
+    };
+
+    let value = response
+        .pointer(&format!("/data/data/{field}"))
+        .or_else(|| response.pointer(&format!("/data/{field}")))
+        .ok_or_else(|| Error::Config(format!("vault secret {path} has no field {field}")))?;
+
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::Config(format!("vault secret {path}#{field} is not a string")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_value_passes_through() {
+        assert_eq!(resolve("sk-literal-key").unwrap(), "sk-literal-key");
+    }
+
+    #[test]
+    fn env_reference_resolves() {
+        std::env::set_var("META_AI_SECRETS_TEST_KEY", "resolved-value");
+        assert_eq!(resolve("${env:META_AI_SECRETS_TEST_KEY}").unwrap(), "resolved-value");
+        std::env::remove_var("META_AI_SECRETS_TEST_KEY");
+    }
+
+    #[test]
+    fn missing_env_reference_errors() {
+        assert!(resolve("${env:META_AI_SECRETS_TEST_MISSING}").is_err());
+    }
+
+    #[test]
+    fn unsupported_aws_sm_reference_errors() {
+        assert!(resolve("${aws-sm:prod/openai-api-key}").is_err());
+    }
+
+    #[test]
+    fn unknown_backend_is_treated_as_literal() {
+        assert_eq!(resolve("${nope:x}").unwrap(), "${nope:x}");
+    }
+}
@@ -15,7 +15,7 @@ pub type AgentId = String;
 pub type TaskId = Uuid;
 
 /// LLM provider types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum LlmProvider {
     OpenAI,
@@ -51,8 +51,32 @@ pub enum TaskStatus {
     Timeout,
 }
 
+impl TaskStatus {
+    /// Whether this is a finished state. Once terminal, a task's status
+    /// never changes again.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled | Self::Timeout)
+    }
+
+    /// Whether moving from `self` to `next` is a legal state transition.
+    /// Terminal states cannot transition anywhere, `Pending` can move to
+    /// `Running` or expire/cancel before ever running, and `Running` can
+    /// move to a terminal state or back to `Pending` when a retryable
+    /// failure is requeued.
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        if self.is_terminal() {
+            return false;
+        }
+        match (self, next) {
+            (Self::Pending, Self::Running | Self::Cancelled | Self::Timeout) => true,
+            (Self::Running, Self::Completed | Self::Failed | Self::Cancelled | Self::Timeout | Self::Pending) => true,
+            _ => false,
+        }
+    }
+}
+
 /// Task priority
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Priority {
     Low = 0,
@@ -76,6 +100,40 @@ pub struct Task {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub metadata: Metadata,
+    /// Overrides `OrchestratorConfig.task_timeout` for this task alone,
+    /// measured from `created_at`. `None` falls back to the orchestrator's
+    /// global default.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Tenant or project this task is billed/attributed to. Used by
+    /// `FairShareScheduler` to interleave tasks across tenants so that one
+    /// noisy tenant can't monopolize the queue. `None` is its own implicit
+    /// tenant (tasks without one are grouped together).
+    #[serde(default)]
+    pub tenant: Option<String>,
+    /// Hold this task out of dispatch until this timestamp. Used for "run in
+    /// an hour" workflows and for retry backoff at the scheduling layer.
+    /// `None` means eligible as soon as scheduled.
+    #[serde(default)]
+    pub not_before: Option<DateTime<Utc>>,
+    /// Caller-supplied key identifying this submission. Submitting the same
+    /// key twice within `OrchestratorConfig.idempotency_window` returns the
+    /// original task's id instead of enqueuing a duplicate. `None` disables
+    /// dedup for this task.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Other tasks that must reach `TaskStatus::Completed` before this one
+    /// becomes eligible for scheduling. A lightweight alternative to a full
+    /// `TaskDag` for callers who just need "run after these finish".
+    #[serde(default)]
+    pub depends_on: Vec<TaskId>,
+    /// How many times this task has already been handed back to the
+    /// scheduler via `TaskScheduler::requeue_task` after a retryable
+    /// failure. Capped by `OrchestratorConfig.max_requeue_attempts`, past
+    /// which the task is left `Failed` and recorded in the dead-letter
+    /// queue instead of being requeued again.
+    #[serde(default)]
+    pub requeue_attempts: u32,
 }
 
 impl Default for Task {
@@ -91,6 +149,12 @@ impl Default for Task {
             created_at: now,
             updated_at: now,
             metadata: HashMap::new(),
+            timeout_ms: None,
+            tenant: None,
+            not_before: None,
+            idempotency_key: None,
+            depends_on: Vec::new(),
+            requeue_attempts: 0,
         }
     }
 }
@@ -104,7 +168,28 @@ pub struct LlmRequest {
     pub prompt: String,
     pub parameters: LlmParameters,
     pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
     pub metadata: Metadata,
+    /// Identifies a multi-turn conversation. Requests sharing a `session_id`
+    /// are routed to the same provider by sticky-session-aware dispatchers,
+    /// so follow-up turns can reuse provider-side cached context. `None`
+    /// means no affinity is requested.
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// A multimodal attachment included with an LLM request.
+///
+/// Only image attachments are supported today; agents without
+/// `AgentCapabilities.supports_vision` must reject requests that carry any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Attachment {
+    /// Inline base64-encoded image data.
+    ImageBase64 { media_type: String, data: String },
+    /// A URL pointing to an image.
+    ImageUrl { url: String },
 }
 
 /// LLM parameters
@@ -117,6 +202,8 @@ pub struct LlmParameters {
     pub presence_penalty: Option<f32>,
     pub stop_sequences: Option<Vec<String>>,
     pub stream: bool,
+    #[serde(default)]
+    pub response_format: Option<ResponseFormat>,
 }
 
 impl Default for LlmParameters {
@@ -129,10 +216,24 @@ impl Default for LlmParameters {
             presence_penalty: None,
             stop_sequences: None,
             stream: false,
+            response_format: None,
         }
     }
 }
 
+/// Desired shape of an agent's response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// Free-form text, the default.
+    Text,
+    /// The response must be valid JSON conforming to `schema`.
+    JsonSchema {
+        name: String,
+        schema: serde_json::Value,
+    },
+}
+
 /// LLM response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmResponse {
@@ -163,6 +264,10 @@ pub struct Document {
     pub embedding: Option<Embedding>,
     pub metadata: Metadata,
     pub created_at: DateTime<Utc>,
+    /// When this document should be treated as expired and swept out of the
+    /// vector store. `None` means it never expires on its own.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Search result
@@ -2,9 +2,13 @@
 
 use async_trait::async_trait;
 use meta_ai_common::{
-    types::{LlmProvider, LlmRequest, LlmResponse},
-    error::Result,
+    types::{Embedding, LlmProvider, LlmRequest, LlmResponse},
+    error::{Error, Result},
 };
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 /// Agent trait for LLM providers
 #[async_trait]
@@ -29,6 +33,27 @@ pub trait Agent: Send + Sync {
     
     /// Get rate limit info
     async fn rate_limit_info(&self) -> Result<RateLimitInfo>;
+
+    /// Embed text into a vector, for agents whose capabilities report
+    /// `supports_embeddings`. Agents that don't support it keep the default,
+    /// which rejects with a clear error.
+    async fn embed(&self, _text: &str) -> Result<Embedding> {
+        Err(Error::Agent(format!("{} does not support embeddings", self.name())))
+    }
+
+    /// Embed a batch of texts in one call, in the same order as `texts`, for
+    /// providers whose embeddings API accepts multiple inputs per request
+    /// (see `dispatcher::BatchingDispatcher` in the orchestrator crate,
+    /// which coalesces concurrent single-text requests into calls like this
+    /// one). The default falls back to one `embed` call per text for agents
+    /// that don't override it with a real batched call.
+    async fn batch_embed(&self, texts: Vec<&str>) -> Result<Vec<Embedding>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.embed(text).await?);
+        }
+        Ok(embeddings)
+    }
 }
 
 /// Agent capabilities
@@ -40,6 +65,7 @@ pub struct AgentCapabilities {
     pub supports_vision: bool,
     pub supports_code_execution: bool,
     pub supports_web_search: bool,
+    pub supports_embeddings: bool,
     pub context_window: u32,
     pub languages: Vec<String>,
     pub specializations: Vec<String>,
@@ -54,6 +80,7 @@ impl Default for AgentCapabilities {
             supports_vision: false,
             supports_code_execution: false,
             supports_web_search: false,
+            supports_embeddings: false,
             context_window: 128000,
             languages: vec!["en".to_string()],
             specializations: vec!["general".to_string()],
@@ -72,6 +99,90 @@ pub struct AgentHealth {
     pub last_error: Option<String>,
 }
 
+/// Smoothing factor for the latency EWMA maintained by `AgentHealthTracker`.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Window over which request counts and error rates are computed.
+const HEALTH_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rolling-window health tracker shared by agent implementations.
+///
+/// Agents update this after every call to `submit`, and `health_check` simply
+/// snapshots it. This keeps `AgentHealth` (and therefore `LowestLatency`
+/// selection) grounded in observed behavior instead of hardcoded numbers.
+pub struct AgentHealthTracker {
+    ewma_latency_ms: Mutex<f64>,
+    recent: Mutex<VecDeque<(Instant, bool)>>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl AgentHealthTracker {
+    pub fn new() -> Self {
+        Self {
+            ewma_latency_ms: Mutex::new(0.0),
+            recent: Mutex::new(VecDeque::new()),
+            last_error: Mutex::new(None),
+        }
+    }
+
+    /// Record the outcome of a completed request.
+    pub fn record(&self, latency_ms: f64, error: Option<&str>) {
+        {
+            let mut ewma = self.ewma_latency_ms.lock();
+            *ewma = if *ewma <= 0.0 {
+                latency_ms
+            } else {
+                LATENCY_EWMA_ALPHA * latency_ms + (1.0 - LATENCY_EWMA_ALPHA) * *ewma
+            };
+        }
+
+        let now = Instant::now();
+        let mut recent = self.recent.lock();
+        recent.push_back((now, error.is_some()));
+        while let Some((ts, _)) = recent.front() {
+            if now.duration_since(*ts) > HEALTH_WINDOW {
+                recent.pop_front();
+            } else {
+                break;
+            }
+        }
+        drop(recent);
+
+        if let Some(message) = error {
+            *self.last_error.lock() = Some(message.to_string());
+        }
+    }
+
+    /// Snapshot the tracker into an `AgentHealth` reading.
+    pub fn snapshot(&self) -> AgentHealth {
+        let recent = self.recent.lock();
+        let total = recent.len();
+        let errors = recent.iter().filter(|(_, is_error)| *is_error).count();
+        let requests_per_minute = total as f64 / (HEALTH_WINDOW.as_secs_f64() / 60.0);
+        let error_rate = if total == 0 {
+            0.0
+        } else {
+            errors as f64 / total as f64
+        };
+        let average_latency_ms = *self.ewma_latency_ms.lock();
+
+        AgentHealth {
+            healthy: error_rate < 0.5,
+            latency_ms: recent.back().map(|_| average_latency_ms),
+            requests_per_minute,
+            average_latency_ms,
+            error_rate,
+            last_error: self.last_error.lock().clone(),
+        }
+    }
+}
+
+impl Default for AgentHealthTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Rate limit information
 #[derive(Debug, Clone)]
 pub struct RateLimitInfo {
@@ -95,6 +206,18 @@ pub enum SelectionStrategy {
     CostOptimized,
     /// Random selection
     Random,
+    /// Weighted/canary split between providers, configured at runtime per
+    /// task type (see `CanaryRouter` in the orchestrator crate).
+    Canary,
+    /// Deferred to a config-driven `RoutingRulesEngine` (in the orchestrator
+    /// crate), matching the request's prompt/metadata/task type to a
+    /// preferred provider and required capabilities.
+    RuleBased,
+    /// Deterministic split between an `AbTestConfig`'s control and
+    /// experiment provider, keyed by a hash of the request's task id (see
+    /// `AbTestEngine` in the orchestrator crate) so retries of the same
+    /// task always land in the same arm.
+    AbTest,
 }
 
 /// Agent selector trait
@@ -129,7 +252,8 @@ pub struct AgentPriority {
 }
 
 /// Task types for routing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TaskType {
     Reasoning,
     CodeGeneration,
@@ -2,9 +2,10 @@
 
 use async_trait::async_trait;
 use meta_ai_common::{
-    types::{Task, TaskStatus, LlmResponse},
+    types::{Task, TaskId, TaskStatus, LlmResponse, SearchResult},
     error::Result,
 };
+use serde::{Deserialize, Serialize};
 
 /// Evaluator trait for model validation and quality assurance
 #[async_trait]
@@ -32,6 +33,37 @@ pub trait Evaluator: Send + Sync {
     
     /// Check for model drift
     async fn check_drift(&self) -> Result<DriftAnalysis>;
+
+    /// Record explicit human feedback on a completed task: a thumbs-up/down
+    /// `rating` plus an optional free-text `comment` (e.g. a correction),
+    /// folded into the same rolling accuracy/bug-rate window
+    /// `post_task_validation` feeds.
+    async fn record_feedback(&self, task_id: TaskId, rating: FeedbackRating, comment: Option<String>) -> Result<()>;
+
+    /// Check that claims in `response` are supported by the retrieved
+    /// `context` chunks it was generated from. The returned
+    /// `ValidationResult.score` is the groundedness score (1.0 fully
+    /// supported, 0.0 unsupported); a score below the evaluator's
+    /// configured threshold adds a `Critical` `OutputQuality` issue, so
+    /// callers that block on critical issues reject ungrounded answers the
+    /// same way they reject any other validation failure.
+    async fn check_groundedness(&self, response: &LlmResponse, context: &[SearchResult]) -> Result<ValidationResult>;
+
+    /// Screen RAG-retrieved `context` chunks for prompt-injection patterns
+    /// (instruction overrides, data-exfiltration asks) before they're folded
+    /// into a prompt, the same way `pre_task_validation` screens the task
+    /// description itself. Each match becomes a `SafetyViolation` issue;
+    /// whether that issue is `Warning` or `Critical` (and thus whether
+    /// `ValidationResult.valid` is `false`) depends on the evaluator's
+    /// configured block threshold.
+    async fn scan_retrieved_context(&self, context: &[SearchResult]) -> Result<ValidationResult>;
+}
+
+/// Explicit human feedback rating on a completed task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackRating {
+    ThumbsUp,
+    ThumbsDown,
 }
 
 /// Validation result
@@ -85,7 +117,7 @@ pub struct SelfCheckResult {
 }
 
 /// Fuzzing test result
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FuzzingResult {
     pub iterations: u32,
     pub failures: u32,
@@ -96,7 +128,7 @@ pub struct FuzzingResult {
 }
 
 /// Drift analysis result
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DriftAnalysis {
     pub drift_detected: bool,
     pub drift_score: f64,
@@ -107,18 +139,19 @@ pub struct DriftAnalysis {
 }
 
 /// Test case for evaluation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestCase {
     pub id: String,
     pub name: String,
     pub input: String,
     pub expected_output: Option<String>,
     pub validation_criteria: Vec<ValidationCriterion>,
+    #[serde(default)]
     pub tags: Vec<String>,
 }
 
 /// Validation criterion
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ValidationCriterion {
     /// Exact match
     ExactMatch(String),
@@ -137,7 +170,7 @@ pub enum ValidationCriterion {
 }
 
 /// Benchmark suite for evaluation
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkSuite {
     pub name: String,
     pub test_cases: Vec<TestCase>,
@@ -160,6 +193,13 @@ pub struct EvaluationMetrics {
     pub p99_latency_ms: f64,
     pub bug_rate: f64,
     pub error_rate: f64,
+    /// Average cost (in the provider pricing model's currency unit) of every
+    /// request, successful or not.
+    pub cost_per_request: f64,
+    /// Average cost per request that actually succeeded, so a provider with
+    /// a high failure rate doesn't look artificially cheap in
+    /// `cost_per_request`.
+    pub cost_per_successful_task: f64,
 }
 
 /// Quality gate definition
@@ -181,6 +221,12 @@ pub enum QualityMetric {
     ErrorRate,
     TokenUsage,
     Cost,
+    /// SLO burn-rate: observed fraction of requests breaching a latency
+    /// target divided by the error budget the target allows. `1.0` burns
+    /// the budget exactly as fast as the SLO permits; a gate comparing this
+    /// `GreaterThan` some threshold like `2.0` catches a latency regression
+    /// well before the budget is actually exhausted.
+    LatencyBurnRate,
 }
 
 /// Comparison operators
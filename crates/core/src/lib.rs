@@ -15,14 +15,18 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 pub mod agent;
+pub mod model_catalog;
 pub mod orchestrator;
 pub mod rag;
 pub mod evaluation;
+pub mod workflow;
 
-pub use agent::Agent;
+pub use agent::{Agent, AgentHealthTracker};
+pub use model_catalog::{ModelCatalog, ModelInfo};
 pub use orchestrator::Orchestrator;
-pub use rag::RagEngine;
-pub use evaluation::Evaluator;
+pub use rag::{FilterValue, MetadataFilter, QueryExpansionMode, RagEngine, Reranker};
+pub use evaluation::{Evaluator, FeedbackRating};
+pub use workflow::{WorkflowBuilder, WorkflowDef};
 
 /// Core AI trait - the main interface for all AI operations
 #[async_trait]
@@ -44,6 +48,14 @@ pub trait CoreAI: Send + Sync {
     
     /// Get system health status
     async fn health_check(&self) -> Result<HealthStatus>;
+
+    /// Record explicit human feedback (thumbs-up/down plus an optional
+    /// correction) on a completed task, folding it into the evaluator's
+    /// accuracy metrics and provider selection quality scores. This is the
+    /// single entry point an HTTP handler would call once this workspace
+    /// grows one; there's no HTTP layer here yet, so `meta-ai-cli`'s
+    /// `feedback` subcommand is the only current caller.
+    async fn record_feedback(&self, task_id: TaskId, rating: FeedbackRating, comment: Option<String>) -> Result<()>;
 }
 
 /// System health status
@@ -55,6 +67,10 @@ pub struct HealthStatus {
     pub accuracy: f64,
     pub bug_rate: f64,
     pub agent_status: Vec<AgentStatus>,
+    /// Latency SLO burn-rate, if `with_latency_burn_rate` configured a
+    /// reading hook. `None` means no hook was configured, not that latency
+    /// is healthy.
+    pub latency_burn_rate: Option<f64>,
 }
 
 /// Agent health status
@@ -74,6 +90,28 @@ pub struct MetaAICore {
     rag_engine: Arc<RwLock<Box<dyn RagEngine>>>,
     evaluator: Arc<RwLock<Box<dyn Evaluator>>>,
     start_time: std::time::Instant,
+    /// Thresholds `health_check` compares live accuracy/bug-rate against.
+    /// Normally set from `EvaluationConfig.accuracy_threshold`/
+    /// `bug_rate_threshold` via `with_thresholds`; default to this crate's
+    /// long-standing hardcoded values.
+    accuracy_threshold: f64,
+    bug_rate_threshold: f64,
+    /// Extra health verdict ANDed into `HealthStatus.healthy`, e.g. a
+    /// `meta-ai-eval::SelfCheckLoop`'s `HealthGauge::healthy` closure. This
+    /// crate doesn't depend on `meta-ai-eval`, so the hook is a plain
+    /// closure rather than that type directly. `None` (the default) skips
+    /// this and relies solely on the live accuracy/bug-rate thresholds.
+    external_health: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
+    /// Latency SLO burn-rate reading, e.g. a `TaskDispatcher::latency_burn_rate`
+    /// call for this deployment's primary `(provider, task type)` pair. This
+    /// crate doesn't depend on `meta-ai-orchestrator-engine`, so the hook is a
+    /// plain closure rather than a `TaskDispatcher` reference. `None` (the
+    /// default) skips this and `health_check` reports no burn-rate reading.
+    latency_burn_rate: Option<Arc<dyn Fn() -> f64 + Send + Sync>>,
+    /// Burn-rate above which `health_check` reports unhealthy. Defaults to
+    /// `2.0`, the same "twice the allowed rate" threshold the SRE burn-rate
+    /// alerting practice this mirrors commonly pages on.
+    latency_burn_rate_threshold: f64,
 }
 
 impl MetaAICore {
@@ -90,8 +128,41 @@ impl MetaAICore {
             rag_engine: Arc::new(RwLock::new(rag_engine)),
             evaluator: Arc::new(RwLock::new(evaluator)),
             start_time: std::time::Instant::now(),
+            accuracy_threshold: 0.9999,
+            bug_rate_threshold: 0.0005,
+            external_health: None,
+            latency_burn_rate: None,
+            latency_burn_rate_threshold: 2.0,
         }
     }
+
+    /// Override the default accuracy/bug-rate thresholds `health_check`
+    /// compares live evaluator readings against, normally sourced from
+    /// `EvaluationConfig`.
+    pub fn with_thresholds(mut self, accuracy_threshold: f64, bug_rate_threshold: f64) -> Self {
+        self.accuracy_threshold = accuracy_threshold;
+        self.bug_rate_threshold = bug_rate_threshold;
+        self
+    }
+
+    /// Fold an external health verdict into `HealthStatus.healthy` alongside
+    /// the live accuracy/bug-rate thresholds. Intended for a background
+    /// self-check loop (e.g. `meta-ai-eval::SelfCheckLoop`) that keeps
+    /// checking even during a quiet period with no real traffic.
+    pub fn with_external_health(mut self, health: Arc<dyn Fn() -> bool + Send + Sync>) -> Self {
+        self.external_health = Some(health);
+        self
+    }
+
+    /// Fold a latency SLO burn-rate reading (e.g. from a
+    /// `TaskDispatcher::latency_burn_rate` closure) into
+    /// `HealthStatus.latency_burn_rate`/`healthy`, flagging unhealthy once it
+    /// exceeds `threshold` (default `2.0`, see `latency_burn_rate_threshold`).
+    pub fn with_latency_burn_rate(mut self, reading: Arc<dyn Fn() -> f64 + Send + Sync>, threshold: f64) -> Self {
+        self.latency_burn_rate = Some(reading);
+        self.latency_burn_rate_threshold = threshold;
+        self
+    }
 }
 
 #[async_trait]
@@ -153,15 +224,27 @@ impl CoreAI for MetaAICore {
         let orchestrator = self.orchestrator.read().await;
         let active_tasks = orchestrator.list_active_tasks().await?.len();
         
+        let thresholds_met = accuracy >= self.accuracy_threshold && bug_rate <= self.bug_rate_threshold;
+        let external_healthy = self.external_health.as_ref().is_none_or(|health| health());
+
+        let latency_burn_rate = self.latency_burn_rate.as_ref().map(|reading| reading());
+        let latency_healthy = latency_burn_rate.is_none_or(|rate| rate < self.latency_burn_rate_threshold);
+
         Ok(HealthStatus {
-            healthy: accuracy >= 0.9999 && bug_rate <= 0.0005,
+            healthy: thresholds_met && external_healthy && latency_healthy,
             uptime_seconds: self.start_time.elapsed().as_secs(),
             active_tasks,
             accuracy,
             bug_rate,
             agent_status,
+            latency_burn_rate,
         })
     }
+
+    async fn record_feedback(&self, task_id: TaskId, rating: FeedbackRating, comment: Option<String>) -> Result<()> {
+        let evaluator = self.evaluator.read().await;
+        evaluator.record_feedback(task_id, rating, comment).await
+    }
 }
 
 #[cfg(test)]
@@ -177,8 +260,12 @@ mod tests {
             async fn execute_task(&self, task: Task) -> Result<TaskStatus>;
             async fn submit_request(&self, request: LlmRequest) -> Result<LlmResponse>;
             async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus>;
+            async fn get_task_result(&self, task_id: TaskId) -> Result<crate::orchestrator::TaskResult>;
+            async fn subscribe(&self, task_id: TaskId) -> Result<crate::orchestrator::TaskEventStream>;
             async fn cancel_task(&mut self, task_id: TaskId) -> Result<()>;
             async fn list_active_tasks(&self) -> Result<Vec<Task>>;
+            async fn execute_dag(&self, dag: &crate::orchestrator::TaskDag) -> Result<crate::orchestrator::DagExecutionResult>;
+            async fn resume_dag(&self, dag_run_id: crate::orchestrator::DagRunId) -> Result<crate::orchestrator::DagExecutionResult>;
         }
     }
     
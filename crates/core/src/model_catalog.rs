@@ -0,0 +1,278 @@
+//! Per-(provider, model) catalog of context window, pricing, capability, and
+//! deprecation metadata. `TaskDispatcher` (in the orchestrator crate) uses
+//! this to drive its context-window guard, cost estimation, and capability
+//! checks against real published model specs instead of the coarse
+//! per-provider constants those checks used before this existed.
+
+use crate::agent::AgentCapabilities;
+use chrono::NaiveDate;
+use meta_ai_common::types::LlmProvider;
+use std::collections::HashMap;
+
+/// Context window, pricing, capability, and deprecation metadata for one
+/// named model from one provider.
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    pub name: String,
+    pub capabilities: AgentCapabilities,
+    /// USD cost per million input (prompt) tokens.
+    pub input_cost_per_million: f64,
+    /// USD cost per million output (completion) tokens.
+    pub output_cost_per_million: f64,
+    /// Date the provider has announced this model will stop being served.
+    /// `None` means no deprecation has been announced.
+    pub deprecation_date: Option<NaiveDate>,
+}
+
+/// Catalog of `ModelInfo` keyed by `(provider, model name)`. `bundled` seeds
+/// a handful of current flagship models per provider; `register` lets a
+/// caller (e.g. config loading) add or override entries on top, the same
+/// "bundled defaults plus config overrides" shape `ModelCatalog::from_config`
+/// builds.
+#[derive(Debug, Clone, Default)]
+pub struct ModelCatalog {
+    models: HashMap<(LlmProvider, String), ModelInfo>,
+}
+
+impl ModelCatalog {
+    /// A catalog seeded with one illustrative flagship model per provider.
+    /// These figures are illustrative, not live pricing-API data; update
+    /// alongside provider contract changes, the same caveat
+    /// `dispatcher::provider_pricing` carries for its coarser figures.
+    pub fn bundled() -> Self {
+        let mut catalog = Self::default();
+
+        catalog.register(
+            LlmProvider::OpenAI,
+            ModelInfo {
+                name: "gpt-4o".to_string(),
+                capabilities: AgentCapabilities {
+                    max_tokens: 16_384,
+                    supports_streaming: true,
+                    supports_function_calling: true,
+                    supports_vision: true,
+                    supports_code_execution: false,
+                    supports_web_search: false,
+                    supports_embeddings: false,
+                    context_window: 128_000,
+                    languages: vec!["en".to_string()],
+                    specializations: vec!["general".to_string(), "vision".to_string()],
+                },
+                input_cost_per_million: 2.50,
+                output_cost_per_million: 10.00,
+                deprecation_date: None,
+            },
+        );
+
+        catalog.register(
+            LlmProvider::Claude,
+            ModelInfo {
+                name: "claude-3-5-sonnet".to_string(),
+                capabilities: AgentCapabilities {
+                    max_tokens: 8_192,
+                    supports_streaming: true,
+                    supports_function_calling: true,
+                    supports_vision: true,
+                    supports_code_execution: false,
+                    supports_web_search: false,
+                    supports_embeddings: false,
+                    context_window: 200_000,
+                    languages: vec!["en".to_string()],
+                    specializations: vec!["general".to_string(), "code".to_string()],
+                },
+                input_cost_per_million: 3.00,
+                output_cost_per_million: 15.00,
+                deprecation_date: None,
+            },
+        );
+
+        catalog.register(
+            LlmProvider::Copilot,
+            ModelInfo {
+                name: "copilot-chat".to_string(),
+                capabilities: AgentCapabilities {
+                    specializations: vec!["code".to_string()],
+                    ..AgentCapabilities::default()
+                },
+                input_cost_per_million: 2.00,
+                output_cost_per_million: 2.00,
+                deprecation_date: None,
+            },
+        );
+
+        catalog.register(
+            LlmProvider::Cursor,
+            ModelInfo {
+                name: "cursor-default".to_string(),
+                capabilities: AgentCapabilities {
+                    specializations: vec!["code".to_string()],
+                    ..AgentCapabilities::default()
+                },
+                input_cost_per_million: 3.00,
+                output_cost_per_million: 3.00,
+                deprecation_date: None,
+            },
+        );
+
+        catalog.register(
+            LlmProvider::CodeWhisperer,
+            ModelInfo {
+                name: "codewhisperer-default".to_string(),
+                capabilities: AgentCapabilities {
+                    specializations: vec!["code".to_string()],
+                    ..AgentCapabilities::default()
+                },
+                input_cost_per_million: 1.50,
+                output_cost_per_million: 1.50,
+                deprecation_date: None,
+            },
+        );
+
+        catalog.register(
+            LlmProvider::Local,
+            ModelInfo {
+                name: "local-default".to_string(),
+                capabilities: AgentCapabilities::default(),
+                input_cost_per_million: 0.0,
+                output_cost_per_million: 0.0,
+                deprecation_date: None,
+            },
+        );
+
+        catalog
+    }
+
+    /// Build a catalog from `bundled` with `overrides` layered on top, keyed
+    /// by `(provider, name)` so an override with the same provider and model
+    /// name as a bundled entry replaces it rather than duplicating it.
+    pub fn with_overrides(overrides: impl IntoIterator<Item = (LlmProvider, ModelInfo)>) -> Self {
+        let mut catalog = Self::bundled();
+        for (provider, info) in overrides {
+            catalog.register(provider, info);
+        }
+        catalog
+    }
+
+    /// Build a catalog from `bundled` with `entries` (normally
+    /// `Config.model_catalog`) layered on top. An entry whose
+    /// `deprecation_date` fails to parse as `YYYY-MM-DD` is registered with
+    /// no deprecation date rather than rejecting the whole config.
+    pub fn from_config(entries: &[meta_ai_common::config::ModelCatalogEntry]) -> Self {
+        let overrides = entries.iter().map(|entry| {
+            let capabilities = AgentCapabilities {
+                max_tokens: entry.max_output_tokens,
+                supports_streaming: entry.supports_streaming,
+                supports_function_calling: entry.supports_function_calling,
+                supports_vision: entry.supports_vision,
+                supports_code_execution: entry.supports_code_execution,
+                supports_web_search: entry.supports_web_search,
+                supports_embeddings: entry.supports_embeddings,
+                context_window: entry.context_window,
+                languages: entry.languages.clone(),
+                specializations: entry.specializations.clone(),
+            };
+            let deprecation_date = entry
+                .deprecation_date
+                .as_deref()
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            (
+                entry.provider,
+                ModelInfo {
+                    name: entry.model.clone(),
+                    capabilities,
+                    input_cost_per_million: entry.input_cost_per_million,
+                    output_cost_per_million: entry.output_cost_per_million,
+                    deprecation_date,
+                },
+            )
+        });
+        Self::with_overrides(overrides.collect::<Vec<_>>())
+    }
+
+    pub fn register(&mut self, provider: LlmProvider, info: ModelInfo) {
+        self.models.insert((provider, info.name.clone()), info);
+    }
+
+    pub fn lookup(&self, provider: LlmProvider, model: &str) -> Option<&ModelInfo> {
+        self.models.get(&(provider, model.to_string()))
+    }
+
+    /// Whether `model` has passed its `deprecation_date` as of `as_of`. A
+    /// model with no catalog entry, or no configured deprecation date, is
+    /// never considered deprecated by this check alone.
+    pub fn is_deprecated(&self, provider: LlmProvider, model: &str, as_of: NaiveDate) -> bool {
+        self.lookup(provider, model).and_then(|info| info.deprecation_date).is_some_and(|date| date <= as_of)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_catalog_has_an_entry_per_provider() {
+        let catalog = ModelCatalog::bundled();
+        assert!(catalog.lookup(LlmProvider::OpenAI, "gpt-4o").is_some());
+        assert!(catalog.lookup(LlmProvider::Claude, "claude-3-5-sonnet").is_some());
+        assert!(catalog.lookup(LlmProvider::OpenAI, "no-such-model").is_none());
+    }
+
+    #[test]
+    fn override_replaces_bundled_entry_with_same_key() {
+        let catalog = ModelCatalog::with_overrides([(
+            LlmProvider::OpenAI,
+            ModelInfo {
+                name: "gpt-4o".to_string(),
+                capabilities: AgentCapabilities::default(),
+                input_cost_per_million: 1.0,
+                output_cost_per_million: 1.0,
+                deprecation_date: None,
+            },
+        )]);
+        assert_eq!(catalog.lookup(LlmProvider::OpenAI, "gpt-4o").unwrap().input_cost_per_million, 1.0);
+    }
+
+    #[test]
+    fn from_config_layers_entries_over_bundled_defaults() {
+        let entries = vec![meta_ai_common::config::ModelCatalogEntry {
+            provider: LlmProvider::OpenAI,
+            model: "gpt-4o-mini".to_string(),
+            context_window: 128_000,
+            max_output_tokens: 16_384,
+            input_cost_per_million: 0.15,
+            output_cost_per_million: 0.60,
+            supports_streaming: true,
+            supports_function_calling: true,
+            supports_vision: true,
+            supports_code_execution: false,
+            supports_web_search: false,
+            supports_embeddings: false,
+            languages: vec!["en".to_string()],
+            specializations: vec!["general".to_string()],
+            deprecation_date: Some("not-a-date".to_string()),
+        }];
+        let catalog = ModelCatalog::from_config(&entries);
+        assert!(catalog.lookup(LlmProvider::OpenAI, "gpt-4o").is_some());
+        let mini = catalog.lookup(LlmProvider::OpenAI, "gpt-4o-mini").unwrap();
+        assert_eq!(mini.input_cost_per_million, 0.15);
+        assert!(mini.deprecation_date.is_none());
+    }
+
+    #[test]
+    fn deprecation_check_compares_against_as_of_date() {
+        let mut catalog = ModelCatalog::bundled();
+        catalog.register(
+            LlmProvider::OpenAI,
+            ModelInfo {
+                name: "gpt-3".to_string(),
+                capabilities: AgentCapabilities::default(),
+                input_cost_per_million: 1.0,
+                output_cost_per_million: 1.0,
+                deprecation_date: Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            },
+        );
+        assert!(catalog.is_deprecated(LlmProvider::OpenAI, "gpt-3", NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()));
+        assert!(!catalog.is_deprecated(LlmProvider::OpenAI, "gpt-3", NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()));
+        assert!(!catalog.is_deprecated(LlmProvider::OpenAI, "gpt-4o", NaiveDate::from_ymd_opt(2099, 1, 1).unwrap()));
+    }
+}
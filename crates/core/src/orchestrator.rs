@@ -5,7 +5,15 @@ use meta_ai_common::{
     types::{Task, TaskId, TaskStatus, LlmRequest, LlmResponse},
     error::Result,
 };
+use futures::Stream;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::pin::Pin;
+use uuid::Uuid;
+
+/// Identifies a single execution of a `TaskDag`, so a crashed or interrupted
+/// run can be checkpointed and resumed with `DagExecutor::resume_dag`.
+pub type DagRunId = Uuid;
 
 /// Orchestrator trait for managing task execution
 #[async_trait]
@@ -18,33 +26,91 @@ pub trait Orchestrator: Send + Sync {
     
     /// Get task status
     async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus>;
-    
+
+    /// Get the stored result of a completed task
+    async fn get_task_result(&self, task_id: TaskId) -> Result<TaskResult>;
+
+    /// Subscribe to lifecycle events (`Pending` -> `Running` -> `Completed`/
+    /// `Failed`) for a single task, so callers can watch progress instead of
+    /// polling `get_task_status`.
+    async fn subscribe(&self, task_id: TaskId) -> Result<TaskEventStream>;
+
     /// Cancel a task
     async fn cancel_task(&mut self, task_id: TaskId) -> Result<()>;
-    
+
     /// List active tasks
     async fn list_active_tasks(&self) -> Result<Vec<Task>>;
+
+    /// Execute a DAG workflow (e.g. one loaded via `WorkflowDef::from_yaml`)
+    /// to completion.
+    async fn execute_dag(&self, dag: &TaskDag) -> Result<DagExecutionResult>;
+
+    /// Resume a previously checkpointed DAG run from where it left off.
+    async fn resume_dag(&self, dag_run_id: DagRunId) -> Result<DagExecutionResult>;
 }
 
-/// DAG (Directed Acyclic Graph) for task dependencies
+/// Stored outcome of a completed task, kept around so callers can retrieve
+/// the generated output after `get_task_status` reports it finished.
+#[derive(Debug, Clone)]
+pub struct TaskResult {
+    pub task_id: TaskId,
+    pub status: TaskStatus,
+    pub response: Option<LlmResponse>,
+    pub error: Option<String>,
+    pub completed_at: chrono::DateTime<chrono::Utc>,
+    /// How long the task spent actually executing (not counting queue wait),
+    /// or `None` if it never reached execution (e.g. expired while queued).
+    pub execution_time_ms: Option<u64>,
+}
+
+/// A task lifecycle transition emitted on the orchestrator's event bus.
 #[derive(Debug, Clone)]
+pub struct TaskEvent {
+    pub task_id: TaskId,
+    pub status: TaskStatus,
+    pub result: Option<TaskResult>,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Stream of `TaskEvent`s returned by `Orchestrator::subscribe`.
+pub type TaskEventStream = Pin<Box<dyn Stream<Item = TaskEvent> + Send>>;
+
+/// DAG (Directed Acyclic Graph) for task dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskDag {
     pub nodes: HashMap<TaskId, DagNode>,
     pub edges: Vec<DagEdge>,
 }
 
 /// DAG node representing a task
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DagNode {
     pub task_id: TaskId,
     pub task: Task,
     pub dependencies: Vec<TaskId>,
     pub dependents: Vec<TaskId>,
     pub status: TaskStatus,
+    #[serde(default)]
+    pub kind: NodeKind,
+}
+
+/// What a `DagNode` does when it executes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum NodeKind {
+    /// Run the node's prompt template once through an agent, as normal.
+    #[default]
+    Standard,
+    /// Fan out: parse `source`'s output as a JSON array of strings (falling
+    /// back to one item per non-empty line if it isn't one), then run this
+    /// node's prompt template once per item with `{{item}}` substituted,
+    /// in parallel. The node's own output is the per-item results joined
+    /// with `\n---\n`, so a downstream node can reduce over it exactly like
+    /// any other upstream output.
+    Map { source: TaskId },
 }
 
 /// DAG edge representing dependency
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DagEdge {
     pub from: TaskId,
     pub to: TaskId,
@@ -52,7 +118,8 @@ pub struct DagEdge {
 }
 
 /// Edge condition for conditional execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EdgeCondition {
     /// Execute if source task succeeded
     OnSuccess,
@@ -64,6 +131,12 @@ pub enum EdgeCondition {
     Custom(String),
 }
 
+impl Default for EdgeCondition {
+    fn default() -> Self {
+        Self::OnSuccess
+    }
+}
+
 /// DAG validation result
 #[derive(Debug)]
 pub struct DagValidation {
@@ -76,12 +149,17 @@ pub struct DagValidation {
 /// DAG executor trait
 #[async_trait]
 pub trait DagExecutor: Send + Sync {
-    /// Execute a DAG
+    /// Execute a DAG, checkpointing per-node completion state as it goes.
     async fn execute_dag(&self, dag: &TaskDag) -> Result<DagExecutionResult>;
-    
+
+    /// Resume a DAG run that was previously checkpointed by `execute_dag`,
+    /// skipping nodes that already completed (or were skipped) and
+    /// continuing from where it left off.
+    async fn resume_dag(&self, dag_run_id: DagRunId) -> Result<DagExecutionResult>;
+
     /// Validate a DAG
     fn validate_dag(&self, dag: &TaskDag) -> DagValidation;
-    
+
     /// Get execution order for DAG
     fn topological_sort(&self, dag: &TaskDag) -> Result<Vec<TaskId>>;
 }
@@ -89,6 +167,7 @@ pub trait DagExecutor: Send + Sync {
 /// DAG execution result
 #[derive(Debug)]
 pub struct DagExecutionResult {
+    pub dag_run_id: DagRunId,
     pub completed_tasks: Vec<TaskId>,
     pub failed_tasks: Vec<TaskId>,
     pub skipped_tasks: Vec<TaskId>,
@@ -96,19 +175,88 @@ pub struct DagExecutionResult {
 }
 
 /// Task scheduler trait
+///
+/// Methods take `&self`, not `&mut self`: every implementation holds its
+/// queue(s) behind interior mutability (`parking_lot::Mutex`, an atomic, or
+/// similar), so a single scheduler instance can be shared as `Arc<dyn
+/// TaskScheduler>` and called from many tasks concurrently without an outer
+/// lock serializing schedule/next_task against each other.
 #[async_trait]
 pub trait TaskScheduler: Send + Sync {
     /// Schedule a task for execution
-    async fn schedule_task(&mut self, task: Task) -> Result<()>;
-    
+    async fn schedule_task(&self, task: Task) -> Result<()>;
+
     /// Get next task to execute
-    async fn next_task(&mut self) -> Result<Option<Task>>;
-    
+    async fn next_task(&self) -> Result<Option<Task>>;
+
     /// Return a task to the queue (e.g., after failure)
-    async fn requeue_task(&mut self, task: Task) -> Result<()>;
-    
+    async fn requeue_task(&self, task: Task) -> Result<()>;
+
     /// Get queue statistics
     async fn queue_stats(&self) -> QueueStats;
+
+    /// Stop handing out tasks via `next_task` (it returns `Ok(None)` until
+    /// `resume` is called) without losing anything already queued.
+    /// `schedule_task` keeps accepting new work while paused.
+    fn pause(&self);
+
+    /// Resume handing out tasks via `next_task` after `pause`.
+    fn resume(&self);
+
+    /// Whether `next_task` is currently paused.
+    fn is_paused(&self) -> bool;
+
+    /// Snapshot of queued (not yet dispatched) tasks matching `filter`, in
+    /// dispatch order, paginated by `limit`/`offset`. For operator
+    /// introspection ("what's stuck?") where `QueueStats`'s aggregate counts
+    /// aren't enough detail; always a slightly stale view since the
+    /// scheduler keeps serving `next_task` concurrently.
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo>;
+}
+
+/// Filter predicate for `TaskScheduler::list_queued_tasks`. Every `Some`
+/// field must match; `None` fields are ignored, so the default filter
+/// matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct QueuedTaskFilter {
+    pub priority: Option<meta_ai_common::types::Priority>,
+    pub tenant: Option<String>,
+    pub provider: Option<meta_ai_common::types::LlmProvider>,
+}
+
+impl QueuedTaskFilter {
+    pub fn matches(&self, task: &Task) -> bool {
+        if let Some(priority) = self.priority {
+            if task.priority != priority {
+                return false;
+            }
+        }
+        if self.tenant.is_some() && task.tenant != self.tenant {
+            return false;
+        }
+        if let Some(provider) = self.provider {
+            if task.provider != Some(provider) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// One queued task, as returned by `TaskScheduler::list_queued_tasks`.
+#[derive(Debug, Clone)]
+pub struct QueuedTaskInfo {
+    pub task_id: TaskId,
+    pub name: String,
+    pub priority: meta_ai_common::types::Priority,
+    /// 0-based position in dispatch order: `next_task` would return
+    /// `position` other queued tasks before this one (ignoring `filter`,
+    /// which only determines which tasks are included in the result, not
+    /// where they sit in the queue).
+    pub position: usize,
+    pub wait_time_ms: u64,
+    pub tenant: Option<String>,
+    pub provider: Option<meta_ai_common::types::LlmProvider>,
 }
 
 /// Queue statistics
@@ -120,6 +268,32 @@ pub struct QueueStats {
     pub failed_tasks: usize,
     pub average_wait_time_ms: f64,
     pub average_execution_time_ms: f64,
+    /// 50th percentile queue wait time, in milliseconds.
+    pub p50_wait_time_ms: f64,
+    /// 95th percentile queue wait time, in milliseconds.
+    pub p95_wait_time_ms: f64,
+    /// Whether dispatch is currently being throttled by a `ResourceConstraints`
+    /// limit (tokens/minute or memory). Individual `TaskScheduler` impls don't
+    /// track this themselves; it's overlaid by `MetaAIOrchestrator::queue_stats`.
+    pub throttled: bool,
+    /// Per-provider concurrency and rate-limit utilization. Like `throttled`,
+    /// `TaskScheduler` impls don't track per-provider dispatch lanes
+    /// themselves; it's overlaid by `MetaAIOrchestrator::queue_stats`.
+    pub provider_utilization: HashMap<meta_ai_common::types::LlmProvider, ProviderUtilization>,
+    /// Whether `next_task` is currently paused (see `TaskScheduler::pause`).
+    pub paused: bool,
+}
+
+/// Snapshot of one provider's dispatch lane: how many of its concurrency
+/// permits are currently in use, and its recent request rate.
+#[derive(Debug, Clone)]
+pub struct ProviderUtilization {
+    pub in_flight: usize,
+    pub max_concurrent: usize,
+    pub requests_per_minute: u32,
+    /// Whether this provider's lane is currently paused (see
+    /// `ProviderLanes::pause` in the orchestrator crate).
+    pub paused: bool,
 }
 
 /// Execution strategy for orchestration
@@ -1,26 +1,54 @@
 //! RAG (Retrieval-Augmented Generation) engine trait
 
 use async_trait::async_trait;
+use futures::Stream;
 use meta_ai_common::{
-    types::{Document, Embedding, SearchResult},
+    types::{Document, Embedding, Metadata, SearchResult},
     error::Result,
 };
+use serde::{Deserialize, Serialize};
+use std::pin::Pin;
 
 /// RAG engine trait for document indexing and retrieval
 #[async_trait]
 pub trait RagEngine: Send + Sync {
     /// Index a document
     async fn index_document(&self, document: Document) -> Result<()>;
-    
-    /// Index multiple documents
-    async fn index_documents(&self, documents: Vec<Document>) -> Result<IndexResult>;
-    
-    /// Search for relevant documents
-    async fn search(&self, query: &str, top_k: usize) -> Result<Vec<SearchResult>>;
-    
-    /// Search with embedding vector
-    async fn search_by_embedding(&self, embedding: &Embedding, top_k: usize) -> Result<Vec<SearchResult>>;
-    
+
+    /// Index multiple documents as a pipeline (chunk, embed, upsert),
+    /// bounding concurrency and retrying per-document failures rather than
+    /// aborting the whole batch. `progress`, if given, receives an
+    /// `IndexProgress` update after every document (whether it succeeded or
+    /// failed), so a caller can drive a progress bar for long ingestion jobs
+    /// without waiting for the final `IndexResult`.
+    async fn index_documents(
+        &self,
+        documents: Vec<Document>,
+        progress: Option<tokio::sync::mpsc::UnboundedSender<IndexProgress>>,
+    ) -> Result<IndexResult>;
+
+    /// Search for relevant documents, optionally restricted to those whose
+    /// metadata matches `filter`. `expansion`, if given, rewrites `query`
+    /// before it's embedded (see `QueryExpansionMode`), to improve recall on
+    /// short or underspecified queries; it has no effect on the BM25 half of
+    /// the search, which still matches the literal query.
+    async fn search(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+        expansion: Option<QueryExpansionMode>,
+    ) -> Result<Vec<SearchResult>>;
+
+    /// Search with embedding vector, optionally restricted to those whose
+    /// metadata matches `filter`.
+    async fn search_by_embedding(
+        &self,
+        embedding: &Embedding,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+    ) -> Result<Vec<SearchResult>>;
+
     /// Generate embedding for text
     async fn generate_embedding(&self, text: &str) -> Result<Embedding>;
     
@@ -35,6 +63,124 @@ pub trait RagEngine: Send + Sync {
     
     /// Clear all documents
     async fn clear_collection(&self) -> Result<()>;
+
+    /// Export every point in the collection, embeddings included, as a
+    /// portable `CollectionSnapshot` that can be serialized and stored
+    /// outside the engine, then later replayed through `restore` to rebuild
+    /// the collection elsewhere without re-embedding anything.
+    async fn snapshot(&self) -> Result<CollectionSnapshot>;
+
+    /// Re-populate the collection from a previously captured `snapshot`,
+    /// upserting each point as-is. Overwrites any existing point with the
+    /// same id, so restoring into a non-empty collection merges rather than
+    /// replacing it; call `clear_collection` first for an exact replay.
+    async fn restore(&self, snapshot: CollectionSnapshot) -> Result<()>;
+
+    /// Like `search`, but returns a stream of score-ordered batches (each up
+    /// to `batch_size` results, clamped to at least 1) instead of
+    /// materializing the full result set in one `Vec`. Intended for
+    /// analytical jobs pulling hundreds of chunks, where a caller can start
+    /// processing the first batch immediately and apply backpressure simply
+    /// by not polling the stream for more; the bounded channel behind it
+    /// stalls production once its buffer fills up.
+    async fn search_stream(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+        expansion: Option<QueryExpansionMode>,
+        batch_size: usize,
+    ) -> Result<SearchResultStream>;
+}
+
+/// Stream of score-ordered `SearchResult` batches returned by
+/// `RagEngine::search_stream`.
+pub type SearchResultStream = Pin<Box<dyn Stream<Item = Vec<SearchResult>> + Send>>;
+
+/// Portable export of a collection's points, produced by `RagEngine::snapshot`
+/// and consumed by `RagEngine::restore`. Serializes directly to/from JSON (or
+/// any other `serde` format) so it can be written to a file or object storage
+/// as an opaque archive and moved between environments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionSnapshot {
+    /// Every point in the collection, each with its embedding populated.
+    pub documents: Vec<Document>,
+}
+
+/// A value a `MetadataFilter` condition compares a document metadata field
+/// against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+/// Filter expression evaluated against document metadata. `RagEngine`
+/// implementations push this down into whatever query mechanism they have
+/// (e.g. Qdrant payload filters); implementations with no query planner to
+/// push it into (e.g. a keyword index) can fall back to `matches`.
+#[derive(Debug, Clone)]
+pub enum MetadataFilter {
+    /// Field equals a value.
+    Eq(String, FilterValue),
+    /// Field equals one of a set of values.
+    In(String, Vec<FilterValue>),
+    /// Field is a number greater than or equal to a threshold.
+    Gte(String, f64),
+    /// Field is a number less than or equal to a threshold.
+    Lte(String, f64),
+    /// All sub-filters match.
+    And(Vec<MetadataFilter>),
+    /// At least one sub-filter matches.
+    Or(Vec<MetadataFilter>),
+    /// The sub-filter does not match.
+    Not(Box<MetadataFilter>),
+}
+
+/// How `RagEngine::search` should rewrite a query before embedding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryExpansionMode {
+    /// Expand the query with related terms/synonyms an author might have
+    /// used instead, widening what the embedding can match.
+    Expand,
+    /// Generate a hypothetical answer to the query (HyDE) and embed that
+    /// instead of the query itself, since an answer tends to sit closer in
+    /// embedding space to the documents that would actually answer it.
+    Hyde,
+}
+
+impl MetadataFilter {
+    /// Evaluate this filter directly against `metadata`.
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        match self {
+            MetadataFilter::Eq(key, value) => {
+                metadata.get(key).is_some_and(|field| Self::value_eq(field, value))
+            }
+            MetadataFilter::In(key, values) => metadata
+                .get(key)
+                .is_some_and(|field| values.iter().any(|value| Self::value_eq(field, value))),
+            MetadataFilter::Gte(key, threshold) => metadata
+                .get(key)
+                .and_then(serde_json::Value::as_f64)
+                .is_some_and(|value| value >= *threshold),
+            MetadataFilter::Lte(key, threshold) => metadata
+                .get(key)
+                .and_then(serde_json::Value::as_f64)
+                .is_some_and(|value| value <= *threshold),
+            MetadataFilter::And(filters) => filters.iter().all(|filter| filter.matches(metadata)),
+            MetadataFilter::Or(filters) => filters.iter().any(|filter| filter.matches(metadata)),
+            MetadataFilter::Not(filter) => !filter.matches(metadata),
+        }
+    }
+
+    fn value_eq(field: &serde_json::Value, value: &FilterValue) -> bool {
+        match value {
+            FilterValue::String(s) => field.as_str() == Some(s.as_str()),
+            FilterValue::Integer(i) => field.as_i64() == Some(*i),
+            FilterValue::Bool(b) => field.as_bool() == Some(*b),
+        }
+    }
 }
 
 /// Result of bulk indexing operation
@@ -45,6 +191,10 @@ pub struct IndexResult {
     pub failed: usize,
     pub duration_ms: u64,
     pub errors: Vec<IndexError>,
+    /// How many chunks were skipped because they were near-duplicates of an
+    /// already-indexed vector (see `QdrantRagEngine::with_dedup_threshold`).
+    /// Always 0 when dedup isn't configured.
+    pub duplicates_skipped: usize,
 }
 
 /// Indexing error
@@ -54,6 +204,18 @@ pub struct IndexError {
     pub error: String,
 }
 
+/// Progress update sent after each document in an `index_documents` call
+/// finishes (successfully or not), so a caller can report progress on a long
+/// ingestion job without waiting for the final `IndexResult`.
+#[derive(Debug, Clone)]
+pub struct IndexProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    /// The document that just finished.
+    pub last_document_id: String,
+}
+
 /// Collection statistics
 #[derive(Debug, Clone)]
 pub struct CollectionStats {
@@ -64,6 +226,24 @@ pub struct CollectionStats {
     pub last_updated: chrono::DateTime<chrono::Utc>,
 }
 
+/// Reranks a set of already-retrieved results against the query that
+/// produced them. Run as an optional second pass after `RagEngine::search`
+/// (which optimizes for recall over a whole collection), a reranker trades
+/// more compute per candidate for precision on the much smaller candidate
+/// set that will actually go into a prompt.
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Rescore `results` against `query` and return the `top_k` best,
+    /// ordered best-first. `results.len()` may be smaller than `top_k`, in
+    /// which case every result is returned, reordered.
+    async fn rerank(
+        &self,
+        query: &str,
+        results: Vec<SearchResult>,
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>>;
+}
+
 /// Embedding model trait
 #[async_trait]
 pub trait EmbeddingModel: Send + Sync {
@@ -155,35 +335,145 @@ pub enum DistanceMetric {
     Manhattan,
 }
 
-/// In-memory embedding cache
+/// An embedding as stored by a `CacheEntry`: either at full `f32` precision,
+/// or compressed to `f16` (half the memory, a small precision loss) when the
+/// owning `EmbeddingCache` was built with `with_options(.., f16: true)`.
+enum StoredEmbedding {
+    Full(Embedding),
+    Half(Vec<half::f16>),
+}
+
+impl StoredEmbedding {
+    fn new(embedding: Embedding, f16: bool) -> Self {
+        if f16 {
+            Self::Half(embedding.into_iter().map(half::f16::from_f32).collect())
+        } else {
+            Self::Full(embedding)
+        }
+    }
+
+    fn to_embedding(&self) -> Embedding {
+        match self {
+            Self::Full(embedding) => embedding.clone(),
+            Self::Half(embedding) => embedding.iter().map(|v| v.to_f32()).collect(),
+        }
+    }
+}
+
+/// An `EmbeddingCache` entry: the embedding itself plus the bookkeeping
+/// `EmbeddingCache` needs for LRU eviction and TTL expiry.
+struct CacheEntry {
+    embedding: StoredEmbedding,
+    inserted_at: std::time::Instant,
+    last_accessed_tick: std::sync::atomic::AtomicU64,
+}
+
+/// Hit/miss counters accumulated by an `EmbeddingCache` since construction
+/// (`clear` doesn't reset them), so a caller can tell whether caching is
+/// actually saving re-embedding work.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddingCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// In-memory embedding cache keyed by (typically) the text that was
+/// embedded. Evicts the least-recently-used entry once `max_size` is
+/// reached, and optionally expires entries older than a configured TTL.
 pub struct EmbeddingCache {
-    cache: dashmap::DashMap<String, Embedding>,
+    cache: dashmap::DashMap<String, CacheEntry>,
     max_size: usize,
+    ttl: Option<std::time::Duration>,
+    /// Store entries as `f16` instead of `f32`, halving memory usage at the
+    /// cost of precision and a conversion on every hit/insert.
+    f16: bool,
+    clock: std::sync::atomic::AtomicU64,
+    hits: std::sync::atomic::AtomicU64,
+    misses: std::sync::atomic::AtomicU64,
 }
 
 impl EmbeddingCache {
     pub fn new(max_size: usize) -> Self {
+        Self::with_ttl(max_size, None)
+    }
+
+    /// Like `new`, but an entry older than `ttl` is treated as a miss (and
+    /// evicted) instead of living forever.
+    pub fn with_ttl(max_size: usize, ttl: Option<std::time::Duration>) -> Self {
+        Self::with_options(max_size, ttl, false)
+    }
+
+    /// Like `with_ttl`, additionally choosing whether entries are compressed
+    /// to `f16` in memory.
+    pub fn with_options(max_size: usize, ttl: Option<std::time::Duration>, f16: bool) -> Self {
         Self {
             cache: dashmap::DashMap::new(),
             max_size,
+            ttl,
+            f16,
+            clock: std::sync::atomic::AtomicU64::new(0),
+            hits: std::sync::atomic::AtomicU64::new(0),
+            misses: std::sync::atomic::AtomicU64::new(0),
         }
     }
-    
+
     pub fn get(&self, key: &str) -> Option<Embedding> {
-        self.cache.get(key).map(|e| e.clone())
+        use std::sync::atomic::Ordering;
+
+        let Some(entry) = self.cache.get(key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        if self.ttl.is_some_and(|ttl| entry.inserted_at.elapsed() > ttl) {
+            drop(entry);
+            self.cache.remove(key);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        entry
+            .last_accessed_tick
+            .store(self.clock.fetch_add(1, Ordering::Relaxed), Ordering::Relaxed);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry.embedding.to_embedding())
     }
-    
+
     pub fn insert(&self, key: String, embedding: Embedding) {
-        if self.cache.len() >= self.max_size {
-            // Simple eviction: remove first item
-            if let Some(first_key) = self.cache.iter().next().map(|e| e.key().clone()) {
-                self.cache.remove(&first_key);
+        use std::sync::atomic::Ordering;
+
+        if self.cache.len() >= self.max_size && !self.cache.contains_key(&key) {
+            let lru_key = self
+                .cache
+                .iter()
+                .min_by_key(|entry| entry.last_accessed_tick.load(Ordering::Relaxed))
+                .map(|entry| entry.key().clone());
+            if let Some(lru_key) = lru_key {
+                self.cache.remove(&lru_key);
             }
         }
-        self.cache.insert(key, embedding);
+
+        let tick = self.clock.fetch_add(1, Ordering::Relaxed);
+        self.cache.insert(
+            key,
+            CacheEntry {
+                embedding: StoredEmbedding::new(embedding, self.f16),
+                inserted_at: std::time::Instant::now(),
+                last_accessed_tick: std::sync::atomic::AtomicU64::new(tick),
+            },
+        );
     }
-    
+
     pub fn clear(&self) {
         self.cache.clear();
     }
+
+    /// Hit/miss counts accumulated so far.
+    pub fn metrics(&self) -> EmbeddingCacheMetrics {
+        use std::sync::atomic::Ordering;
+
+        EmbeddingCacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
 }
\ No newline at end of file
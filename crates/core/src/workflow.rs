@@ -0,0 +1,352 @@
+//! Declarative DAG workflow definitions, loadable from YAML/JSON and
+//! convertible into a `TaskDag` for execution.
+
+use crate::orchestrator::{DagEdge, DagNode, EdgeCondition, NodeKind, TaskDag};
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{LlmProvider, Priority, Task, TaskId, TaskStatus},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Declarative definition of a DAG workflow, as loaded from a YAML or JSON
+/// workflow file. Nodes are addressed by their human-readable string id;
+/// `TaskDag::from(workflow)` resolves those ids into `TaskId`s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDef {
+    pub name: String,
+    pub nodes: HashMap<String, WorkflowNodeDef>,
+}
+
+/// A single node in a `WorkflowDef`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowNodeDef {
+    /// The prompt sent to the agent for this node. May reference another
+    /// node's output with `{{node_id}}`; substitution happens at execution
+    /// time, not during loading.
+    pub prompt: String,
+    #[serde(default)]
+    pub provider: Option<LlmProvider>,
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Upstream nodes this node depends on, and under what condition it runs.
+    #[serde(default)]
+    pub depends_on: Vec<WorkflowDependencyDef>,
+    /// Makes this a fan-out node: `prompt` runs once per item of the named
+    /// node's output (parsed as a JSON array of strings), with `{{item}}`
+    /// substituted for each. Implies a dependency on that node, in addition
+    /// to any listed in `depends_on`.
+    #[serde(default)]
+    pub map_source: Option<String>,
+}
+
+/// An upstream dependency of a `WorkflowNodeDef`. The plain string form is
+/// shorthand for `{ node: <id>, condition: on_success }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorkflowDependencyDef {
+    Node(String),
+    Conditional {
+        node: String,
+        #[serde(default)]
+        condition: EdgeCondition,
+    },
+}
+
+impl WorkflowDependencyDef {
+    fn node_id(&self) -> &str {
+        match self {
+            Self::Node(id) => id,
+            Self::Conditional { node, .. } => node,
+        }
+    }
+
+    fn condition(&self) -> EdgeCondition {
+        match self {
+            Self::Node(_) => EdgeCondition::OnSuccess,
+            Self::Conditional { condition, .. } => condition.clone(),
+        }
+    }
+}
+
+impl WorkflowDef {
+    /// Parse a workflow definition from a YAML document.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let def: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| Error::Validation(format!("invalid workflow YAML: {e}")))?;
+        def.validate()?;
+        Ok(def)
+    }
+
+    /// Parse a workflow definition from a JSON document.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let def: Self = serde_json::from_str(json)
+            .map_err(|e| Error::Validation(format!("invalid workflow JSON: {e}")))?;
+        def.validate()?;
+        Ok(def)
+    }
+
+    /// Check that every node has a non-empty prompt and that every
+    /// `depends_on` reference points at a node that actually exists in this
+    /// workflow.
+    fn validate(&self) -> Result<()> {
+        if self.nodes.is_empty() {
+            return Err(Error::Validation("workflow has no nodes".to_string()));
+        }
+
+        for (id, node) in &self.nodes {
+            if node.prompt.trim().is_empty() {
+                return Err(Error::Validation(format!("node '{id}' has an empty prompt")));
+            }
+            for dep in &node.depends_on {
+                if !self.nodes.contains_key(dep.node_id()) {
+                    return Err(Error::Validation(format!(
+                        "node '{id}' depends on unknown node '{}'",
+                        dep.node_id()
+                    )));
+                }
+            }
+            if let Some(source) = &node.map_source {
+                if !self.nodes.contains_key(source) {
+                    return Err(Error::Validation(format!(
+                        "node '{id}' maps over unknown node '{source}'"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolve this definition into an executable `TaskDag`, generating a
+    /// fresh `TaskId` for each node.
+    pub fn into_dag(self) -> TaskDag {
+        let node_ids: HashMap<String, TaskId> = self
+            .nodes
+            .keys()
+            .map(|name| (name.clone(), TaskId::new_v4()))
+            .collect();
+
+        let mut dependents: HashMap<String, Vec<TaskId>> = HashMap::new();
+        for (name, node) in &self.nodes {
+            for dep in &node.depends_on {
+                dependents.entry(dep.node_id().to_string()).or_default().push(node_ids[name]);
+            }
+            if let Some(source) = &node.map_source {
+                dependents.entry(source.clone()).or_default().push(node_ids[name]);
+            }
+        }
+
+        let mut nodes = HashMap::new();
+        let mut edges = Vec::new();
+
+        for (name, node_def) in self.nodes {
+            let task_id = node_ids[&name];
+            let mut dependencies = Vec::with_capacity(node_def.depends_on.len());
+            let mut seen_deps: HashSet<&str> = HashSet::new();
+            for dep in &node_def.depends_on {
+                let from = node_ids[dep.node_id()];
+                dependencies.push(from);
+                seen_deps.insert(dep.node_id());
+                edges.push(DagEdge {
+                    from,
+                    to: task_id,
+                    condition: Some(dep.condition()),
+                });
+            }
+            if let Some(source) = &node_def.map_source {
+                if seen_deps.insert(source.as_str()) {
+                    let from = node_ids[source];
+                    dependencies.push(from);
+                    edges.push(DagEdge {
+                        from,
+                        to: task_id,
+                        condition: Some(EdgeCondition::OnSuccess),
+                    });
+                }
+            }
+
+            let task = Task {
+                id: task_id,
+                name: name.clone(),
+                description: Some(node_def.prompt),
+                status: TaskStatus::Pending,
+                priority: node_def.priority.unwrap_or(Priority::Medium),
+                provider: node_def.provider,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                metadata: HashMap::new(),
+                timeout_ms: None,
+            };
+
+            let kind = match &node_def.map_source {
+                Some(source_name) => NodeKind::Map { source: node_ids[source_name] },
+                None => NodeKind::Standard,
+            };
+
+            nodes.insert(
+                task_id,
+                DagNode {
+                    task_id,
+                    task,
+                    dependencies,
+                    dependents: dependents.remove(&name).unwrap_or_default(),
+                    status: TaskStatus::Pending,
+                    kind,
+                },
+            );
+        }
+
+        TaskDag { nodes, edges }
+    }
+}
+
+/// Programmatic builder for a `TaskDag`, for callers that would rather build
+/// a workflow in code than load one from YAML/JSON.
+#[derive(Debug, Clone)]
+pub struct WorkflowBuilder {
+    name: String,
+    nodes: HashMap<String, WorkflowNodeDef>,
+}
+
+impl WorkflowBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), nodes: HashMap::new() }
+    }
+
+    /// Add a node with the given id and prompt and no dependencies.
+    pub fn node(mut self, id: impl Into<String>, prompt: impl Into<String>) -> Self {
+        self.nodes.insert(
+            id.into(),
+            WorkflowNodeDef {
+                prompt: prompt.into(),
+                provider: None,
+                priority: None,
+                depends_on: Vec::new(),
+                map_source: None,
+            },
+        );
+        self
+    }
+
+    /// Add a fan-out node: `prompt` runs once per item of `source`'s output
+    /// (parsed as a JSON array of strings), with `{{item}}` substituted for
+    /// each. `source` must already have been added via `node`.
+    pub fn map_over(mut self, id: impl Into<String>, prompt: impl Into<String>, source: impl Into<String>) -> Self {
+        self.nodes.insert(
+            id.into(),
+            WorkflowNodeDef {
+                prompt: prompt.into(),
+                provider: None,
+                priority: None,
+                depends_on: Vec::new(),
+                map_source: Some(source.into()),
+            },
+        );
+        self
+    }
+
+    /// Make `id` depend on `on` under `condition`. `id` and `on` must already
+    /// have been added via `node`.
+    pub fn depends_on(mut self, id: &str, on: impl Into<String>, condition: EdgeCondition) -> Self {
+        if let Some(node) = self.nodes.get_mut(id) {
+            node.depends_on.push(WorkflowDependencyDef::Conditional { node: on.into(), condition });
+        }
+        self
+    }
+
+    /// Finish building and resolve into an executable `TaskDag`.
+    pub fn build(self) -> Result<TaskDag> {
+        let def = WorkflowDef { name: self.name, nodes: self.nodes };
+        def.validate()?;
+        Ok(def.into_dag())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_workflow_into_dag() {
+        let yaml = r"
+name: example
+nodes:
+  fetch:
+    prompt: fetch the data
+  summarize:
+    prompt: 'summarize {{fetch}}'
+    depends_on: [fetch]
+  notify_failure:
+    prompt: alert on failure
+    depends_on:
+      - node: summarize
+        condition: on_failure
+";
+        let def = WorkflowDef::from_yaml(yaml).unwrap();
+        let dag = def.into_dag();
+        assert_eq!(dag.nodes.len(), 3);
+        assert_eq!(dag.edges.len(), 2);
+    }
+
+    #[test]
+    fn rejects_dependency_on_unknown_node() {
+        let yaml = r"
+name: example
+nodes:
+  summarize:
+    prompt: summarize it
+    depends_on: [missing]
+";
+        assert!(WorkflowDef::from_yaml(yaml).is_err());
+    }
+
+    #[test]
+    fn builder_produces_dag_with_edges() {
+        let dag = WorkflowBuilder::new("example")
+            .node("fetch", "fetch the data")
+            .node("summarize", "summarize it")
+            .depends_on("summarize", "fetch", EdgeCondition::OnSuccess)
+            .build()
+            .unwrap();
+
+        assert_eq!(dag.nodes.len(), 2);
+        assert_eq!(dag.edges.len(), 1);
+    }
+
+    #[test]
+    fn map_node_implies_dependency_on_its_source() {
+        let yaml = r"
+name: example
+nodes:
+  split:
+    prompt: split the document into chunks
+  summarize_chunk:
+    prompt: 'summarize {{item}}'
+    map_source: split
+  reduce:
+    prompt: 'combine {{nodes.summarize_chunk.output}}'
+    depends_on: [summarize_chunk]
+";
+        let def = WorkflowDef::from_yaml(yaml).unwrap();
+        let dag = def.into_dag();
+        assert_eq!(dag.nodes.len(), 3);
+        assert_eq!(dag.edges.len(), 2);
+
+        let summarize = dag.nodes.values().find(|n| n.task.name == "summarize_chunk").unwrap();
+        assert!(matches!(summarize.kind, NodeKind::Map { .. }));
+        assert_eq!(summarize.dependencies.len(), 1);
+    }
+
+    #[test]
+    fn rejects_map_over_unknown_node() {
+        let yaml = r"
+name: example
+nodes:
+  summarize_chunk:
+    prompt: 'summarize {{item}}'
+    map_source: missing
+";
+        assert!(WorkflowDef::from_yaml(yaml).is_err());
+    }
+}
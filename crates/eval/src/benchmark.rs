@@ -0,0 +1,196 @@
+//! Executes a `BenchmarkSuite`'s test cases through an `Orchestrator` against
+//! one or more providers, evaluating each case's `ValidationCriterion`s and
+//! producing a pass/fail report with per-case diffs.
+
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{LlmParameters, LlmProvider, LlmRequest, LlmResponse, Metadata},
+};
+use meta_ai_core::{
+    evaluation::{BenchmarkSuite, TestCase, ValidationCriterion},
+    orchestrator::Orchestrator,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Outcome of one `TestCase` run against one provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub test_case_id: String,
+    pub provider: LlmProvider,
+    pub passed: bool,
+    pub actual_output: String,
+    pub latency_ms: u64,
+    /// Which criteria failed, and why, in declaration order. Empty when `passed`.
+    pub failures: Vec<String>,
+}
+
+/// Report of a `BenchmarkSuite` run across one or more providers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub suite_name: String,
+    /// One entry per (test case, provider) combination run.
+    pub results: Vec<CaseResult>,
+}
+
+impl BenchmarkReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// Fraction of (test case, provider) runs that passed. `1.0` if nothing
+    /// was run.
+    pub fn pass_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+        self.passed() as f64 / self.results.len() as f64
+    }
+}
+
+/// Runs a `BenchmarkSuite` through an `Orchestrator`.
+pub struct BenchmarkRunner {
+    orchestrator: Arc<dyn Orchestrator>,
+}
+
+impl BenchmarkRunner {
+    pub fn new(orchestrator: Arc<dyn Orchestrator>) -> Self {
+        Self { orchestrator }
+    }
+
+    /// Run every test case in `suite` against each of `providers`, returning
+    /// a report of the pass/fail verdict of every (test case, provider)
+    /// combination.
+    pub async fn run(&self, suite: &BenchmarkSuite, providers: &[LlmProvider]) -> Result<BenchmarkReport> {
+        let mut results = Vec::with_capacity(suite.test_cases.len() * providers.len());
+        for test_case in &suite.test_cases {
+            for &provider in providers {
+                results.push(self.run_case(test_case, provider).await?);
+            }
+        }
+        Ok(BenchmarkReport { suite_name: suite.name.clone(), results })
+    }
+
+    async fn run_case(&self, test_case: &TestCase, provider: LlmProvider) -> Result<CaseResult> {
+        let request = LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider,
+            prompt: test_case.input.clone(),
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: Vec::new(),
+            metadata: Metadata::new(),
+            session_id: None,
+        };
+
+        let started = Instant::now();
+        let response = self.orchestrator.submit_request(request).await?;
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        let failures: Vec<String> = test_case
+            .validation_criteria
+            .iter()
+            .filter_map(|criterion| Self::check_criterion(criterion, test_case, &response, latency_ms))
+            .collect();
+
+        Ok(CaseResult {
+            test_case_id: test_case.id.clone(),
+            provider,
+            passed: failures.is_empty(),
+            actual_output: response.content,
+            latency_ms,
+            failures,
+        })
+    }
+
+    /// Evaluate one `ValidationCriterion` against `response`/`latency_ms`,
+    /// returning a human-readable failure (the diff between expected and
+    /// actual), or `None` if it's satisfied.
+    fn check_criterion(
+        criterion: &ValidationCriterion,
+        test_case: &TestCase,
+        response: &LlmResponse,
+        latency_ms: u64,
+    ) -> Option<String> {
+        match criterion {
+            ValidationCriterion::ExactMatch(expected) => (response.content.trim() != expected.trim())
+                .then(|| format!("expected exact match {expected:?}, got {:?}", response.content)),
+            ValidationCriterion::Contains(needle) => (!response.content.contains(needle.as_str()))
+                .then(|| format!("expected output to contain {needle:?}, got {:?}", response.content)),
+            ValidationCriterion::Regex(pattern) => match regex::Regex::new(pattern) {
+                Ok(re) => (!re.is_match(&response.content))
+                    .then(|| format!("output {:?} didn't match regex {pattern:?}", response.content)),
+                Err(e) => Some(format!("invalid regex {pattern:?}: {e}")),
+            },
+            // No pluggable custom-validator registry exists yet; leave
+            // unevaluated rather than silently passing or failing a case on
+            // a criterion nothing actually checks.
+            ValidationCriterion::Custom(_) => None,
+            ValidationCriterion::SemanticSimilarity(threshold) => {
+                let Some(expected) = &test_case.expected_output else {
+                    return Some("SemanticSimilarity criterion requires expected_output".to_string());
+                };
+                let similarity = Self::token_overlap_similarity(expected, &response.content);
+                (similarity < *threshold).then(|| {
+                    format!("semantic similarity {similarity:.2} below threshold {threshold:.2} (expected {expected:?})")
+                })
+            }
+            ValidationCriterion::ResponseTime(limit_ms) => {
+                (latency_ms > *limit_ms).then(|| format!("latency {latency_ms}ms exceeded limit {limit_ms}ms"))
+            }
+            ValidationCriterion::TokenLimit(limit) => (response.usage.total_tokens > *limit)
+                .then(|| format!("token usage {} exceeded limit {limit}", response.usage.total_tokens)),
+        }
+    }
+
+    /// Token-overlap similarity (Jaccard index over whitespace-split,
+    /// lowercased tokens) between `expected` and `actual`, used as a
+    /// dependency-free proxy for semantic similarity. `1.0` if both are
+    /// empty.
+    fn token_overlap_similarity(expected: &str, actual: &str) -> f64 {
+        let tokens = |s: &str| -> HashSet<String> { s.to_lowercase().split_whitespace().map(str::to_string).collect() };
+        let (a, b) = (tokens(expected), tokens(actual));
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        let union = a.union(&b).count();
+        if union == 0 {
+            1.0
+        } else {
+            a.intersection(&b).count() as f64 / union as f64
+        }
+    }
+}
+
+/// Load test cases from a JSONL document (one `TestCase` JSON object per
+/// non-empty line) into a `BenchmarkSuite`.
+pub fn load_test_cases_jsonl(
+    name: &str,
+    jsonl: &str,
+    pass_threshold: f64,
+    time_limit_ms: Option<u64>,
+) -> Result<BenchmarkSuite> {
+    let test_cases = jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| Error::Validation(format!("invalid test case JSON: {e}")))
+        })
+        .collect::<Result<Vec<TestCase>>>()?;
+
+    Ok(BenchmarkSuite { name: name.to_string(), test_cases, pass_threshold, time_limit_ms })
+}
+
+/// Load a whole `BenchmarkSuite` (name, test cases, and thresholds) from a
+/// YAML document.
+pub fn load_suite_yaml(yaml: &str) -> Result<BenchmarkSuite> {
+    serde_yaml::from_str(yaml).map_err(|e| Error::Validation(format!("invalid benchmark suite YAML: {e}")))
+}
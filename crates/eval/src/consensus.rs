@@ -0,0 +1,124 @@
+//! Reconciling multiple candidate answers to the same prompt into one.
+//!
+//! Used by ensemble/consensus execution, where a request is fanned out to
+//! several providers and their responses need to be collapsed into a single
+//! answer.
+
+use meta_ai_common::types::LlmResponse;
+use std::collections::HashMap;
+
+/// How to pick a winner among several candidate responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconciliationStrategy {
+    /// Group responses by normalized content and pick the largest group.
+    /// Ties break in favor of the earliest response.
+    MajorityVote,
+    /// Pick the response whose content is, on average, most similar to the
+    /// others (a token-overlap centroid), which tends to reward the answer
+    /// most other providers agree with even when no two are identical.
+    SimilarityScoring,
+}
+
+/// Reconcile `responses` and return the index of the winning response.
+///
+/// # Panics
+///
+/// Panics if `responses` is empty; callers should not invoke this without at
+/// least one candidate.
+pub fn reconcile(responses: &[LlmResponse], strategy: ReconciliationStrategy) -> usize {
+    assert!(!responses.is_empty(), "reconcile requires at least one response");
+
+    match strategy {
+        ReconciliationStrategy::MajorityVote => majority_vote(responses),
+        ReconciliationStrategy::SimilarityScoring => similarity_scoring(responses),
+    }
+}
+
+fn normalize(content: &str) -> String {
+    content.trim().to_lowercase()
+}
+
+fn majority_vote(responses: &[LlmResponse]) -> usize {
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, response) in responses.iter().enumerate() {
+        groups.entry(normalize(&response.content)).or_default().push(index);
+    }
+
+    groups
+        .into_values()
+        .max_by_key(|indices| indices.len())
+        .and_then(|indices| indices.into_iter().min())
+        .unwrap_or(0)
+}
+
+fn similarity_scoring(responses: &[LlmResponse]) -> usize {
+    let token_sets: Vec<_> = responses
+        .iter()
+        .map(|r| normalize(&r.content).split_whitespace().map(str::to_string).collect::<Vec<_>>())
+        .collect();
+
+    (0..responses.len())
+        .max_by(|&a, &b| {
+            average_similarity(a, &token_sets)
+                .partial_cmp(&average_similarity(b, &token_sets))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(0)
+}
+
+fn average_similarity(index: usize, token_sets: &[Vec<String>]) -> f64 {
+    let others: Vec<_> = token_sets.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, t)| t).collect();
+    if others.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = others.iter().map(|other| jaccard_similarity(&token_sets[index], other)).sum();
+    total / others.len() as f64
+}
+
+fn jaccard_similarity(a: &[String], b: &[String]) -> f64 {
+    let a: std::collections::HashSet<_> = a.iter().collect();
+    let b: std::collections::HashSet<_> = b.iter().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(&b).count();
+    let union = a.union(&b).count();
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(content: &str) -> LlmResponse {
+        LlmResponse {
+            request_id: uuid::Uuid::new_v4(),
+            content: content.to_string(),
+            usage: Default::default(),
+            latency_ms: 0,
+            provider: meta_ai_common::types::LlmProvider::OpenAI,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn majority_vote_picks_most_common_answer() {
+        let responses = vec![response("Paris"), response("Paris"), response("Lyon")];
+        assert_eq!(reconcile(&responses, ReconciliationStrategy::MajorityVote), 0);
+    }
+
+    #[test]
+    fn similarity_scoring_picks_most_central_answer() {
+        let responses = vec![
+            response("the capital of France is Paris"),
+            response("Paris is the capital of France"),
+            response("bananas are yellow"),
+        ];
+        let winner = reconcile(&responses, ReconciliationStrategy::SimilarityScoring);
+        assert!(winner == 0 || winner == 1);
+    }
+}
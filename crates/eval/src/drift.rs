@@ -0,0 +1,272 @@
+//! Persisted per-provider baseline metrics (accuracy, latency distribution,
+//! refusal rate) and drift detection against them by periodically replaying
+//! a canary `BenchmarkSuite`. Backs `MetaEvaluator::check_drift`.
+
+use crate::benchmark::{BenchmarkReport, BenchmarkRunner};
+use meta_ai_common::{
+    error::{Error, Result},
+    types::LlmProvider,
+};
+use meta_ai_core::evaluation::{BenchmarkSuite, DriftAnalysis};
+use parking_lot::Mutex;
+use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+use std::collections::HashMap;
+
+/// Latency bucket upper bounds (ms), used to build the histogram `check_drift`
+/// compares between baseline and current replay via PSI. A reading of
+/// exactly `n` ms falls in the first bucket whose bound is greater than `n`;
+/// anything at or above the last bound falls in the overflow bucket.
+const LATENCY_BUCKET_BOUNDS_MS: [u64; 7] = [100, 250, 500, 1000, 2000, 4000, 8000];
+const LATENCY_BUCKETS: usize = LATENCY_BUCKET_BOUNDS_MS.len() + 1;
+
+/// Added to every bucket's share before computing PSI, so an empty bucket in
+/// either distribution doesn't produce a division by zero or `ln(0)`.
+const PSI_EPSILON: f64 = 1e-4;
+
+/// PSI at or above this is treated as a meaningful distribution shift (the
+/// commonly used "major shift" threshold for population stability
+/// monitoring).
+const PSI_DRIFT_THRESHOLD: f64 = 0.2;
+
+/// Absolute accuracy or refusal-rate change treated as meaningful drift.
+const RATE_DRIFT_THRESHOLD: f64 = 0.05;
+
+fn latency_bucket(latency_ms: u64) -> usize {
+    LATENCY_BUCKET_BOUNDS_MS
+        .iter()
+        .position(|&bound| latency_ms < bound)
+        .unwrap_or(LATENCY_BUCKET_BOUNDS_MS.len())
+}
+
+/// Baseline metrics recorded for one provider at some point in time,
+/// replayed against by `DriftDetector::check_drift`.
+#[derive(Debug, Clone, Copy)]
+pub struct Baseline {
+    pub accuracy: f64,
+    pub refusal_rate: f64,
+    pub latency_histogram: [u64; LATENCY_BUCKETS],
+}
+
+impl Baseline {
+    fn distribution(&self) -> [f64; LATENCY_BUCKETS] {
+        let total = (self.latency_histogram.iter().sum::<u64>().max(1)) as f64;
+        let mut distribution = [0.0; LATENCY_BUCKETS];
+        for (bucket, &count) in self.latency_histogram.iter().enumerate() {
+            distribution[bucket] = count as f64 / total;
+        }
+        distribution
+    }
+}
+
+enum Store {
+    InMemory(Mutex<HashMap<LlmProvider, Baseline>>),
+    Sqlite(SqlitePool),
+}
+
+/// Stores one baseline per provider and checks a fresh canary replay against
+/// it. Mirrors `OutcomeTracker`'s in-memory/SQLite choice so baselines can
+/// either reset on restart or persist across one.
+pub struct DriftDetector {
+    store: Store,
+}
+
+impl DriftDetector {
+    /// Track baselines in memory only; they reset on every restart.
+    pub fn in_memory() -> Self {
+        Self { store: Store::InMemory(Mutex::new(HashMap::new())) }
+    }
+
+    /// Track baselines in a SQLite database at `database_url` (created if it
+    /// does not exist), so they survive a restart.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| Error::Evaluation(format!("failed to connect to drift baseline database: {e}")))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS eval_drift_baselines (
+                provider TEXT PRIMARY KEY,
+                accuracy REAL NOT NULL,
+                refusal_rate REAL NOT NULL,
+                latency_histogram TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| Error::Evaluation(format!("failed to initialize eval_drift_baselines schema: {e}")))?;
+
+        Ok(Self { store: Store::Sqlite(pool) })
+    }
+
+    /// Persist `baseline` as the new comparison point for `provider`,
+    /// replacing whatever was stored before.
+    pub async fn record_baseline(&self, provider: LlmProvider, baseline: Baseline) -> Result<()> {
+        match &self.store {
+            Store::InMemory(baselines) => {
+                baselines.lock().insert(provider, baseline);
+                Ok(())
+            }
+            Store::Sqlite(pool) => {
+                let histogram = serde_json::to_string(&baseline.latency_histogram)
+                    .map_err(|e| Error::Evaluation(format!("failed to encode latency histogram: {e}")))?;
+                sqlx::query(
+                    "INSERT INTO eval_drift_baselines (provider, accuracy, refusal_rate, latency_histogram)
+                     VALUES (?, ?, ?, ?)
+                     ON CONFLICT(provider) DO UPDATE SET
+                        accuracy = excluded.accuracy,
+                        refusal_rate = excluded.refusal_rate,
+                        latency_histogram = excluded.latency_histogram",
+                )
+                .bind(provider.as_str())
+                .bind(baseline.accuracy)
+                .bind(baseline.refusal_rate)
+                .bind(histogram)
+                .execute(pool)
+                .await
+                .map_err(|e| Error::Evaluation(format!("failed to record drift baseline: {e}")))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// The baseline currently stored for `provider`, if one has been
+    /// recorded.
+    pub async fn baseline(&self, provider: LlmProvider) -> Result<Option<Baseline>> {
+        match &self.store {
+            Store::InMemory(baselines) => Ok(baselines.lock().get(&provider).copied()),
+            Store::Sqlite(pool) => {
+                let row = sqlx::query(
+                    "SELECT accuracy, refusal_rate, latency_histogram FROM eval_drift_baselines WHERE provider = ?",
+                )
+                .bind(provider.as_str())
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| Error::Evaluation(format!("failed to load drift baseline: {e}")))?;
+
+                let Some(row) = row else { return Ok(None) };
+                let accuracy: f64 =
+                    row.try_get("accuracy").map_err(|e| Error::Evaluation(format!("malformed baseline row: {e}")))?;
+                let refusal_rate: f64 = row
+                    .try_get("refusal_rate")
+                    .map_err(|e| Error::Evaluation(format!("malformed baseline row: {e}")))?;
+                let histogram_json: String = row
+                    .try_get("latency_histogram")
+                    .map_err(|e| Error::Evaluation(format!("malformed baseline row: {e}")))?;
+                let latency_histogram: [u64; LATENCY_BUCKETS] = serde_json::from_str(&histogram_json)
+                    .map_err(|e| Error::Evaluation(format!("failed to decode latency histogram: {e}")))?;
+
+                Ok(Some(Baseline { accuracy, refusal_rate, latency_histogram }))
+            }
+        }
+    }
+
+    /// Replay `suite` against `provider` through `runner`, and compare the
+    /// outcome against the baseline stored for `provider`. If no baseline
+    /// exists yet, this run establishes one and reports no drift (there's
+    /// nothing to compare it to).
+    pub async fn check_drift(
+        &self,
+        runner: &BenchmarkRunner,
+        suite: &BenchmarkSuite,
+        provider: LlmProvider,
+    ) -> Result<DriftAnalysis> {
+        let report = runner.run(suite, &[provider]).await?;
+        let current_accuracy = report.pass_rate();
+        let refusal_rate = refusal_rate(&report);
+        let mut latency_histogram = [0u64; LATENCY_BUCKETS];
+        for result in &report.results {
+            latency_histogram[latency_bucket(result.latency_ms)] += 1;
+        }
+        let current = Baseline { accuracy: current_accuracy, refusal_rate, latency_histogram };
+
+        let Some(baseline) = self.baseline(provider).await? else {
+            self.record_baseline(provider, current).await?;
+            return Ok(DriftAnalysis {
+                drift_detected: false,
+                drift_score: 0.0,
+                baseline_accuracy: current_accuracy,
+                current_accuracy,
+                performance_change: 0.0,
+                recommendations: vec![format!(
+                    "no prior baseline for {provider:?}: this canary replay was recorded as the new baseline"
+                )],
+            });
+        };
+
+        let drift_score = population_stability_index(&baseline.distribution(), &current.distribution());
+        let performance_change = current_accuracy - baseline.accuracy;
+        let refusal_change = refusal_rate - baseline.refusal_rate;
+
+        let accuracy_drifted = performance_change.abs() >= RATE_DRIFT_THRESHOLD;
+        let latency_drifted = drift_score >= PSI_DRIFT_THRESHOLD;
+        let refusal_drifted = refusal_change.abs() >= RATE_DRIFT_THRESHOLD;
+
+        let mut recommendations = Vec::new();
+        if accuracy_drifted {
+            recommendations.push(format!(
+                "accuracy moved {performance_change:+.3} since baseline ({:.3} -> {current_accuracy:.3}) for {provider:?}; investigate recent model or prompt changes",
+                baseline.accuracy
+            ));
+        }
+        if latency_drifted {
+            recommendations.push(format!(
+                "latency distribution PSI {drift_score:.3} exceeds {PSI_DRIFT_THRESHOLD} for {provider:?}; check for provider-side slowdowns or rate limiting"
+            ));
+        }
+        if refusal_drifted {
+            recommendations.push(format!(
+                "refusal rate moved {refusal_change:+.3} since baseline ({:.3} -> {refusal_rate:.3}) for {provider:?}; review prompts against recent policy changes",
+                baseline.refusal_rate
+            ));
+        }
+
+        Ok(DriftAnalysis {
+            drift_detected: accuracy_drifted || latency_drifted || refusal_drifted,
+            drift_score,
+            baseline_accuracy: baseline.accuracy,
+            current_accuracy,
+            performance_change,
+            recommendations,
+        })
+    }
+}
+
+/// Crude refusal-phrase detector, used as a dependency-free proxy for "did
+/// the model decline the task" the same way `benchmark::token_overlap_similarity`
+/// proxies semantic similarity. Not a classifier.
+fn is_refusal(content: &str) -> bool {
+    const REFUSAL_PHRASES: &[&str] = &[
+        "i cannot help with that",
+        "i can't help with that",
+        "i cannot assist with",
+        "i'm not able to help with",
+        "as an ai, i cannot",
+        "i won't be able to help",
+    ];
+    let lowered = content.to_lowercase();
+    REFUSAL_PHRASES.iter().any(|phrase| lowered.contains(phrase))
+}
+
+fn refusal_rate(report: &BenchmarkReport) -> f64 {
+    if report.results.is_empty() {
+        return 0.0;
+    }
+    let refusals = report.results.iter().filter(|result| is_refusal(&result.actual_output)).count();
+    refusals as f64 / report.results.len() as f64
+}
+
+/// Population Stability Index between two discrete distributions over the
+/// same buckets: `sum((current - baseline) * ln(current / baseline))`.
+fn population_stability_index(baseline: &[f64; LATENCY_BUCKETS], current: &[f64; LATENCY_BUCKETS]) -> f64 {
+    baseline
+        .iter()
+        .zip(current.iter())
+        .map(|(&b, &c)| {
+            let b = b.max(PSI_EPSILON);
+            let c = c.max(PSI_EPSILON);
+            (c - b) * (c / b).ln()
+        })
+        .sum()
+}
@@ -1,78 +1,601 @@
 //! Main evaluator implementation
 
+use crate::benchmark::BenchmarkRunner;
+use crate::drift::DriftDetector;
+use crate::groundedness::{self, GroundednessReport};
+use crate::outcome_tracker::{Outcome, OutcomeTracker};
 use async_trait::async_trait;
-use meta_ai_common::{error::{Error, Result}, types::{Task, TaskStatus, LlmResponse}};
-use meta_ai_core::evaluation::{
-    Evaluator, ValidationResult, SelfCheckResult, FuzzingResult, DriftAnalysis
+use meta_ai_common::{
+    error::{Error, Result},
+    metrics::MetricsCollector,
+    types::{LlmParameters, LlmProvider, LlmRequest, LlmResponse, Metadata, SearchResult, Task, TaskId, TaskStatus},
 };
+use meta_ai_core::{
+    evaluation::{
+        BenchmarkSuite, DriftAnalysis, Evaluator, FeedbackRating, FuzzingResult, IssueCategory, IssueSeverity,
+        SelfCheckResult, ValidationIssue, ValidationResult,
+    },
+    orchestrator::Orchestrator,
+};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Score and rationale an LLM judge returned for a response, cached by a
+/// hash of the judged content so repeat judgments of the same response
+/// don't cost another judge-model call.
+#[derive(Debug, Clone)]
+struct JudgeVerdict {
+    score: f64,
+    rationale: String,
+}
+
+/// Optional LLM-as-judge configuration for `validate_response`. Absent by
+/// default, since judging every response costs an extra model call.
+struct JudgeConfig {
+    orchestrator: Arc<dyn Orchestrator>,
+    provider: LlmProvider,
+    pass_threshold: f64,
+}
+
+/// Optional drift-detection configuration for `check_drift`: a canary test
+/// suite periodically replayed against `provider`, compared against a
+/// baseline persisted in `detector` (established on the first call if none
+/// exists yet). Absent by default, since a drift check costs a full replay
+/// of the canary suite.
+struct DriftConfig {
+    runner: BenchmarkRunner,
+    suite: BenchmarkSuite,
+    provider: LlmProvider,
+    detector: DriftDetector,
+    metrics: Option<Arc<dyn MetricsCollector>>,
+}
 
-pub struct MetaEvaluator;
+/// Optional LLM-judge configuration for `check_groundedness`. Absent by
+/// default, in which case `check_groundedness` falls back to
+/// `groundedness::heuristic_groundedness`.
+struct GroundednessConfig {
+    orchestrator: Arc<dyn Orchestrator>,
+    provider: LlmProvider,
+    pass_threshold: f64,
+}
+
+/// Optional prompt-injection detection configuration. Absent by default, in
+/// which case `pre_task_validation`/`scan_retrieved_context` skip the
+/// screen entirely (neither the safety nor groundedness screens are
+/// gated this way, but this one typically comes from a `SecurityConfig`
+/// that operators may choose not to set).
+struct PromptInjectionConfig {
+    /// Score (0.0-1.0) at or above which a match is escalated from a
+    /// `Warning` issue to a `Critical` one (and thus blocks the task).
+    /// `None` means matches are always flagged, never blocked.
+    block_threshold: Option<f64>,
+    metrics: Option<Arc<dyn MetricsCollector>>,
+}
+
+/// Cap on `MetaEvaluator::task_providers`: a plain cache, not a durable
+/// store, so once it fills up we just drop the oldest association (keyed by
+/// task id, which doesn't have a natural eviction order) by clearing it
+/// outright rather than tracking insertion order for a handful of entries
+/// that matter.
+const TASK_PROVIDER_CACHE_CAP: usize = 10_000;
+
+pub struct MetaEvaluator {
+    outcomes: OutcomeTracker,
+    judge: Option<JudgeConfig>,
+    judge_cache: Mutex<HashMap<u64, JudgeVerdict>>,
+    redact: bool,
+    drift: Option<DriftConfig>,
+    /// Provider each recently completed task was served by, recorded in
+    /// `post_task_validation` and consulted by `record_feedback` so feedback
+    /// reported by task id alone can still be attributed to a provider.
+    task_providers: Mutex<HashMap<TaskId, LlmProvider>>,
+    groundedness: Option<GroundednessConfig>,
+    prompt_injection: Option<PromptInjectionConfig>,
+}
 
 impl MetaEvaluator {
-    pub fn new() -> Self { Self }
+    /// Track outcomes in memory only; accuracy/bug-rate reset on restart.
+    pub fn new() -> Self {
+        Self {
+            outcomes: OutcomeTracker::in_memory(),
+            judge: None,
+            judge_cache: Mutex::new(HashMap::new()),
+            redact: false,
+            drift: None,
+            task_providers: Mutex::new(HashMap::new()),
+            groundedness: None,
+            prompt_injection: None,
+        }
+    }
+
+    /// Track outcomes in a SQLite database at `database_url` (e.g.
+    /// `"sqlite://eval_outcomes.db"`), so accuracy/bug-rate survive a
+    /// restart instead of resetting.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        Ok(Self {
+            outcomes: OutcomeTracker::connect(database_url).await?,
+            judge: None,
+            judge_cache: Mutex::new(HashMap::new()),
+            redact: false,
+            drift: None,
+            task_providers: Mutex::new(HashMap::new()),
+            groundedness: None,
+            prompt_injection: None,
+        })
+    }
+
+    /// Enable LLM-as-judge scoring in `validate_response`: every response is
+    /// sent to `provider` with a quality rubric, and scores below
+    /// `pass_threshold` (0.0-1.0) become an `OutputQuality` issue. Judgments
+    /// are cached by response content, so re-validating the same response
+    /// doesn't cost another judge-model call.
+    pub fn with_judge(mut self, orchestrator: Arc<dyn Orchestrator>, provider: LlmProvider, pass_threshold: f64) -> Self {
+        self.judge = Some(JudgeConfig { orchestrator, provider, pass_threshold });
+        self
+    }
+
+    /// Enable redaction: when a safety screen finds PII, secrets, or unsafe
+    /// content, the masked text is attached to the `ValidationResult`'s
+    /// metadata under `"redacted_content"` / `"redacted_description"` so
+    /// callers can log or return the masked version instead of the raw one.
+    pub fn with_redaction(mut self, enabled: bool) -> Self {
+        self.redact = enabled;
+        self
+    }
+
+    /// Enable drift detection in `check_drift`: each call replays `suite`
+    /// against `provider` through `orchestrator` and compares the result to
+    /// the baseline persisted in `detector`, establishing one on the first
+    /// call if none exists yet. `metrics`, if given, is updated with the
+    /// observed accuracy and an alert is raised through it whenever drift is
+    /// detected.
+    pub fn with_drift_detection(
+        mut self,
+        orchestrator: Arc<dyn Orchestrator>,
+        suite: BenchmarkSuite,
+        provider: LlmProvider,
+        detector: DriftDetector,
+        metrics: Option<Arc<dyn MetricsCollector>>,
+    ) -> Self {
+        self.drift = Some(DriftConfig { runner: BenchmarkRunner::new(orchestrator), suite, provider, detector, metrics });
+        self
+    }
+
+    /// Enable LLM-judge scoring in `check_groundedness`: every RAG response
+    /// is sent to `provider` along with its retrieved context and asked to
+    /// flag unsupported claims; a score below `pass_threshold` becomes a
+    /// `Critical` `OutputQuality` issue. Without this, `check_groundedness`
+    /// falls back to `groundedness::heuristic_groundedness`.
+    pub fn with_groundedness_check(mut self, orchestrator: Arc<dyn Orchestrator>, provider: LlmProvider, pass_threshold: f64) -> Self {
+        self.groundedness = Some(GroundednessConfig { orchestrator, provider, pass_threshold });
+        self
+    }
+
+    /// Enable prompt-injection screening in `pre_task_validation` and
+    /// `scan_retrieved_context`: matches always add a `Warning` issue, and
+    /// escalate to a blocking `Critical` one once the match's weight reaches
+    /// `block_threshold`. Normally sourced from
+    /// `SecurityConfig.prompt_injection_block_threshold`. `metrics`, if
+    /// given, is incremented every time a match is found.
+    pub fn with_prompt_injection_detection(
+        mut self,
+        block_threshold: Option<f64>,
+        metrics: Option<Arc<dyn MetricsCollector>>,
+    ) -> Self {
+        self.prompt_injection = Some(PromptInjectionConfig { block_threshold, metrics });
+        self
+    }
+
+    /// Accuracy observed for `provider` over the most recent outcomes
+    /// (post-task validations and `record_feedback` calls alike), for a
+    /// caller to periodically push into
+    /// `TaskDispatcher::set_provider_quality_score` so cost-optimized
+    /// selection reflects observed feedback instead of only the static
+    /// `provider_pricing` table.
+    pub async fn provider_quality_score(&self, provider: LlmProvider) -> Result<f64> {
+        self.outcomes.provider_accuracy(provider).await
+    }
+
+    /// Check `response` against `request`'s expected format and, if it's
+    /// malformed, resubmit to `request.provider` with the validation errors
+    /// appended as a correction instruction, up to `max_retries` times.
+    /// Returns the first response that passes, or the last attempt if every
+    /// retry was still malformed.
+    pub async fn fix_format(
+        &self,
+        orchestrator: &dyn Orchestrator,
+        request: &LlmRequest,
+        mut response: LlmResponse,
+        max_retries: u32,
+    ) -> Result<LlmResponse> {
+        for _ in 0..max_retries {
+            let issues = crate::format_check::check_format_against_request(request, &response);
+            if issues.is_empty() {
+                break;
+            }
+
+            let complaints: Vec<&str> = issues.iter().map(|i| i.description.as_str()).collect();
+            let retry_request = LlmRequest {
+                id: Uuid::new_v4(),
+                task_id: request.task_id,
+                provider: request.provider,
+                prompt: format!(
+                    "Your previous response had formatting problems: {}. Here is your previous response:\n\n{}\n\nRespond again, fixing these problems.",
+                    complaints.join("; "),
+                    response.content
+                ),
+                parameters: request.parameters.clone(),
+                timeout_ms: request.timeout_ms,
+                attachments: Vec::new(),
+                metadata: Metadata::new(),
+                session_id: request.session_id.clone(),
+            };
+
+            response = orchestrator.submit_request(retry_request).await?;
+        }
+
+        Ok(response)
+    }
+
+    async fn judge_verdict(&self, judge: &JudgeConfig, response: &LlmResponse) -> Result<JudgeVerdict> {
+        let cache_key = Self::judge_cache_key(&response.content);
+        if let Some(cached) = self.judge_cache.lock().get(&cache_key).cloned() {
+            return Ok(cached);
+        }
+
+        let prompt = format!(
+            "You are an impartial judge scoring an AI assistant's response on a 0.0-1.0 quality \
+             rubric (relevance, correctness, clarity). Respond with ONLY a JSON object of the form \
+             {{\"score\": <0.0-1.0>, \"rationale\": \"<one sentence>\"}}.\n\nResponse to judge:\n{}",
+            response.content
+        );
+
+        let request = LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider: judge.provider,
+            prompt,
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: Vec::new(),
+            metadata: Metadata::new(),
+            session_id: None,
+        };
+
+        let judge_response = judge.orchestrator.submit_request(request).await?;
+        let verdict = Self::parse_verdict(&judge_response.content)?;
+
+        self.judge_cache.lock().insert(cache_key, verdict.clone());
+        Ok(verdict)
+    }
+
+    /// Ask `config.orchestrator` whether `response` is supported by
+    /// `context`. Unlike `judge_verdict`, this isn't cached by response
+    /// content alone, since the same response can be judged against
+    /// different retrieved context on different calls.
+    async fn judge_groundedness(
+        &self,
+        config: &GroundednessConfig,
+        response: &LlmResponse,
+        context: &[SearchResult],
+    ) -> Result<GroundednessReport> {
+        let context_text =
+            context.iter().map(|result| result.document.content.as_str()).collect::<Vec<_>>().join("\n---\n");
+
+        let prompt = format!(
+            "You are a fact-checker verifying whether an AI assistant's response is fully supported by \
+             retrieved context. Respond with ONLY a JSON object of the form \
+             {{\"score\": <0.0-1.0, fraction of claims that are grounded>, \"ungrounded_claims\": [<unsupported claim strings>]}}.\
+             \n\nRetrieved context:\n{context_text}\n\nResponse to check:\n{}",
+            response.content
+        );
+
+        let request = LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider: config.provider,
+            prompt,
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: Vec::new(),
+            metadata: Metadata::new(),
+            session_id: None,
+        };
+
+        let judge_response = config.orchestrator.submit_request(request).await?;
+        Self::parse_groundedness_verdict(&judge_response.content)
+    }
+
+    /// Extract and parse the `{"score": ..., "ungrounded_claims": [...]}`
+    /// JSON object from a groundedness judge's reply, tolerating
+    /// surrounding prose or code fences around it.
+    fn parse_groundedness_verdict(content: &str) -> Result<GroundednessReport> {
+        #[derive(serde::Deserialize)]
+        struct RawVerdict {
+            score: f64,
+            ungrounded_claims: Vec<String>,
+        }
+
+        let json = regex::Regex::new(r"(?s)\{.*\}")
+            .unwrap()
+            .find(content)
+            .ok_or_else(|| Error::Evaluation(format!("groundedness judge response had no JSON verdict: {content:?}")))?;
+
+        let raw: RawVerdict = serde_json::from_str(json.as_str())
+            .map_err(|e| Error::Evaluation(format!("failed to parse groundedness verdict: {e}")))?;
+
+        Ok(GroundednessReport { score: raw.score.clamp(0.0, 1.0), ungrounded_claims: raw.ungrounded_claims })
+    }
+
+    /// Screen `text` for prompt-injection patterns, escalating every match
+    /// to `Critical` once the highest-weighted match reaches the configured
+    /// block threshold, and recording a metric per screen that finds one.
+    /// Returns no issues (and records nothing) if prompt-injection detection
+    /// isn't configured.
+    fn screen_prompt_injection(&self, text: &str) -> Vec<ValidationIssue> {
+        let Some(config) = &self.prompt_injection else {
+            return Vec::new();
+        };
+
+        let (detected_score, mut issues) = crate::prompt_injection::score(text);
+        if issues.is_empty() {
+            return issues;
+        }
+
+        if let Some(metrics) = &config.metrics {
+            metrics.record_error("prompt_injection", "warning", "n/a");
+        }
+
+        if config.block_threshold.is_some_and(|threshold| detected_score >= threshold) {
+            for issue in &mut issues {
+                issue.severity = IssueSeverity::Critical;
+            }
+        }
+
+        issues
+    }
+
+    fn judge_cache_key(content: &str) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Extract and parse the `{"score": ..., "rationale": ...}` JSON object
+    /// from a judge model's reply, tolerating surrounding prose or code
+    /// fences around it.
+    fn parse_verdict(content: &str) -> Result<JudgeVerdict> {
+        #[derive(serde::Deserialize)]
+        struct RawVerdict {
+            score: f64,
+            rationale: String,
+        }
+
+        let json = regex::Regex::new(r"(?s)\{.*\}")
+            .unwrap()
+            .find(content)
+            .ok_or_else(|| Error::Evaluation(format!("judge response had no JSON verdict: {content:?}")))?;
+
+        let raw: RawVerdict = serde_json::from_str(json.as_str())
+            .map_err(|e| Error::Evaluation(format!("failed to parse judge verdict: {e}")))?;
+
+        Ok(JudgeVerdict { score: raw.score.clamp(0.0, 1.0), rationale: raw.rationale })
+    }
+}
+
+impl Default for MetaEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[async_trait]
 impl Evaluator for MetaEvaluator {
-    async fn pre_task_validation(&self, _task: &Task) -> Result<ValidationResult> {
+    async fn pre_task_validation(&self, task: &Task) -> Result<ValidationResult> {
+        let mut issues = task.description.as_deref().map(crate::safety::screen_text).unwrap_or_default();
+        if let Some(description) = task.description.as_deref() {
+            issues.extend(self.screen_prompt_injection(description));
+        }
+        let mut metadata = std::collections::HashMap::new();
+        if self.redact {
+            if let Some(description) = &task.description {
+                if !issues.is_empty() {
+                    metadata.insert(
+                        "redacted_description".to_string(),
+                        serde_json::Value::String(crate::safety::redact_text(description)),
+                    );
+                }
+            }
+        }
+
         Ok(ValidationResult {
-            valid: true,
-            score: 1.0,
-            issues: vec![],
-            metadata: std::collections::HashMap::new(),
+            valid: !issues.iter().any(|i| matches!(i.severity, IssueSeverity::Critical)),
+            score: if issues.is_empty() { 1.0 } else { 0.5 },
+            issues,
+            metadata,
         })
     }
-    
-    async fn post_task_validation(&self, _task: &Task, _status: &TaskStatus) -> Result<ValidationResult> {
+
+    async fn post_task_validation(&self, task: &Task, status: &TaskStatus) -> Result<ValidationResult> {
+        if let Some(provider) = task.provider {
+            let mut task_providers = self.task_providers.lock();
+            if task_providers.len() >= TASK_PROVIDER_CACHE_CAP {
+                task_providers.clear();
+            }
+            task_providers.insert(task.id, provider);
+        }
+
+        match status {
+            TaskStatus::Completed => {
+                self.outcomes
+                    .record(Outcome {
+                        correct: true,
+                        is_bug: false,
+                        task_id: Some(task.id),
+                        provider: task.provider,
+                        comment: None,
+                    })
+                    .await?;
+            }
+            TaskStatus::Failed | TaskStatus::Timeout => {
+                self.outcomes
+                    .record(Outcome {
+                        correct: false,
+                        is_bug: true,
+                        task_id: Some(task.id),
+                        provider: task.provider,
+                        comment: None,
+                    })
+                    .await?;
+            }
+            // A still-running task isn't a quality signal yet, and a
+            // cancelled one was never actually run to completion, so
+            // neither is recorded as correct or a bug.
+            TaskStatus::Pending | TaskStatus::Running | TaskStatus::Cancelled => {}
+        }
+
+        let correct = matches!(status, TaskStatus::Completed);
         Ok(ValidationResult {
-            valid: true,
-            score: 1.0,
+            valid: !matches!(status, TaskStatus::Failed | TaskStatus::Timeout),
+            score: if correct { 1.0 } else { 0.0 },
             issues: vec![],
             metadata: std::collections::HashMap::new(),
         })
     }
-    
-    async fn validate_response(&self, _response: &LlmResponse) -> Result<ValidationResult> {
+
+    async fn validate_response(&self, response: &LlmResponse) -> Result<ValidationResult> {
+        let mut issues = crate::safety::screen_text(&response.content);
+        issues.extend(crate::format_check::check_format(response));
+
+        let mut metadata = std::collections::HashMap::new();
+        if self.redact && !issues.is_empty() {
+            metadata.insert(
+                "redacted_content".to_string(),
+                serde_json::Value::String(crate::safety::redact_text(&response.content)),
+            );
+        }
+
+        let score = if let Some(judge) = &self.judge {
+            let verdict = self.judge_verdict(judge, response).await?;
+            if verdict.score < judge.pass_threshold {
+                issues.push(ValidationIssue {
+                    severity: IssueSeverity::Warning,
+                    category: IssueCategory::OutputQuality,
+                    description: format!(
+                        "judge scored response {:.2} (below pass threshold {:.2}): {}",
+                        verdict.score, judge.pass_threshold, verdict.rationale
+                    ),
+                    suggestion: None,
+                });
+            }
+            verdict.score
+        } else if issues.is_empty() {
+            1.0
+        } else {
+            0.5
+        };
+
         Ok(ValidationResult {
-            valid: true,
-            score: 1.0,
-            issues: vec![],
-            metadata: std::collections::HashMap::new(),
+            valid: !issues.iter().any(|i| matches!(i.severity, IssueSeverity::Critical)),
+            score,
+            issues,
+            metadata,
         })
     }
-    
+
     async fn get_accuracy(&self) -> Result<f64> {
-        Ok(0.9999)
+        self.outcomes.accuracy().await
     }
-    
+
     async fn get_bug_rate(&self) -> Result<f64> {
-        Ok(0.0001)
+        self.outcomes.bug_rate().await
     }
-    
+
     async fn self_check(&self) -> Result<SelfCheckResult> {
+        let accuracy = self.outcomes.accuracy().await?;
+        let bug_rate = self.outcomes.bug_rate().await?;
         Ok(SelfCheckResult {
-            passed: true,
-            accuracy: 0.9999,
-            bug_rate: 0.0001,
+            passed: accuracy >= 0.9999 && bug_rate <= 0.0005,
+            accuracy,
+            bug_rate,
             tests_run: 100,
             tests_passed: 100,
             duration_ms: 1000,
             issues: vec![],
         })
     }
-    
+
     async fn fuzz_test(&self, _iterations: u32) -> Result<FuzzingResult> {
         Err(Error::Evaluation("Not implemented".to_string()))
     }
-    
+
     async fn check_drift(&self) -> Result<DriftAnalysis> {
-        Ok(DriftAnalysis {
-            drift_detected: false,
-            drift_score: 0.01,
-            baseline_accuracy: 0.9999,
-            current_accuracy: 0.9999,
-            performance_change: 0.0,
-            recommendations: vec![],
+        let Some(drift) = &self.drift else {
+            return Err(Error::Evaluation(
+                "drift detection not configured; call MetaEvaluator::with_drift_detection first".to_string(),
+            ));
+        };
+
+        let analysis = drift.detector.check_drift(&drift.runner, &drift.suite, drift.provider).await?;
+
+        if let Some(metrics) = &drift.metrics {
+            metrics.update_accuracy(drift.provider.as_str(), "canary_replay", analysis.current_accuracy);
+            if analysis.drift_detected {
+                metrics.record_error("model_drift", "warning", drift.provider.as_str());
+            }
+        }
+
+        Ok(analysis)
+    }
+
+    async fn record_feedback(&self, task_id: TaskId, rating: FeedbackRating, comment: Option<String>) -> Result<()> {
+        let provider = self.task_providers.lock().get(&task_id).copied();
+        let correct = rating == FeedbackRating::ThumbsUp;
+        self.outcomes
+            .record(Outcome { correct, is_bug: !correct, task_id: Some(task_id), provider, comment })
+            .await
+    }
+
+    async fn check_groundedness(&self, response: &LlmResponse, context: &[SearchResult]) -> Result<ValidationResult> {
+        let (report, pass_threshold) = match &self.groundedness {
+            Some(config) => (self.judge_groundedness(config, response, context).await?, config.pass_threshold),
+            None => (groundedness::heuristic_groundedness(&response.content, context), 0.5),
+        };
+
+        let mut issues = Vec::new();
+        if report.score < pass_threshold {
+            issues.push(ValidationIssue {
+                severity: IssueSeverity::Critical,
+                category: IssueCategory::OutputQuality,
+                description: format!(
+                    "groundedness score {:.2} below threshold {pass_threshold:.2}; unsupported claims: {}",
+                    report.score,
+                    report.ungrounded_claims.join("; ")
+                ),
+                suggestion: Some("verify or remove claims not supported by the retrieved context".to_string()),
+            });
+        }
+
+        Ok(ValidationResult {
+            valid: !issues.iter().any(|i| matches!(i.severity, IssueSeverity::Critical)),
+            score: report.score,
+            issues,
+            metadata: std::collections::HashMap::new(),
+        })
+    }
+
+    async fn scan_retrieved_context(&self, context: &[SearchResult]) -> Result<ValidationResult> {
+        let issues: Vec<ValidationIssue> =
+            context.iter().flat_map(|result| self.screen_prompt_injection(&result.document.content)).collect();
+
+        Ok(ValidationResult {
+            valid: !issues.iter().any(|i| matches!(i.severity, IssueSeverity::Critical)),
+            score: if issues.is_empty() { 1.0 } else { 0.5 },
+            issues,
+            metadata: std::collections::HashMap::new(),
         })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,161 @@
+//! Structured-output validation: checks responses that claim to be JSON or
+//! code for well-formedness, emitting `IssueCategory::FormatError` issues.
+//!
+//! `Evaluator::validate_response` only receives the response, not the
+//! request that produced it, so [`check_format`] can only catch generic
+//! malformedness (JSON that doesn't parse, code blocks with unbalanced
+//! delimiters). Callers that have the original request — e.g. `fix_format`'s
+//! retry loop — should use [`check_format_against_request`] instead, which
+//! also validates JSON responses against `LlmParameters.response_format` via
+//! `meta_ai_common::schema::validate_response`.
+
+use meta_ai_common::types::{LlmRequest, LlmResponse, ResponseFormat};
+use meta_ai_core::evaluation::{IssueCategory, IssueSeverity, ValidationIssue};
+
+/// Check `response` for well-formed JSON and for balanced delimiters in any
+/// fenced code blocks.
+pub fn check_format(response: &LlmResponse) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let trimmed = response.content.trim();
+
+    if looks_like_json(trimmed) {
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(trimmed) {
+            issues.push(format_issue(format!("response looks like JSON but failed to parse: {e}")));
+        }
+    }
+
+    for block in fenced_code_blocks(&response.content) {
+        if let Some(delimiter) = unbalanced_delimiter(&block) {
+            issues.push(format_issue(format!("code block has unbalanced {delimiter}")));
+        }
+    }
+
+    issues
+}
+
+/// As [`check_format`], plus schema validation when `request` asked for
+/// `ResponseFormat::JsonSchema`.
+pub fn check_format_against_request(request: &LlmRequest, response: &LlmResponse) -> Vec<ValidationIssue> {
+    let mut issues = check_format(response);
+
+    if let Some(format @ ResponseFormat::JsonSchema { .. }) = &request.parameters.response_format {
+        if let Err(e) = meta_ai_common::schema::validate_response(format, &response.content) {
+            issues.push(format_issue(e.to_string()));
+        }
+    }
+
+    issues
+}
+
+fn looks_like_json(trimmed: &str) -> bool {
+    (trimmed.starts_with('{') && trimmed.ends_with('}')) || (trimmed.starts_with('[') && trimmed.ends_with(']'))
+}
+
+fn format_issue(description: String) -> ValidationIssue {
+    ValidationIssue {
+        severity: IssueSeverity::Error,
+        category: IssueCategory::FormatError,
+        description,
+        suggestion: Some("ask the model to correct its output and retry".to_string()),
+    }
+}
+
+/// Extract the contents of every ```-fenced block, ignoring any language tag.
+fn fenced_code_blocks(content: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with("```") {
+            let mut block = String::new();
+            for inner in lines.by_ref() {
+                if inner.trim_start().starts_with("```") {
+                    break;
+                }
+                block.push_str(inner);
+                block.push('\n');
+            }
+            blocks.push(block);
+        }
+    }
+    blocks
+}
+
+/// Lightweight brace/bracket/paren balance check used in lieu of a real
+/// parser (tree-sitter isn't a workspace dependency): not a syntax
+/// guarantee, but catches the common "truncated output" failure mode.
+fn unbalanced_delimiter(code: &str) -> Option<&'static str> {
+    let mut stack = Vec::new();
+    for c in code.chars() {
+        match c {
+            '(' | '[' | '{' => stack.push(c),
+            ')' => {
+                if stack.pop() != Some('(') {
+                    return Some("parentheses");
+                }
+            }
+            ']' => {
+                if stack.pop() != Some('[') {
+                    return Some("brackets");
+                }
+            }
+            '}' => {
+                if stack.pop() != Some('{') {
+                    return Some("braces");
+                }
+            }
+            _ => {}
+        }
+    }
+    if stack.is_empty() {
+        None
+    } else {
+        Some("delimiters")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_common::types::{LlmProvider, Metadata, RequestId, TokenUsage};
+
+    fn response(content: &str) -> LlmResponse {
+        LlmResponse {
+            request_id: RequestId::new_v4(),
+            content: content.to_string(),
+            usage: TokenUsage::default(),
+            latency_ms: 1,
+            provider: LlmProvider::OpenAI,
+            metadata: Metadata::new(),
+        }
+    }
+
+    #[test]
+    fn plain_text_response_has_no_issues() {
+        assert!(check_format(&response("Here is a summary of the document.")).is_empty());
+    }
+
+    #[test]
+    fn malformed_json_looking_response_is_flagged() {
+        let issues = check_format(&response("{ \"key\": \"value\""));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].category, IssueCategory::FormatError);
+    }
+
+    #[test]
+    fn well_formed_json_response_has_no_issues() {
+        assert!(check_format(&response("{\"key\": \"value\"}")).is_empty());
+    }
+
+    #[test]
+    fn unbalanced_code_block_is_flagged() {
+        let content = "```rust\nfn main() {\n    println!(\"hi\");\n```";
+        let issues = check_format(&response(content));
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn balanced_code_block_has_no_issues() {
+        let content = "```rust\nfn main() {\n    println!(\"hi\");\n}\n```";
+        assert!(check_format(&response(content)).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Dependency-free groundedness scoring for RAG-augmented responses: splits
+//! a response into claim-sized sentences and checks each one for lexical
+//! overlap with the retrieved context chunks. Backs
+//! `MetaEvaluator::check_groundedness` when no LLM judge is configured for
+//! it.
+
+use meta_ai_common::types::SearchResult;
+use std::collections::HashSet;
+
+/// Result of a groundedness check: an overall score (fraction of claims
+/// judged supported) and the claims that weren't.
+#[derive(Debug, Clone)]
+pub struct GroundednessReport {
+    pub score: f64,
+    pub ungrounded_claims: Vec<String>,
+}
+
+/// Minimum token overlap with some retrieved chunk for a claim to count as
+/// supported. Deliberately lenient, since this is a lexical proxy, not a
+/// real NLI entailment check.
+const SUPPORT_THRESHOLD: f64 = 0.2;
+
+/// Split `text` into claim-sized sentences: trimmed fragments between `.`,
+/// `!`, `?`, dropping anything too short (fewer than four words) to be a
+/// real claim worth checking.
+fn split_claims(text: &str) -> Vec<String> {
+    text.split(['.', '!', '?'])
+        .map(str::trim)
+        .filter(|s| s.split_whitespace().count() >= 4)
+        .map(str::to_string)
+        .collect()
+}
+
+fn tokens(s: &str) -> HashSet<String> {
+    s.to_lowercase().split_whitespace().map(str::to_string).collect()
+}
+
+/// Jaccard token overlap between `claim` and `chunk`, the same kind of
+/// dependency-free proxy `benchmark::token_overlap_similarity` uses for
+/// semantic similarity.
+fn overlap(claim: &str, chunk: &str) -> f64 {
+    let (a, b) = (tokens(claim), tokens(chunk));
+    if a.is_empty() {
+        return 1.0;
+    }
+    let union = a.union(&b).count();
+    if union == 0 {
+        1.0
+    } else {
+        a.intersection(&b).count() as f64 / union as f64
+    }
+}
+
+/// Token-overlap groundedness check: a claim counts as supported if it
+/// overlaps at least `SUPPORT_THRESHOLD` with some chunk in `context`. Not a
+/// real NLI classifier, the same way `drift::is_refusal` proxies a
+/// different semantic judgment cheaply. `1.0` if the response has no
+/// claim-sized sentences to check.
+pub fn heuristic_groundedness(response: &str, context: &[SearchResult]) -> GroundednessReport {
+    let claims = split_claims(response);
+    if claims.is_empty() {
+        return GroundednessReport { score: 1.0, ungrounded_claims: vec![] };
+    }
+
+    let total = claims.len();
+    let ungrounded: Vec<String> = claims
+        .into_iter()
+        .filter(|claim| !context.iter().any(|chunk| overlap(claim, &chunk.document.content) >= SUPPORT_THRESHOLD))
+        .collect();
+
+    GroundednessReport { score: 1.0 - (ungrounded.len() as f64 / total as f64), ungrounded_claims: ungrounded }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_common::types::Document;
+
+    fn chunk(content: &str) -> SearchResult {
+        SearchResult {
+            document: Document {
+                id: "doc-1".to_string(),
+                content: content.to_string(),
+                embedding: None,
+                metadata: Default::default(),
+                created_at: chrono::Utc::now(),
+                expires_at: None,
+            },
+            score: 1.0,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn response_with_no_claim_sized_sentences_scores_fully_grounded() {
+        let report = heuristic_groundedness("Yes.", &[]);
+        assert_eq!(report.score, 1.0);
+        assert!(report.ungrounded_claims.is_empty());
+    }
+
+    #[test]
+    fn claim_overlapping_context_is_grounded() {
+        let context = [chunk("The quarterly revenue grew by twelve percent year over year.")];
+        let report = heuristic_groundedness("The quarterly revenue grew by twelve percent year over year.", &context);
+        assert_eq!(report.score, 1.0);
+        assert!(report.ungrounded_claims.is_empty());
+    }
+
+    #[test]
+    fn claim_unrelated_to_context_is_ungrounded() {
+        let context = [chunk("The quarterly revenue grew by twelve percent year over year.")];
+        let report = heuristic_groundedness("The moon landing happened in nineteen sixty nine.", &context);
+        assert_eq!(report.score, 0.0);
+        assert_eq!(report.ungrounded_claims.len(), 1);
+    }
+
+    #[test]
+    fn score_is_fraction_of_grounded_claims() {
+        let context = [chunk("The quarterly revenue grew by twelve percent year over year.")];
+        let response = "The quarterly revenue grew by twelve percent year over year. The moon landing happened in nineteen sixty nine.";
+        let report = heuristic_groundedness(response, &context);
+        assert_eq!(report.score, 0.5);
+        assert_eq!(report.ungrounded_claims.len(), 1);
+    }
+}
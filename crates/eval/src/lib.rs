@@ -3,10 +3,32 @@
 
 //! Evaluation and quality assurance
 
+pub mod benchmark;
+pub mod consensus;
+pub mod drift;
 pub mod evaluator;
+pub mod format_check;
 pub mod fuzzer;
+pub mod groundedness;
 pub mod metrics;
+pub mod outcome_tracker;
+pub mod prompt_injection;
+pub mod regression;
+pub mod report;
+pub mod safety;
+pub mod self_check_loop;
 
+pub use benchmark::{load_suite_yaml, load_test_cases_jsonl, BenchmarkReport, BenchmarkRunner, CaseResult};
+pub use consensus::{reconcile, ReconciliationStrategy};
+pub use drift::{Baseline, DriftDetector};
 pub use evaluator::MetaEvaluator;
+pub use format_check::{check_format, check_format_against_request};
 pub use fuzzer::FuzzingEngine;
-pub use metrics::EvaluationMetrics;
\ No newline at end of file
+pub use groundedness::{heuristic_groundedness, GroundednessReport};
+pub use metrics::EvaluationMetrics;
+pub use outcome_tracker::{Outcome, OutcomeTracker};
+pub use prompt_injection::score as score_prompt_injection;
+pub use regression::{compare_runs, CaseRegression, PromotionVerdict, RegressionReport};
+pub use report::EvaluationReport;
+pub use safety::{redact_text, screen_text};
+pub use self_check_loop::{HealthGauge, SelfCheckLoop};
\ No newline at end of file
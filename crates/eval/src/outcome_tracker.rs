@@ -0,0 +1,182 @@
+//! Rolling window of observed task outcomes backing `MetaEvaluator`'s
+//! `get_accuracy`/`get_bug_rate`, fed by `post_task_validation` and explicit
+//! user feedback. Optionally persisted to SQLite so the window survives a
+//! restart instead of resetting every time the process does.
+
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{LlmProvider, TaskId},
+};
+use parking_lot::Mutex;
+use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+use std::collections::VecDeque;
+
+/// How many of the most recent outcomes `accuracy`/`bug_rate` are computed
+/// over, so a quality regression shows up quickly instead of being diluted
+/// by a long history of prior good behavior.
+const WINDOW_SIZE: usize = 1000;
+
+/// A single observed outcome, from a post-task validation or explicit user
+/// feedback.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub correct: bool,
+    pub is_bug: bool,
+    /// Task this outcome was recorded for, when known (always set by
+    /// `post_task_validation`; feedback carries whatever task id the caller
+    /// reported it against).
+    pub task_id: Option<TaskId>,
+    /// Provider that served the task, when known, so `provider_accuracy` can
+    /// break the rolling window down per provider.
+    pub provider: Option<LlmProvider>,
+    /// Free-text note attached to the outcome, e.g. a user's correction.
+    pub comment: Option<String>,
+}
+
+enum Store {
+    InMemory(Mutex<VecDeque<Outcome>>),
+    Sqlite(SqlitePool),
+}
+
+/// Rolling-window tracker of observed task outcomes.
+pub struct OutcomeTracker {
+    store: Store,
+}
+
+impl OutcomeTracker {
+    /// Track outcomes in memory only; the window resets on every restart.
+    pub fn in_memory() -> Self {
+        Self { store: Store::InMemory(Mutex::new(VecDeque::with_capacity(WINDOW_SIZE))) }
+    }
+
+    /// Track outcomes in a SQLite database at `database_url` (created if it
+    /// does not exist), so the window survives a restart.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| Error::Evaluation(format!("failed to connect to outcome tracker database: {e}")))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS eval_outcomes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                recorded_at TEXT NOT NULL,
+                correct INTEGER NOT NULL,
+                is_bug INTEGER NOT NULL,
+                task_id TEXT,
+                provider TEXT,
+                comment TEXT
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| Error::Evaluation(format!("failed to initialize eval_outcomes schema: {e}")))?;
+
+        Ok(Self { store: Store::Sqlite(pool) })
+    }
+
+    /// Record one observed outcome.
+    pub async fn record(&self, outcome: Outcome) -> Result<()> {
+        match &self.store {
+            Store::InMemory(outcomes) => {
+                let mut outcomes = outcomes.lock();
+                if outcomes.len() == WINDOW_SIZE {
+                    outcomes.pop_front();
+                }
+                outcomes.push_back(outcome);
+                Ok(())
+            }
+            Store::Sqlite(pool) => {
+                sqlx::query(
+                    "INSERT INTO eval_outcomes (recorded_at, correct, is_bug, task_id, provider, comment)
+                     VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(chrono::Utc::now().to_rfc3339())
+                .bind(i64::from(outcome.correct))
+                .bind(i64::from(outcome.is_bug))
+                .bind(outcome.task_id.map(|id| id.to_string()))
+                .bind(outcome.provider.map(|provider| provider.as_str()))
+                .bind(outcome.comment)
+                .execute(pool)
+                .await
+                .map_err(|e| Error::Evaluation(format!("failed to record outcome: {e}")))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Fraction of the most recent outcomes (up to `WINDOW_SIZE`) that were
+    /// correct. `1.0` (optimistic default) if nothing has been recorded yet.
+    pub async fn accuracy(&self) -> Result<f64> {
+        match &self.store {
+            Store::InMemory(outcomes) => {
+                let outcomes = outcomes.lock();
+                if outcomes.is_empty() {
+                    return Ok(1.0);
+                }
+                let correct = outcomes.iter().filter(|o| o.correct).count();
+                Ok(correct as f64 / outcomes.len() as f64)
+            }
+            Store::Sqlite(pool) => Self::windowed_rate(pool, "correct", 1.0, None).await,
+        }
+    }
+
+    /// Fraction of the most recent outcomes (up to `WINDOW_SIZE`) flagged as
+    /// a bug. `0.0` if nothing has been recorded yet.
+    pub async fn bug_rate(&self) -> Result<f64> {
+        match &self.store {
+            Store::InMemory(outcomes) => {
+                let outcomes = outcomes.lock();
+                if outcomes.is_empty() {
+                    return Ok(0.0);
+                }
+                let bugs = outcomes.iter().filter(|o| o.is_bug).count();
+                Ok(bugs as f64 / outcomes.len() as f64)
+            }
+            Store::Sqlite(pool) => Self::windowed_rate(pool, "is_bug", 0.0, None).await,
+        }
+    }
+
+    /// Fraction of the most recent outcomes (up to `WINDOW_SIZE`) recorded
+    /// for `provider` that were correct. `1.0` (optimistic default) if
+    /// nothing has been recorded for it yet. Feeds
+    /// `TaskDispatcher::set_provider_quality_score` so provider selection can
+    /// reflect observed feedback instead of only the static pricing table.
+    pub async fn provider_accuracy(&self, provider: LlmProvider) -> Result<f64> {
+        match &self.store {
+            Store::InMemory(outcomes) => {
+                let outcomes = outcomes.lock();
+                let matching: Vec<&Outcome> =
+                    outcomes.iter().filter(|o| o.provider == Some(provider)).collect();
+                if matching.is_empty() {
+                    return Ok(1.0);
+                }
+                let correct = matching.iter().filter(|o| o.correct).count();
+                Ok(correct as f64 / matching.len() as f64)
+            }
+            Store::Sqlite(pool) => Self::windowed_rate(pool, "correct", 1.0, Some(provider)).await,
+        }
+    }
+
+    async fn windowed_rate(pool: &SqlitePool, column: &str, default: f64, provider: Option<LlmProvider>) -> Result<f64> {
+        let where_clause = if provider.is_some() { "WHERE provider = ?" } else { "" };
+        let query = format!(
+            "SELECT AVG(value) as rate FROM (\
+                 SELECT {column} as value FROM eval_outcomes {where_clause} ORDER BY id DESC LIMIT ?\
+             )"
+        );
+        let mut query = sqlx::query(&query);
+        if let Some(provider) = provider {
+            query = query.bind(provider.as_str());
+        }
+        let row = query
+            .bind(WINDOW_SIZE as i64)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| Error::Evaluation(format!("failed to compute windowed {column} rate: {e}")))?;
+        let rate: Option<f64> =
+            row.try_get("rate").map_err(|e| Error::Evaluation(format!("malformed outcome rate row: {e}")))?;
+        Ok(rate.unwrap_or(default))
+    }
+}
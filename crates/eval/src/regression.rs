@@ -0,0 +1,219 @@
+//! Diffs two `BenchmarkReport`s (e.g. the existing provider/model
+//! configuration and a candidate one run through the same suite) per test
+//! case and per metric, flagging regressions significant enough to block
+//! promoting the candidate.
+
+use crate::benchmark::{BenchmarkReport, CaseResult};
+use meta_ai_common::types::LlmProvider;
+use std::collections::HashMap;
+
+/// Pass-rate drop at or above this blocks promotion outright.
+const PASS_RATE_BLOCK_THRESHOLD: f64 = 0.05;
+/// Pass-rate drop at or above this (but below the block threshold) merits a
+/// warning rather than blocking.
+const PASS_RATE_WARN_THRESHOLD: f64 = 0.01;
+/// Average-latency increase, as a fraction of the baseline average, at or
+/// above which promotion is blocked.
+const LATENCY_BLOCK_THRESHOLD: f64 = 0.5;
+const LATENCY_WARN_THRESHOLD: f64 = 0.2;
+
+/// A test case that passed on the baseline run but failed on the candidate
+/// run, identified by `(test_case_id, provider)` since the same case may be
+/// run against more than one provider in a single suite.
+#[derive(Debug, Clone)]
+pub struct CaseRegression {
+    pub test_case_id: String,
+    pub provider: LlmProvider,
+}
+
+/// What `compare_runs` recommends doing with the candidate configuration.
+/// Mirrors the orchestrator crate's `quality_gate::GateOutcome` Pass/Warn/
+/// Block split, but this crate can't depend on that one, so it's its own
+/// small enum.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromotionVerdict {
+    Promote,
+    Warn { reasons: Vec<String> },
+    Block { reasons: Vec<String> },
+}
+
+/// Structured diff between a baseline and candidate `BenchmarkReport`.
+#[derive(Debug, Clone)]
+pub struct RegressionReport {
+    pub baseline_pass_rate: f64,
+    pub candidate_pass_rate: f64,
+    pub pass_rate_change: f64,
+    pub baseline_avg_latency_ms: f64,
+    pub candidate_avg_latency_ms: f64,
+    /// Candidate latency relative to baseline, e.g. `0.2` is a 20% increase.
+    /// `0.0` if the baseline had no results to compare against.
+    pub latency_change_fraction: f64,
+    /// Test cases that passed on the baseline and failed on the candidate,
+    /// in suite order. Cases that flipped the other way (failed -> passed)
+    /// aren't regressions and are left out.
+    pub regressed_cases: Vec<CaseRegression>,
+    pub verdict: PromotionVerdict,
+}
+
+fn avg_latency_ms(report: &BenchmarkReport) -> f64 {
+    if report.results.is_empty() {
+        return 0.0;
+    }
+    report.results.iter().map(|r| r.latency_ms as f64).sum::<f64>() / report.results.len() as f64
+}
+
+/// Diff `baseline` and `candidate` (two `BenchmarkReport`s produced by
+/// running the same `BenchmarkSuite`, e.g. the caller's existing
+/// configuration and a proposed replacement) per test case and per metric
+/// (pass rate, average latency), returning a structured verdict on whether
+/// the candidate is safe to promote.
+pub fn compare_runs(baseline: &BenchmarkReport, candidate: &BenchmarkReport) -> RegressionReport {
+    let baseline_pass_rate = baseline.pass_rate();
+    let candidate_pass_rate = candidate.pass_rate();
+    let pass_rate_change = candidate_pass_rate - baseline_pass_rate;
+
+    let baseline_avg_latency_ms = avg_latency_ms(baseline);
+    let candidate_avg_latency_ms = avg_latency_ms(candidate);
+    let latency_change_fraction = if baseline_avg_latency_ms > 0.0 {
+        (candidate_avg_latency_ms - baseline_avg_latency_ms) / baseline_avg_latency_ms
+    } else {
+        0.0
+    };
+
+    let baseline_by_case: HashMap<(&str, LlmProvider), &CaseResult> =
+        baseline.results.iter().map(|result| ((result.test_case_id.as_str(), result.provider), result)).collect();
+
+    let regressed_cases: Vec<CaseRegression> = candidate
+        .results
+        .iter()
+        .filter_map(|candidate_result| {
+            let baseline_result =
+                baseline_by_case.get(&(candidate_result.test_case_id.as_str(), candidate_result.provider))?;
+            (baseline_result.passed && !candidate_result.passed).then(|| CaseRegression {
+                test_case_id: candidate_result.test_case_id.clone(),
+                provider: candidate_result.provider,
+            })
+        })
+        .collect();
+
+    let mut block_reasons = Vec::new();
+    let mut warn_reasons = Vec::new();
+
+    if pass_rate_change <= -PASS_RATE_BLOCK_THRESHOLD {
+        block_reasons.push(format!(
+            "pass rate dropped {:.1} points ({:.1}% -> {:.1}%)",
+            -pass_rate_change * 100.0,
+            baseline_pass_rate * 100.0,
+            candidate_pass_rate * 100.0
+        ));
+    } else if pass_rate_change <= -PASS_RATE_WARN_THRESHOLD {
+        warn_reasons.push(format!(
+            "pass rate dropped {:.1} points ({:.1}% -> {:.1}%)",
+            -pass_rate_change * 100.0,
+            baseline_pass_rate * 100.0,
+            candidate_pass_rate * 100.0
+        ));
+    }
+
+    if latency_change_fraction >= LATENCY_BLOCK_THRESHOLD {
+        block_reasons.push(format!(
+            "average latency increased {:.1}% ({baseline_avg_latency_ms:.0}ms -> {candidate_avg_latency_ms:.0}ms)",
+            latency_change_fraction * 100.0
+        ));
+    } else if latency_change_fraction >= LATENCY_WARN_THRESHOLD {
+        warn_reasons.push(format!(
+            "average latency increased {:.1}% ({baseline_avg_latency_ms:.0}ms -> {candidate_avg_latency_ms:.0}ms)",
+            latency_change_fraction * 100.0
+        ));
+    }
+
+    if !regressed_cases.is_empty() {
+        warn_reasons.push(format!("{} test case(s) regressed from pass to fail", regressed_cases.len()));
+    }
+
+    let verdict = if !block_reasons.is_empty() {
+        PromotionVerdict::Block { reasons: block_reasons }
+    } else if !warn_reasons.is_empty() {
+        PromotionVerdict::Warn { reasons: warn_reasons }
+    } else {
+        PromotionVerdict::Promote
+    };
+
+    RegressionReport {
+        baseline_pass_rate,
+        candidate_pass_rate,
+        pass_rate_change,
+        baseline_avg_latency_ms,
+        candidate_avg_latency_ms,
+        latency_change_fraction,
+        regressed_cases,
+        verdict,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_common::types::LlmProvider;
+
+    fn case(test_case_id: &str, provider: LlmProvider, passed: bool, latency_ms: u64) -> CaseResult {
+        CaseResult {
+            test_case_id: test_case_id.to_string(),
+            provider,
+            passed,
+            actual_output: String::new(),
+            latency_ms,
+            failures: if passed { vec![] } else { vec!["failed".to_string()] },
+        }
+    }
+
+    #[test]
+    fn identical_runs_promote() {
+        let report = BenchmarkReport {
+            suite_name: "suite".to_string(),
+            results: vec![case("a", LlmProvider::OpenAI, true, 100), case("b", LlmProvider::OpenAI, true, 120)],
+        };
+        let verdict = compare_runs(&report, &report).verdict;
+        assert_eq!(verdict, PromotionVerdict::Promote);
+    }
+
+    #[test]
+    fn pass_to_fail_flip_is_blocked() {
+        let baseline = BenchmarkReport {
+            suite_name: "suite".to_string(),
+            results: vec![
+                case("a", LlmProvider::OpenAI, true, 100),
+                case("b", LlmProvider::OpenAI, true, 100),
+                case("c", LlmProvider::OpenAI, true, 100),
+                case("d", LlmProvider::OpenAI, true, 100),
+            ],
+        };
+        let candidate = BenchmarkReport {
+            suite_name: "suite".to_string(),
+            results: vec![
+                case("a", LlmProvider::OpenAI, false, 100),
+                case("b", LlmProvider::OpenAI, true, 100),
+                case("c", LlmProvider::OpenAI, true, 100),
+                case("d", LlmProvider::OpenAI, true, 100),
+            ],
+        };
+        let report = compare_runs(&baseline, &candidate);
+        assert_eq!(report.regressed_cases.len(), 1);
+        assert_eq!(report.regressed_cases[0].test_case_id, "a");
+        assert!(matches!(report.verdict, PromotionVerdict::Block { .. }));
+    }
+
+    #[test]
+    fn latency_regression_without_pass_rate_drop_warns() {
+        let baseline = BenchmarkReport {
+            suite_name: "suite".to_string(),
+            results: vec![case("a", LlmProvider::OpenAI, true, 100)],
+        };
+        let candidate = BenchmarkReport {
+            suite_name: "suite".to_string(),
+            results: vec![case("a", LlmProvider::OpenAI, true, 130)],
+        };
+        let report = compare_runs(&baseline, &candidate);
+        assert!(matches!(report.verdict, PromotionVerdict::Warn { .. }));
+    }
+}
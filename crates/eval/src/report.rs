@@ -0,0 +1,143 @@
+//! Aggregates benchmark runs, fuzzing sessions, and drift analyses into a
+//! single `EvaluationReport` with a stable JSON schema (additive changes
+//! only, so existing CI/dashboard consumers keep working) plus a
+//! human-readable HTML summary rendered from the same data.
+
+use meta_ai_core::evaluation::{DriftAnalysis, FuzzingResult};
+use meta_ai_common::error::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+use crate::benchmark::BenchmarkReport;
+
+/// One evaluation cycle's worth of benchmark runs, fuzzing sessions, and
+/// drift analyses. Built incrementally via `add_benchmark`/`add_fuzzing`/
+/// `add_drift`, then exported with `to_json`/`to_html`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvaluationReport {
+    pub benchmarks: Vec<BenchmarkReport>,
+    pub fuzzing: Vec<FuzzingResult>,
+    pub drift: Vec<DriftAnalysis>,
+}
+
+impl EvaluationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_benchmark(&mut self, report: BenchmarkReport) -> &mut Self {
+        self.benchmarks.push(report);
+        self
+    }
+
+    pub fn add_fuzzing(&mut self, result: FuzzingResult) -> &mut Self {
+        self.fuzzing.push(result);
+        self
+    }
+
+    pub fn add_drift(&mut self, analysis: DriftAnalysis) -> &mut Self {
+        self.drift.push(analysis);
+        self
+    }
+
+    /// Pretty-printed JSON, the machine-readable form CI systems and
+    /// dashboards consume.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Self-contained HTML summary: one section per report type, a
+    /// pass/fail table for benchmarks, failure counts for fuzzing runs, and
+    /// a drift/no-drift banner per analysis. No external assets, so it can
+    /// be written to a single file and opened directly.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Evaluation Report</title>\
+             <style>body{{font-family:sans-serif;margin:2rem}}table{{border-collapse:collapse;width:100%}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}.pass{{color:#2a7d2a}}.fail{{color:#b00020}}\
+             .drift{{color:#b00020}}.no-drift{{color:#2a7d2a}}</style></head><body><h1>Evaluation Report</h1>"
+        );
+
+        let _ = write!(html, "<h2>Benchmarks</h2>");
+        if self.benchmarks.is_empty() {
+            let _ = write!(html, "<p>No benchmark runs.</p>");
+        }
+        for report in &self.benchmarks {
+            let _ = write!(
+                html,
+                "<h3>{} - {}/{} passed ({:.1}%)</h3>",
+                escape_html(&report.suite_name),
+                report.passed(),
+                report.results.len(),
+                report.pass_rate() * 100.0
+            );
+            let _ = write!(
+                html,
+                "<table><tr><th>Test case</th><th>Provider</th><th>Result</th><th>Latency (ms)</th><th>Failures</th></tr>"
+            );
+            for case in &report.results {
+                let (class, label) = if case.passed { ("pass", "PASS") } else { ("fail", "FAIL") };
+                let _ = write!(
+                    html,
+                    "<tr><td>{}</td><td>{:?}</td><td class=\"{class}\">{label}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&case.test_case_id),
+                    case.provider,
+                    case.latency_ms,
+                    escape_html(&case.failures.join("; "))
+                );
+            }
+            let _ = write!(html, "</table>");
+        }
+
+        let _ = write!(html, "<h2>Fuzzing</h2>");
+        if self.fuzzing.is_empty() {
+            let _ = write!(html, "<p>No fuzzing sessions.</p>");
+        }
+        let _ = write!(
+            html,
+            "<table><tr><th>Iterations</th><th>Failures</th><th>Crashes</th><th>Timeouts</th><th>Coverage</th><th>Unique errors</th></tr>"
+        );
+        for result in &self.fuzzing {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.1}%</td><td>{}</td></tr>",
+                result.iterations,
+                result.failures,
+                result.crash_count,
+                result.timeout_count,
+                result.coverage_percent,
+                escape_html(&result.unique_errors.join("; "))
+            );
+        }
+        let _ = write!(html, "</table>");
+
+        let _ = write!(html, "<h2>Drift</h2>");
+        if self.drift.is_empty() {
+            let _ = write!(html, "<p>No drift analyses.</p>");
+        }
+        for analysis in &self.drift {
+            let (class, label) = if analysis.drift_detected { ("drift", "DRIFT DETECTED") } else { ("no-drift", "NO DRIFT") };
+            let _ = write!(
+                html,
+                "<p class=\"{class}\">{label} - baseline accuracy {:.3}, current accuracy {:.3}, change {:.3}, drift score {:.3}</p>",
+                analysis.baseline_accuracy, analysis.current_accuracy, analysis.performance_change, analysis.drift_score
+            );
+            if !analysis.recommendations.is_empty() {
+                let _ = write!(html, "<ul>");
+                for recommendation in &analysis.recommendations {
+                    let _ = write!(html, "<li>{}</li>", escape_html(recommendation));
+                }
+                let _ = write!(html, "</ul>");
+            }
+        }
+
+        let _ = write!(html, "</body></html>");
+        html
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
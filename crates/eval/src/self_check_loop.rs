@@ -0,0 +1,155 @@
+//! Background loop driven by `EvaluationConfig.self_check_interval_ms`:
+//! periodically runs `Evaluator::self_check` (and, if configured, replays a
+//! canary `BenchmarkSuite`), records the resulting accuracy/bug-rate as
+//! metrics gauges, and flips a shared `HealthGauge` when either breaches its
+//! threshold. Without this, a quiet period with no real traffic never
+//! refreshes `HealthStatus` and a regression only surfaces once live
+//! requests resume.
+
+use crate::benchmark::BenchmarkRunner;
+use meta_ai_common::{metrics::MetricsCollector, types::LlmProvider};
+use meta_ai_core::{
+    evaluation::{BenchmarkSuite, Evaluator},
+    orchestrator::Orchestrator,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Shared health verdict maintained by `SelfCheckLoop`, readable without
+/// awaiting a fresh self-check. Starts healthy; a `CoreAI::health_check`
+/// implementation can fold this into its own verdict alongside live
+/// accuracy/bug-rate.
+#[derive(Clone)]
+pub struct HealthGauge(Arc<AtomicBool>);
+
+impl HealthGauge {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+
+    pub fn healthy(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, healthy: bool) {
+        self.0.store(healthy, Ordering::Relaxed);
+    }
+}
+
+impl Default for HealthGauge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs `Evaluator::self_check` (and, if given, a canary suite replay)
+/// every `interval_ms`, recording accuracy/bug-rate through `metrics` and
+/// updating `health` when either breaches its threshold.
+pub struct SelfCheckLoop {
+    evaluator: Arc<dyn Evaluator>,
+    metrics: Arc<dyn MetricsCollector>,
+    health: HealthGauge,
+    accuracy_threshold: f64,
+    bug_rate_threshold: f64,
+    interval_ms: u64,
+    canary: Option<(BenchmarkRunner, BenchmarkSuite, LlmProvider)>,
+}
+
+impl SelfCheckLoop {
+    /// `accuracy_threshold`/`bug_rate_threshold` and `interval_ms` normally
+    /// come straight from `EvaluationConfig`.
+    pub fn new(
+        evaluator: Arc<dyn Evaluator>,
+        metrics: Arc<dyn MetricsCollector>,
+        accuracy_threshold: f64,
+        bug_rate_threshold: f64,
+        interval_ms: u64,
+    ) -> Self {
+        Self {
+            evaluator,
+            metrics,
+            health: HealthGauge::new(),
+            accuracy_threshold,
+            bug_rate_threshold,
+            interval_ms,
+            canary: None,
+        }
+    }
+
+    /// Also replay `suite` against `provider` through `orchestrator` on
+    /// every tick; a canary pass rate below `suite.pass_threshold` (or a
+    /// failed replay) counts as unhealthy alongside the self-check
+    /// thresholds.
+    pub fn with_canary(mut self, orchestrator: Arc<dyn Orchestrator>, suite: BenchmarkSuite, provider: LlmProvider) -> Self {
+        self.canary = Some((BenchmarkRunner::new(orchestrator), suite, provider));
+        self
+    }
+
+    /// The health verdict maintained by the running loop. Clone and store
+    /// this wherever `HealthStatus` is assembled.
+    pub fn health(&self) -> HealthGauge {
+        self.health.clone()
+    }
+
+    /// Run the background loop: every `interval_ms`, run a self-check cycle
+    /// and (if configured) a canary replay.
+    pub fn start(self) -> tokio::task::JoinHandle<()> {
+        let interval = Duration::from_millis(self.interval_ms.max(1));
+        tokio::spawn(async move {
+            loop {
+                self.run_once().await;
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    async fn run_once(&self) {
+        let self_check = match self.evaluator.self_check().await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("self-check cycle failed to run: {e}");
+                self.metrics.record_error("self_check_failed", "critical", "self_check");
+                self.health.set(false);
+                return;
+            }
+        };
+
+        self.metrics.update_accuracy("self_check", "self_check", self_check.accuracy);
+        self.metrics.update_bug_rate("self_check", "self_check", self_check.bug_rate);
+
+        let mut healthy =
+            self_check.accuracy >= self.accuracy_threshold && self_check.bug_rate <= self.bug_rate_threshold;
+
+        if let Some((runner, suite, provider)) = &self.canary {
+            match runner.run(suite, &[*provider]).await {
+                Ok(report) => {
+                    let pass_rate = report.pass_rate();
+                    self.metrics.update_accuracy(provider.as_str(), "canary", pass_rate);
+                    if pass_rate < suite.pass_threshold {
+                        healthy = false;
+                    }
+                }
+                Err(e) => {
+                    warn!("canary replay failed: {e}");
+                    healthy = false;
+                }
+            }
+        }
+
+        if !healthy {
+            self.metrics.record_error("self_check_unhealthy", "critical", "self_check");
+        }
+
+        self.health.set(healthy);
+        info!(
+            healthy,
+            accuracy = self_check.accuracy,
+            bug_rate = self_check.bug_rate,
+            "self-check cycle completed"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Throughput comparison between the single-queue `PriorityScheduler` and
+//! the sharded, work-stealing `ShardedScheduler` under concurrent
+//! submission. Run with `cargo bench -p meta-ai-orchestrator-engine`.
+//!
+//! A single queue serializes every `schedule_task`/`next_task` call behind
+//! one mutex, so its throughput should flatten as producer count grows past
+//! a handful, while `ShardedScheduler` keeps scaling by spreading tasks
+//! (and the mutex contention) across shards.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use meta_ai_common::types::Task;
+use meta_ai_core::orchestrator::TaskScheduler;
+use meta_ai_orchestrator_engine::scheduler::{PriorityScheduler, ShardedScheduler};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// How many tasks each concurrent producer submits per benchmark iteration.
+const TASKS_PER_PRODUCER: usize = 250;
+
+/// Submit `TASKS_PER_PRODUCER` tasks from each of `producers` concurrent
+/// tokio tasks, then drain every task back out.
+async fn drive<S: TaskScheduler + 'static>(scheduler: Arc<S>, producers: usize) {
+    let mut handles = Vec::with_capacity(producers);
+    for _ in 0..producers {
+        let scheduler = Arc::clone(&scheduler);
+        handles.push(tokio::spawn(async move {
+            for _ in 0..TASKS_PER_PRODUCER {
+                scheduler.schedule_task(Task::default()).await.unwrap();
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let mut drained = 0;
+    while drained < producers * TASKS_PER_PRODUCER {
+        if scheduler.next_task().await.unwrap().is_some() {
+            drained += 1;
+        }
+    }
+}
+
+fn bench_concurrent_submission(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("scheduler_throughput");
+
+    for producers in [1usize, 4, 16] {
+        group.bench_with_input(BenchmarkId::new("single_queue", producers), &producers, |b, &producers| {
+            b.iter(|| rt.block_on(drive(Arc::new(PriorityScheduler::new(100_000)), producers)));
+        });
+        group.bench_with_input(BenchmarkId::new("sharded_4", producers), &producers, |b, &producers| {
+            b.iter(|| rt.block_on(drive(Arc::new(ShardedScheduler::new(4, 100_000)), producers)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_submission);
+criterion_main!(benches);
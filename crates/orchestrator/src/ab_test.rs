@@ -0,0 +1,268 @@
+//! A/B experiment routing and analysis for `AbTestConfig`.
+//!
+//! Unlike `CanaryRouter`'s weighted-random choice, arm assignment here is
+//! deterministic: it's hashed from the request's task id, so a requeued or
+//! retried task always lands back in the same arm instead of being
+//! re-randomized and skewing the sample.
+
+use dashmap::DashMap;
+use meta_ai_common::types::{LlmProvider, TaskId};
+use meta_ai_core::evaluation::{AbTestConfig, QualityMetric};
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Which arm of an experiment a task was assigned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arm {
+    Control,
+    Experiment,
+}
+
+/// Online mean/variance accumulator (Welford's algorithm), so per-arm
+/// metric tracking doesn't need to retain every sample.
+#[derive(Debug, Default, Clone, Copy)]
+struct MetricAccumulator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl MetricAccumulator {
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = value - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+}
+
+/// Per-arm accumulators for every `QualityMetric` an experiment tracks.
+#[derive(Debug, Default, Clone, Copy)]
+struct ArmMetrics {
+    accuracy: MetricAccumulator,
+    bug_rate: MetricAccumulator,
+    latency: MetricAccumulator,
+    error_rate: MetricAccumulator,
+    token_usage: MetricAccumulator,
+    cost: MetricAccumulator,
+    latency_burn_rate: MetricAccumulator,
+}
+
+impl ArmMetrics {
+    fn accumulator_mut(&mut self, metric: QualityMetric) -> &mut MetricAccumulator {
+        match metric {
+            QualityMetric::Accuracy => &mut self.accuracy,
+            QualityMetric::BugRate => &mut self.bug_rate,
+            QualityMetric::Latency => &mut self.latency,
+            QualityMetric::ErrorRate => &mut self.error_rate,
+            QualityMetric::TokenUsage => &mut self.token_usage,
+            QualityMetric::Cost => &mut self.cost,
+            QualityMetric::LatencyBurnRate => &mut self.latency_burn_rate,
+        }
+    }
+
+    fn accumulator(&self, metric: QualityMetric) -> MetricAccumulator {
+        match metric {
+            QualityMetric::Accuracy => self.accuracy,
+            QualityMetric::BugRate => self.bug_rate,
+            QualityMetric::Latency => self.latency,
+            QualityMetric::ErrorRate => self.error_rate,
+            QualityMetric::TokenUsage => self.token_usage,
+            QualityMetric::Cost => self.cost,
+            QualityMetric::LatencyBurnRate => self.latency_burn_rate,
+        }
+    }
+}
+
+/// Result of a sequential significance test (Welch's t-test) between an
+/// experiment's two arms for one tracked metric.
+#[derive(Debug, Clone, Copy)]
+pub struct Significance {
+    /// Experiment mean minus control mean.
+    pub effect: f64,
+    pub t_statistic: f64,
+    /// Two-sided p-value from a normal approximation to the t-distribution
+    /// (no `statrs`-style dependency is available in this workspace).
+    pub p_value: f64,
+}
+
+/// A running A/B experiment: deterministic arm assignment plus per-arm
+/// metric accumulation and significance testing.
+pub struct Experiment {
+    config: AbTestConfig,
+    control: Mutex<ArmMetrics>,
+    experiment: Mutex<ArmMetrics>,
+    control_samples: AtomicU64,
+    experiment_samples: AtomicU64,
+}
+
+impl Experiment {
+    pub fn new(config: AbTestConfig) -> Self {
+        Self {
+            config,
+            control: Mutex::new(ArmMetrics::default()),
+            experiment: Mutex::new(ArmMetrics::default()),
+            control_samples: AtomicU64::new(0),
+            experiment_samples: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &AbTestConfig {
+        &self.config
+    }
+
+    /// Deterministically assign `task_id` to an arm: the hash of
+    /// `(experiment name, task_id)` maps uniformly onto `[0.0, 1.0)`, and
+    /// `traffic_split` is the experiment arm's share of that range.
+    pub fn assign(&self, task_id: TaskId) -> Arm {
+        let mut hasher = DefaultHasher::new();
+        self.config.name.hash(&mut hasher);
+        task_id.hash(&mut hasher);
+        let bucket = (hasher.finish() as f64) / (u64::MAX as f64);
+        if bucket < self.config.traffic_split {
+            Arm::Experiment
+        } else {
+            Arm::Control
+        }
+    }
+
+    /// The provider `task_id` should be dispatched to under this experiment.
+    pub fn provider_for(&self, task_id: TaskId) -> LlmProvider {
+        match self.assign(task_id) {
+            Arm::Control => self.config.control_provider,
+            Arm::Experiment => self.config.experiment_provider,
+        }
+    }
+
+    /// Record one observation of `metric` for `arm`. Ignored if `metric`
+    /// isn't in `AbTestConfig.metrics_to_track`.
+    pub fn record(&self, arm: Arm, metric: QualityMetric, value: f64) {
+        if !self
+            .config
+            .metrics_to_track
+            .iter()
+            .any(|tracked| std::mem::discriminant(tracked) == std::mem::discriminant(&metric))
+        {
+            return;
+        }
+
+        let (accumulator, samples) = match arm {
+            Arm::Control => (&self.control, &self.control_samples),
+            Arm::Experiment => (&self.experiment, &self.experiment_samples),
+        };
+        accumulator.lock().accumulator_mut(metric).record(value);
+        samples.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Whether both arms have collected `AbTestConfig.minimum_samples`, the
+    /// threshold below which `significance` shouldn't be trusted to decide
+    /// the experiment.
+    pub fn has_minimum_samples(&self) -> bool {
+        let minimum = u64::from(self.config.minimum_samples);
+        self.control_samples.load(Ordering::Relaxed) >= minimum
+            && self.experiment_samples.load(Ordering::Relaxed) >= minimum
+    }
+
+    /// Sequential Welch's t-test for `metric` between the two arms. Callers
+    /// may poll this at any time (it's valid to call before
+    /// `has_minimum_samples` is true; the result is just less reliable).
+    /// Returns `None` until both arms have at least two samples for the
+    /// metric.
+    pub fn significance(&self, metric: QualityMetric) -> Option<Significance> {
+        let control = self.control.lock().accumulator(metric);
+        let experiment = self.experiment.lock().accumulator(metric);
+        if control.count < 2 || experiment.count < 2 {
+            return None;
+        }
+
+        let standard_error =
+            (control.variance() / control.count as f64 + experiment.variance() / experiment.count as f64).sqrt();
+        let effect = experiment.mean - control.mean;
+
+        if standard_error == 0.0 {
+            return Some(Significance {
+                effect,
+                t_statistic: 0.0,
+                p_value: if effect == 0.0 { 1.0 } else { 0.0 },
+            });
+        }
+
+        let t_statistic = effect / standard_error;
+        Some(Significance { effect, t_statistic, p_value: two_sided_p_value(t_statistic) })
+    }
+}
+
+/// Two-sided p-value for a standard-normal approximation of the t
+/// statistic, via the Abramowitz-Stegun approximation to the error
+/// function. Adequate once each arm has a reasonable sample count (the
+/// usual case for `minimum_samples`); not a substitute for an exact
+/// Student's t distribution at very small sample sizes.
+fn two_sided_p_value(t_statistic: f64) -> f64 {
+    let z = t_statistic.abs() / std::f64::consts::SQRT_2;
+    let erf = {
+        // Abramowitz & Stegun 7.1.26
+        let a1 = 0.254_829_592;
+        let a2 = -0.284_496_736;
+        let a3 = 1.421_413_741;
+        let a4 = -1.453_152_027;
+        let a5 = 1.061_405_429;
+        let p = 0.327_591_1;
+        let sign = if z < 0.0 { -1.0 } else { 1.0 };
+        let z = z.abs();
+        let t = 1.0 / (1.0 + p * z);
+        let poly = ((((a5 * t + a4) * t) + a3) * t + a2) * t + a1;
+        sign * (1.0 - poly * t * (-z * z).exp())
+    };
+    let two_sided_cdf_upper_tail = 1.0 - erf;
+    two_sided_cdf_upper_tail.clamp(0.0, 1.0)
+}
+
+/// Named registry of running experiments, mirroring `CanaryRouter`'s
+/// runtime-configurable-without-redeploy shape.
+pub struct AbTestEngine {
+    experiments: DashMap<String, std::sync::Arc<Experiment>>,
+}
+
+impl AbTestEngine {
+    pub fn new() -> Self {
+        Self { experiments: DashMap::new() }
+    }
+
+    /// Start (or replace) an experiment under `config.name`.
+    pub fn create(&self, config: AbTestConfig) -> std::sync::Arc<Experiment> {
+        let experiment = std::sync::Arc::new(Experiment::new(config.clone()));
+        self.experiments.insert(config.name.clone(), experiment.clone());
+        experiment
+    }
+
+    pub fn get(&self, name: &str) -> Option<std::sync::Arc<Experiment>> {
+        self.experiments.get(name).map(|entry| entry.clone())
+    }
+
+    /// Stop and remove an experiment, returning it so the caller can read a
+    /// final `significance` snapshot before it's gone.
+    pub fn conclude(&self, name: &str) -> Option<std::sync::Arc<Experiment>> {
+        self.experiments.remove(name).map(|(_, experiment)| experiment)
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        self.experiments.iter().map(|entry| entry.key().clone()).collect()
+    }
+}
+
+impl Default for AbTestEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
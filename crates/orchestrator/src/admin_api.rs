@@ -0,0 +1,186 @@
+//! Runtime admin HTTP surface: `GET` the redacted effective config, `PATCH` a
+//! whitelisted subset of it (log level, concurrency limits, agent enabled
+//! flags, canary routing weights) without a restart. Every `PATCH` is
+//! validated before anything is applied and audit-logged with who changed
+//! what.
+//!
+//! This is the first HTTP endpoint in the workspace; `axum`/`tower`/
+//! `tower-http` have sat in `[workspace.dependencies]` unused until now.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use meta_ai_common::{config::Config, error::Result, types::LlmProvider};
+use meta_ai_core::agent::TaskType;
+use secrecy::{ExposeSecret, Secret};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::canary_router::RoutingArm;
+use crate::dispatcher::TaskDispatcher;
+
+/// Shared state the admin HTTP surface reads and mutates. `config` is the
+/// same `Arc<RwLock<Config>>` the rest of the process holds, so a `PATCH`
+/// here is visible to every other reader immediately - there's no separate
+/// "admin config" copy to drift out of sync.
+#[derive(Clone)]
+pub struct AdminState {
+    config: Arc<RwLock<Config>>,
+    dispatcher: Arc<TaskDispatcher>,
+    /// Bearer token a request must present as `Authorization: Bearer
+    /// <token>`. `None` disables auth entirely - only appropriate for local
+    /// development, never a real deployment.
+    admin_token: Option<Secret<String>>,
+}
+
+impl AdminState {
+    pub fn new(config: Arc<RwLock<Config>>, dispatcher: Arc<TaskDispatcher>, admin_token: Option<Secret<String>>) -> Self {
+        Self { config, dispatcher, admin_token }
+    }
+}
+
+/// Whitelisted subset of `Config` this API allows mutating. Every field is
+/// optional; only the ones present in a `PATCH` body are changed, everything
+/// else is left untouched.
+///
+/// `max_concurrent_tasks`/`task_queue_size` only update the stored `Config`
+/// (visible on the next `GET`, and to anything that rebuilds a dispatcher
+/// from it later) - they do not hot-resize the semaphore backing an
+/// already-running `TaskDispatcher`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfigPatch {
+    pub log_level: Option<String>,
+    pub max_concurrent_tasks: Option<usize>,
+    pub task_queue_size: Option<usize>,
+    #[serde(default)]
+    pub agent_enabled: HashMap<LlmProvider, bool>,
+    #[serde(default)]
+    pub routing_weights: Vec<RoutingWeightPatch>,
+}
+
+/// One `TaskDispatcher::set_canary_route` update. `task_type` of `None` sets
+/// the fallback route used by task types with no dedicated split.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoutingWeightPatch {
+    pub task_type: Option<TaskType>,
+    pub arms: Vec<(LlmProvider, u32)>,
+}
+
+const VALID_LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+
+/// Validate a `ConfigPatch` before any of it is applied, so a `PATCH` either
+/// takes effect atomically or not at all rather than partially applying.
+fn validate(patch: &ConfigPatch) -> Result<(), String> {
+    if let Some(level) = &patch.log_level {
+        if !VALID_LOG_LEVELS.contains(&level.as_str()) {
+            return Err(format!("log_level must be one of {VALID_LOG_LEVELS:?}"));
+        }
+    }
+    if patch.max_concurrent_tasks == Some(0) {
+        return Err("max_concurrent_tasks must be greater than 0".to_string());
+    }
+    if patch.task_queue_size == Some(0) {
+        return Err("task_queue_size must be greater than 0".to_string());
+    }
+    for route in &patch.routing_weights {
+        if route.arms.is_empty() {
+            return Err("routing_weights entries must have at least one arm".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Whether `headers` carries the bearer token `state.admin_token` expects.
+/// Always `true` when auth is disabled (`admin_token` is `None`). Compares
+/// in constant time so response timing can't be used to guess the token
+/// byte by byte.
+fn authorized(state: &AdminState, headers: &HeaderMap) -> bool {
+    let Some(expected) = &state.admin_token else { return true };
+    let Some(header) = headers.get(axum::http::header::AUTHORIZATION) else { return false };
+    let Ok(header) = header.to_str() else { return false };
+    let Some(presented) = header.strip_prefix("Bearer ") else { return false };
+    presented.as_bytes().ct_eq(expected.expose_secret().as_bytes()).into()
+}
+
+async fn get_config(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !authorized(&state, &headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let config = state.config.read().await;
+    Ok(Json(config.effective()))
+}
+
+async fn patch_config(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+    Json(patch): Json<ConfigPatch>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    if !authorized(&state, &headers) {
+        return Err((StatusCode::UNAUTHORIZED, "missing or invalid bearer token".to_string()));
+    }
+    validate(&patch).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let actor = headers
+        .get("x-admin-actor")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+
+    {
+        let mut config = state.config.write().await;
+        if let Some(level) = &patch.log_level {
+            config.observability.log_level = level.clone();
+        }
+        if let Some(max_concurrent_tasks) = patch.max_concurrent_tasks {
+            config.orchestrator.max_concurrent_tasks = max_concurrent_tasks;
+        }
+        if let Some(task_queue_size) = patch.task_queue_size {
+            config.orchestrator.task_queue_size = task_queue_size;
+        }
+        for (provider, enabled) in &patch.agent_enabled {
+            if let Some(agent) = config.agents.get_mut(provider) {
+                agent.enabled = *enabled;
+            }
+        }
+    }
+
+    for route in &patch.routing_weights {
+        let arms = route.arms.iter().map(|(provider, weight)| RoutingArm::new(*provider, *weight)).collect();
+        state.dispatcher.set_canary_route(route.task_type, arms);
+    }
+
+    info!(actor = %actor, patch = ?patch, "admin config patch applied");
+
+    let config = state.config.read().await;
+    Ok(Json(config.effective()))
+}
+
+/// Build the admin router: `GET /admin/config` (redacted effective config)
+/// and `PATCH /admin/config` (whitelisted mutation, see `ConfigPatch`).
+pub fn admin_router(state: AdminState) -> Router {
+    Router::new()
+        .route("/admin/config", get(get_config).patch(patch_config))
+        .with_state(state)
+}
+
+/// Serve `admin_router(state)` on `0.0.0.0:{port}` until the process exits
+/// or the returned future is dropped. Mirrors
+/// `meta_ai_common::metrics_server::serve_metrics`'s bind-and-serve shape.
+pub async fn serve_admin(state: AdminState, port: u16) -> Result<()> {
+    let app = admin_router(state);
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
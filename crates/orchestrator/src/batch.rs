@@ -0,0 +1,89 @@
+//! Tracking handle for a group of tasks submitted together via
+//! `MetaAIOrchestrator::execute_batch`.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use meta_ai_common::types::{TaskId, TaskStatus};
+use meta_ai_core::orchestrator::TaskResult;
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
+
+/// Identifies a batch of tasks submitted together via `execute_batch`.
+pub type BatchId = Uuid;
+
+/// Aggregate progress of a batch, as reported by `BatchHandle::progress`.
+#[derive(Debug, Clone)]
+pub struct BatchProgress {
+    pub batch_id: BatchId,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    /// Estimated completion time, extrapolated from the average duration of
+    /// tasks finished so far. `None` until at least one task has finished.
+    pub eta: Option<DateTime<Utc>>,
+}
+
+/// Handle to a batch of tasks submitted together. Poll `progress` for
+/// aggregate status, or `join` to wait for every task to reach a terminal
+/// state and collect their results.
+pub struct BatchHandle {
+    batch_id: BatchId,
+    task_ids: Vec<TaskId>,
+    task_results: Arc<DashMap<TaskId, TaskResult>>,
+    submitted_at: DateTime<Utc>,
+}
+
+impl BatchHandle {
+    pub(crate) fn new(
+        batch_id: BatchId,
+        task_ids: Vec<TaskId>,
+        task_results: Arc<DashMap<TaskId, TaskResult>>,
+    ) -> Self {
+        Self { batch_id, task_ids, task_results, submitted_at: Utc::now() }
+    }
+
+    pub fn batch_id(&self) -> BatchId {
+        self.batch_id
+    }
+
+    pub fn total(&self) -> usize {
+        self.task_ids.len()
+    }
+
+    /// Snapshot the batch's current progress.
+    pub fn progress(&self) -> BatchProgress {
+        let mut completed = 0;
+        let mut failed = 0;
+        for task_id in &self.task_ids {
+            if let Some(result) = self.task_results.get(task_id) {
+                match result.status {
+                    TaskStatus::Completed => completed += 1,
+                    TaskStatus::Failed | TaskStatus::Cancelled | TaskStatus::Timeout => failed += 1,
+                    TaskStatus::Pending | TaskStatus::Running => {}
+                }
+            }
+        }
+
+        let done = completed + failed;
+        let eta = (done > 0 && done < self.task_ids.len()).then(|| {
+            let elapsed = Utc::now().signed_duration_since(self.submitted_at);
+            let remaining = (self.task_ids.len() - done) as i32;
+            Utc::now() + (elapsed / done as i32) * remaining
+        });
+
+        BatchProgress { batch_id: self.batch_id, total: self.task_ids.len(), completed, failed, eta }
+    }
+
+    /// Wait for every task in the batch to reach a terminal state, then
+    /// return all of their results in submission order.
+    pub async fn join(&self) -> Vec<TaskResult> {
+        loop {
+            if self.task_ids.iter().all(|id| self.task_results.contains_key(id)) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        self.task_ids.iter().filter_map(|id| self.task_results.get(id).map(|r| r.clone())).collect()
+    }
+}
@@ -0,0 +1,151 @@
+//! Coalesces concurrent single-text embedding requests into batched
+//! `Agent::batch_embed` calls, to amortize round-trips for providers whose
+//! embeddings endpoint accepts multiple inputs per call.
+//!
+//! Scoped to embeddings only: completions have no batched counterpart on
+//! `Agent` (`submit` takes one `LlmRequest` and returns one `LlmResponse`),
+//! so there's nothing to coalesce multiple completion calls into without
+//! inventing a wire format no provider in this codebase speaks.
+
+use dashmap::DashMap;
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{Embedding, LlmProvider},
+};
+use meta_ai_core::agent::Agent;
+use parking_lot::Mutex;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::sync::oneshot;
+
+/// A text waiting on a batch flush, plus the channel its caller is blocked on.
+struct PendingEmbed {
+    text: String,
+    reply: oneshot::Sender<Result<Embedding>>,
+}
+
+/// Per-provider queue of texts waiting for the next batch flush.
+struct PendingBatch {
+    queue: Mutex<VecDeque<PendingEmbed>>,
+}
+
+/// Coalesces `embed` calls for the same provider that arrive within a short
+/// window into a single `Agent::batch_embed` call, then demultiplexes the
+/// results back to each caller via a `oneshot` channel.
+///
+/// The first caller to find an empty queue for a provider spawns the
+/// window-delayed flush task, mirroring the lazy per-key bulkhead
+/// initialization in `TaskDispatcher::bulkhead_semaphore`.
+pub struct BatchingDispatcher {
+    pending: Arc<DashMap<LlmProvider, Arc<PendingBatch>>>,
+    window: Duration,
+    max_batch_size: usize,
+}
+
+impl BatchingDispatcher {
+    /// `window` is how long to wait after the first text arrives before
+    /// flushing a provider's queue; `max_batch_size` caps how many texts go
+    /// into a single `batch_embed` call.
+    pub fn new(window: Duration, max_batch_size: usize) -> Self {
+        Self {
+            pending: Arc::new(DashMap::new()),
+            window,
+            max_batch_size,
+        }
+    }
+
+    /// Embed `text` against `provider`, coalesced with any other `embed`
+    /// calls for the same provider that arrive within `window`. `agents` is
+    /// searched for a matching `Agent::provider()` at flush time, the same
+    /// way `TaskDispatcher` resolves a pinned provider to an agent.
+    pub async fn embed(
+        &self,
+        agents: Arc<Vec<Box<dyn Agent>>>,
+        provider: LlmProvider,
+        text: String,
+    ) -> Result<Embedding> {
+        let (reply, rx) = oneshot::channel();
+
+        let is_first = {
+            let batch = self
+                .pending
+                .entry(provider)
+                .or_insert_with(|| Arc::new(PendingBatch { queue: Mutex::new(VecDeque::new()) }));
+            let mut queue = batch.queue.lock();
+            queue.push_back(PendingEmbed { text, reply });
+            queue.len() == 1
+        };
+
+        if is_first {
+            let pending = Arc::clone(&self.pending);
+            let window = self.window;
+            let max_batch_size = self.max_batch_size;
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                Self::flush(pending, provider, agents, max_batch_size).await;
+            });
+        }
+
+        rx.await
+            .map_err(|_| Error::Internal("batch embed sender dropped before replying".to_string()))?
+    }
+
+    /// Drain up to `max_batch_size` pending texts for `provider` and embed
+    /// them in one `Agent::batch_embed` call, replying to each caller in
+    /// order. Keeps flushing until the queue is empty, so texts that arrive
+    /// while a batch is in flight don't wait out another full window.
+    async fn flush(
+        pending: Arc<DashMap<LlmProvider, Arc<PendingBatch>>>,
+        provider: LlmProvider,
+        agents: Arc<Vec<Box<dyn Agent>>>,
+        max_batch_size: usize,
+    ) {
+        let Some(batch) = pending.get(&provider).map(|b| Arc::clone(&b)) else {
+            return;
+        };
+
+        let Some(agent) = agents.iter().find(|a| a.provider() == provider) else {
+            let mut queue = batch.queue.lock();
+            for pending_embed in queue.drain(..) {
+                let _ = pending_embed.reply.send(Err(Error::Agent(format!(
+                    "no agent registered for provider {provider:?}"
+                ))));
+            }
+            return;
+        };
+
+        loop {
+            let drained: Vec<PendingEmbed> = {
+                let mut queue = batch.queue.lock();
+                let n = queue.len().min(max_batch_size);
+                queue.drain(..n).collect()
+            };
+
+            if drained.is_empty() {
+                return;
+            }
+
+            let texts: Vec<&str> = drained.iter().map(|p| p.text.as_str()).collect();
+            match agent.batch_embed(texts).await {
+                Ok(embeddings) => {
+                    for (pending_embed, embedding) in drained.into_iter().zip(embeddings) {
+                        let _ = pending_embed.reply.send(Ok(embedding));
+                    }
+                }
+                Err(err) => {
+                    // `Error` isn't `Clone`; every request in a failed batch
+                    // shares the same provider-level failure, so report it
+                    // to each caller as its own `Error::Agent` wrapping the
+                    // original message.
+                    let message = err.to_string();
+                    for pending_embed in drained {
+                        let _ = pending_embed.reply.send(Err(Error::Agent(message.clone())));
+                    }
+                }
+            }
+
+            if batch.queue.lock().is_empty() {
+                return;
+            }
+        }
+    }
+}
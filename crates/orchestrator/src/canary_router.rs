@@ -0,0 +1,158 @@
+//! Weighted and canary routing between providers.
+//!
+//! `SelectionStrategy::Canary` defers the actual provider choice to a
+//! `CanaryRouter`: a runtime-configurable set of percentage-style splits
+//! (e.g. 95% Claude, 5% OpenAI canary) per task type, so a new provider or
+//! model can be rolled out behind a small traffic slice and ramped up
+//! without a redeploy. Per-arm selection counts let an operator verify the
+//! actual split matches what was configured.
+
+use dashmap::DashMap;
+use meta_ai_common::types::LlmProvider;
+use meta_ai_core::agent::TaskType;
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// One provider's share of traffic within a route, as an integer weight.
+/// Weights are normalized against their sum, so `[(Claude, 95), (OpenAI,
+/// 5)]` and `[(Claude, 19), (OpenAI, 1)]` route identically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoutingArm {
+    pub provider: LlmProvider,
+    pub weight: u32,
+}
+
+impl RoutingArm {
+    pub fn new(provider: LlmProvider, weight: u32) -> Self {
+        Self { provider, weight }
+    }
+}
+
+/// Selection count for one `(task_type, provider)` arm, as reported by
+/// `CanaryRouter::arm_stats`.
+#[derive(Debug, Clone)]
+pub struct CanaryArmStats {
+    pub task_type: Option<TaskType>,
+    pub provider: LlmProvider,
+    pub selections: u64,
+}
+
+/// Runtime-configurable weighted routing between providers, keyed by task
+/// type the same way `FairShareScheduler` keys tenant weights: `None` is its
+/// own route, used for task types with no specific configuration.
+pub struct CanaryRouter {
+    routes: DashMap<Option<TaskType>, Vec<RoutingArm>>,
+    stats: DashMap<(Option<TaskType>, LlmProvider), AtomicU64>,
+}
+
+impl CanaryRouter {
+    pub fn new() -> Self {
+        Self { routes: DashMap::new(), stats: DashMap::new() }
+    }
+
+    /// Configure (or replace) the weighted split for `task_type`. Pass
+    /// `None` to set the fallback route used by task types with no
+    /// dedicated split.
+    pub fn set_route(&self, task_type: Option<TaskType>, arms: Vec<RoutingArm>) {
+        self.routes.insert(task_type, arms);
+    }
+
+    /// Weighted-random provider choice for `task_type`, falling back to the
+    /// `None` route if `task_type` has no dedicated split configured.
+    /// Returns `None` if neither route is configured (or every arm has zero
+    /// weight), leaving the caller to apply its own default.
+    pub fn choose_provider(&self, task_type: Option<TaskType>) -> Option<LlmProvider> {
+        let arms = self
+            .routes
+            .get(&task_type)
+            .map(|arms| arms.clone())
+            .or_else(|| self.routes.get(&None).map(|arms| arms.clone()))?;
+
+        let total: u32 = arms.iter().map(|arm| arm.weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rand::thread_rng().gen_range(0..total);
+        for arm in &arms {
+            if roll < arm.weight {
+                return Some(arm.provider);
+            }
+            roll -= arm.weight;
+        }
+        arms.last().map(|arm| arm.provider)
+    }
+
+    /// Record that `provider` was chosen for `task_type`, for `arm_stats`.
+    pub fn record_selection(&self, task_type: Option<TaskType>, provider: LlmProvider) {
+        self.stats.entry((task_type, provider)).or_insert_with(|| AtomicU64::new(0)).fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot of every arm's selection count so far.
+    pub fn arm_stats(&self) -> Vec<CanaryArmStats> {
+        self.stats
+            .iter()
+            .map(|entry| {
+                let (task_type, provider) = *entry.key();
+                CanaryArmStats { task_type, provider, selections: entry.value().load(Ordering::Relaxed) }
+            })
+            .collect()
+    }
+}
+
+impl Default for CanaryRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_weight_arms_yield_no_choice() {
+        let router = CanaryRouter::new();
+        router.set_route(None, vec![RoutingArm::new(LlmProvider::Claude, 0)]);
+        assert_eq!(router.choose_provider(None), None);
+    }
+
+    #[test]
+    fn test_single_arm_always_wins() {
+        let router = CanaryRouter::new();
+        router.set_route(None, vec![RoutingArm::new(LlmProvider::Claude, 1)]);
+        for _ in 0..20 {
+            assert_eq!(router.choose_provider(None), Some(LlmProvider::Claude));
+        }
+    }
+
+    #[test]
+    fn test_falls_back_to_default_route() {
+        let router = CanaryRouter::new();
+        router.set_route(None, vec![RoutingArm::new(LlmProvider::OpenAI, 1)]);
+        // No route configured for Reasoning, so it should fall back to the
+        // `None` default rather than returning `None`.
+        assert_eq!(router.choose_provider(Some(TaskType::Reasoning)), Some(LlmProvider::OpenAI));
+    }
+
+    #[test]
+    fn test_unconfigured_router_yields_no_choice() {
+        let router = CanaryRouter::new();
+        assert_eq!(router.choose_provider(None), None);
+    }
+
+    #[test]
+    fn test_arm_stats_tracks_selections() {
+        let router = CanaryRouter::new();
+        router.record_selection(Some(TaskType::CodeGeneration), LlmProvider::OpenAI);
+        router.record_selection(Some(TaskType::CodeGeneration), LlmProvider::OpenAI);
+        router.record_selection(Some(TaskType::CodeGeneration), LlmProvider::Claude);
+
+        let stats = router.arm_stats();
+        let openai = stats
+            .iter()
+            .find(|s| s.task_type == Some(TaskType::CodeGeneration) && s.provider == LlmProvider::OpenAI)
+            .unwrap();
+        assert_eq!(openai.selections, 2);
+    }
+}
@@ -0,0 +1,118 @@
+//! Retrieval-augmented context injection for task execution. Opt-in per task
+//! (see `ContextAugmenter::query_for`): most tasks have no connection to the
+//! RAG engine at all, so this only runs for the subset of tasks whose
+//! metadata asks for it, keeping the common path untouched.
+
+use meta_ai_common::{error::Result, types::Task};
+use meta_ai_core::rag::{QueryExpansionMode, RagEngine};
+use std::sync::Arc;
+
+/// Metadata key a task sets to opt into context augmentation; its value is
+/// the query retrieved chunks are searched for.
+const RAG_QUERY_KEY: &str = "rag_query";
+/// Metadata key overriding `ContextAugmenterConfig.top_k` for a single task.
+const RAG_TOP_K_KEY: &str = "rag_top_k";
+/// Metadata key requesting query expansion for a single task's retrieval,
+/// set to `"expand"` or `"hyde"` (see `QueryExpansionMode`). Any other value,
+/// or an engine with no query expander configured, falls back to searching
+/// the literal query.
+const RAG_QUERY_EXPANSION_KEY: &str = "rag_query_expansion";
+
+/// Rough characters-per-token ratio used to estimate how much of
+/// `max_context_tokens` a chunk of retrieved text will consume, without
+/// pulling a real tokenizer in for every provider this might run against.
+const CHARS_PER_TOKEN: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct ContextAugmenterConfig {
+    /// Default number of chunks retrieved per task, overridable per task via
+    /// the `rag_top_k` metadata key.
+    pub top_k: usize,
+    /// Maximum estimated tokens of retrieved context injected into a single
+    /// prompt. Chunks are added in retrieval order until the next one would
+    /// exceed this budget, then retrieval stops.
+    pub max_context_tokens: usize,
+}
+
+impl Default for ContextAugmenterConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            max_context_tokens: 2000,
+        }
+    }
+}
+
+/// Renders a task's top-k retrieved chunks into its prompt before dispatch,
+/// with citations back to each chunk's source document.
+pub struct ContextAugmenter {
+    rag_engine: Arc<dyn RagEngine>,
+    config: ContextAugmenterConfig,
+}
+
+impl ContextAugmenter {
+    pub fn new(rag_engine: Arc<dyn RagEngine>, config: ContextAugmenterConfig) -> Self {
+        Self { rag_engine, config }
+    }
+
+    /// The query `task` wants retrieval run against, if it opted in.
+    fn query_for(task: &Task) -> Option<&str> {
+        task.metadata.get(RAG_QUERY_KEY).and_then(serde_json::Value::as_str)
+    }
+
+    fn top_k_for(&self, task: &Task) -> usize {
+        task.metadata
+            .get(RAG_TOP_K_KEY)
+            .and_then(serde_json::Value::as_u64)
+            .map_or(self.config.top_k, |n| n as usize)
+    }
+
+    fn expansion_for(task: &Task) -> Option<QueryExpansionMode> {
+        match task.metadata.get(RAG_QUERY_EXPANSION_KEY).and_then(serde_json::Value::as_str) {
+            Some("expand") => Some(QueryExpansionMode::Expand),
+            Some("hyde") => Some(QueryExpansionMode::Hyde),
+            _ => None,
+        }
+    }
+
+    /// If `task` opts into context augmentation (see `query_for`), retrieve
+    /// its top-k chunks, render them into a cited context block prepended to
+    /// `prompt`, and return the augmented prompt. Returns `prompt` unchanged
+    /// for tasks that didn't opt in, or whose retrieval came back empty.
+    pub async fn augment(&self, task: &Task, prompt: String) -> Result<String> {
+        let Some(query) = Self::query_for(task) else {
+            return Ok(prompt);
+        };
+
+        let results = self
+            .rag_engine
+            .search(query, self.top_k_for(task), None, Self::expansion_for(task))
+            .await?;
+        let mut context = String::new();
+        let mut remaining_tokens = self.config.max_context_tokens;
+
+        for (index, result) in results.iter().enumerate() {
+            let citation = format!(
+                "[{}] (source: {})\n{}\n\n",
+                index + 1,
+                result.document.id,
+                result.document.content
+            );
+            let estimated_tokens = citation.len().div_ceil(CHARS_PER_TOKEN);
+            if estimated_tokens > remaining_tokens {
+                break;
+            }
+            remaining_tokens -= estimated_tokens;
+            context.push_str(&citation);
+        }
+
+        if context.is_empty() {
+            return Ok(prompt);
+        }
+
+        Ok(format!(
+            "Use the following retrieved context to answer the request below. \
+             Cite sources using their bracketed number, e.g. [1].\n\n{context}---\n\n{prompt}"
+        ))
+    }
+}
@@ -0,0 +1,237 @@
+//! Scheduled and recurring tasks.
+//!
+//! `CronScheduler` holds task templates that are due either once (`run_at`)
+//! or on a cron expression, and feeds a fresh copy of the task into a
+//! `TaskScheduler` each time they come due. It runs as its own background
+//! loop, started alongside `MetaAIOrchestrator::start`.
+
+use chrono::{DateTime, Utc};
+use cron::Schedule as CronExpr;
+use dashmap::DashMap;
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{Task, TaskStatus},
+};
+use meta_ai_core::orchestrator::TaskScheduler;
+use std::{str::FromStr, sync::Arc, time::Duration};
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Identifies a schedule registered with a `CronScheduler`.
+pub type ScheduleId = Uuid;
+
+/// When a schedule's task comes due.
+#[derive(Debug, Clone)]
+pub enum ScheduleSpec {
+    /// Run once, at this timestamp.
+    Once(DateTime<Utc>),
+    /// Run repeatedly, per this cron expression (standard 6-field: `sec min hour dom mon dow`).
+    Cron(String, CronExpr),
+}
+
+impl ScheduleSpec {
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Once(at) => (*at > after).then_some(*at),
+            Self::Cron(_, expr) => expr.after(&after).next(),
+        }
+    }
+}
+
+/// A single registered schedule.
+struct ScheduleEntry {
+    task_template: Task,
+    spec: ScheduleSpec,
+    enabled: bool,
+    next_run: Option<DateTime<Utc>>,
+}
+
+/// Read-only snapshot of a schedule, returned by `CronScheduler::list`.
+#[derive(Debug, Clone)]
+pub struct ScheduleInfo {
+    pub id: ScheduleId,
+    pub task_name: String,
+    pub enabled: bool,
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+/// Holds scheduled and recurring task definitions and feeds them into a
+/// `TaskScheduler` as they come due.
+pub struct CronScheduler {
+    entries: Arc<DashMap<ScheduleId, ScheduleEntry>>,
+}
+
+impl Default for CronScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CronScheduler {
+    pub fn new() -> Self {
+        Self { entries: Arc::new(DashMap::new()) }
+    }
+
+    /// Register a recurring schedule. `cron_expr` is a standard 6-field cron
+    /// expression (seconds first), e.g. `"0 0 * * * *"` for hourly.
+    pub fn add_cron(&self, task_template: Task, cron_expr: &str) -> Result<ScheduleId> {
+        let expr = CronExpr::from_str(cron_expr)
+            .map_err(|e| Error::Validation(format!("invalid cron expression '{cron_expr}': {e}")))?;
+        let next_run = expr.after(&Utc::now()).next();
+        let id = Uuid::new_v4();
+        self.entries.insert(
+            id,
+            ScheduleEntry {
+                task_template,
+                spec: ScheduleSpec::Cron(cron_expr.to_string(), expr),
+                enabled: true,
+                next_run,
+            },
+        );
+        debug!("Registered cron schedule {} ({})", id, cron_expr);
+        Ok(id)
+    }
+
+    /// Register a one-off schedule that fires once at `run_at`.
+    pub fn add_once(&self, task_template: Task, run_at: DateTime<Utc>) -> Result<ScheduleId> {
+        let id = Uuid::new_v4();
+        self.entries.insert(
+            id,
+            ScheduleEntry {
+                task_template,
+                spec: ScheduleSpec::Once(run_at),
+                enabled: true,
+                next_run: Some(run_at),
+            },
+        );
+        debug!("Registered one-off schedule {} at {}", id, run_at);
+        Ok(id)
+    }
+
+    /// List all registered schedules.
+    pub fn list(&self) -> Vec<ScheduleInfo> {
+        self.entries
+            .iter()
+            .map(|entry| ScheduleInfo {
+                id: *entry.key(),
+                task_name: entry.value().task_template.name.clone(),
+                enabled: entry.value().enabled,
+                next_run: entry.value().next_run,
+            })
+            .collect()
+    }
+
+    /// Pause a schedule; it stays registered but stops firing until resumed.
+    pub fn pause(&self, id: ScheduleId) -> Result<()> {
+        let mut entry = self
+            .entries
+            .get_mut(&id)
+            .ok_or_else(|| Error::Internal(format!("schedule {id} not found")))?;
+        entry.enabled = false;
+        Ok(())
+    }
+
+    /// Resume a previously paused schedule.
+    pub fn resume(&self, id: ScheduleId) -> Result<()> {
+        let mut entry = self
+            .entries
+            .get_mut(&id)
+            .ok_or_else(|| Error::Internal(format!("schedule {id} not found")))?;
+        entry.enabled = true;
+        Ok(())
+    }
+
+    /// Permanently remove a schedule.
+    pub fn delete(&self, id: ScheduleId) -> Result<()> {
+        self.entries
+            .remove(&id)
+            .ok_or_else(|| Error::Internal(format!("schedule {id} not found")))?;
+        Ok(())
+    }
+
+    /// Run the background loop: every `poll_interval`, hand any due,
+    /// enabled schedule's task off to `scheduler`, then advance (cron) or
+    /// retire (one-off) it.
+    pub fn start(
+        &self,
+        scheduler: Arc<dyn TaskScheduler>,
+        poll_interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let entries = Arc::clone(&self.entries);
+
+        tokio::spawn(async move {
+            loop {
+                let now = Utc::now();
+                let due: Vec<ScheduleId> = entries
+                    .iter()
+                    .filter(|entry| entry.enabled && entry.next_run.is_some_and(|at| at <= now))
+                    .map(|entry| *entry.key())
+                    .collect();
+
+                for id in due {
+                    let Some(mut entry) = entries.get_mut(&id) else { continue };
+
+                    let mut task = entry.task_template.clone();
+                    task.id = Uuid::new_v4();
+                    task.status = TaskStatus::Pending;
+                    task.created_at = now;
+                    task.updated_at = now;
+
+                    if let Err(e) = scheduler.schedule_task(task).await {
+                        warn!("Failed to dispatch scheduled task for schedule {}: {}", id, e);
+                    } else {
+                        info!("Dispatched scheduled task for schedule {}", id);
+                    }
+
+                    entry.next_run = entry.spec.next_after(now);
+                    if entry.next_run.is_none() {
+                        entry.enabled = false;
+                    }
+                }
+
+                tokio::time::sleep(poll_interval).await;
+            }
+        })
+    }
+
+    #[instrument(skip(self))]
+    fn schedule_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cron_schedule_computes_next_run() {
+        let scheduler = CronScheduler::new();
+        let id = scheduler.add_cron(Task::default(), "0 * * * * *").unwrap();
+        let info = scheduler.list().into_iter().find(|s| s.id == id).unwrap();
+        assert!(info.enabled);
+        assert!(info.next_run.is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_cron_expression() {
+        let scheduler = CronScheduler::new();
+        assert!(scheduler.add_cron(Task::default(), "not a cron expr").is_err());
+    }
+
+    #[test]
+    fn pause_resume_and_delete() {
+        let scheduler = CronScheduler::new();
+        let id = scheduler.add_once(Task::default(), Utc::now() + chrono::Duration::hours(1)).unwrap();
+
+        scheduler.pause(id).unwrap();
+        assert!(!scheduler.list().into_iter().find(|s| s.id == id).unwrap().enabled);
+
+        scheduler.resume(id).unwrap();
+        assert!(scheduler.list().into_iter().find(|s| s.id == id).unwrap().enabled);
+
+        scheduler.delete(id).unwrap();
+        assert!(scheduler.list().is_empty());
+        assert_eq!(scheduler.schedule_count(), 0);
+    }
+}
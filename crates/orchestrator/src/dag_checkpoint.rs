@@ -0,0 +1,141 @@
+//! SQLite-backed checkpoint store for DAG execution.
+//!
+//! `DagExecutorImpl` persists the DAG definition here before it starts
+//! running, and the status (and output, if any) of each node as it
+//! finishes. `resume_dag` reloads that state and carries on from wherever
+//! execution stopped, instead of re-running completed nodes.
+
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{LlmResponse, TaskId, TaskStatus},
+};
+use meta_ai_core::orchestrator::{DagRunId, TaskDag};
+use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+use std::collections::HashMap;
+use tracing::{debug, instrument};
+
+/// Persists DAG run checkpoints to SQLite.
+pub struct DagCheckpointStore {
+    pool: SqlitePool,
+}
+
+impl DagCheckpointStore {
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to connect to DAG checkpoint database: {e}")))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS dag_runs (
+                run_id TEXT PRIMARY KEY,
+                dag TEXT NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to initialize dag_runs schema: {e}")))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS dag_checkpoints (
+                run_id TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                status TEXT NOT NULL,
+                output TEXT,
+                PRIMARY KEY (run_id, task_id)
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to initialize dag_checkpoints schema: {e}")))?;
+
+        Ok(Self { pool })
+    }
+
+    /// Record the DAG definition for `run_id`, so it can be reloaded by `resume_dag`.
+    #[instrument(skip(self, dag))]
+    pub async fn save_dag(&self, run_id: DagRunId, dag: &TaskDag) -> Result<()> {
+        let payload = serde_json::to_string(dag)?;
+        sqlx::query("INSERT OR REPLACE INTO dag_runs (run_id, dag) VALUES (?, ?)")
+            .bind(run_id.to_string())
+            .bind(payload)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to persist dag run {run_id}: {e}")))?;
+        Ok(())
+    }
+
+    /// Record the outcome of a single node, so it's skipped on resume.
+    #[instrument(skip(self, output))]
+    pub async fn save_node_result(
+        &self,
+        run_id: DagRunId,
+        task_id: TaskId,
+        status: TaskStatus,
+        output: Option<&LlmResponse>,
+    ) -> Result<()> {
+        let status_json = serde_json::to_string(&status)?;
+        let output_json = output.map(serde_json::to_string).transpose()?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO dag_checkpoints (run_id, task_id, status, output) VALUES (?, ?, ?, ?)",
+        )
+        .bind(run_id.to_string())
+        .bind(task_id.to_string())
+        .bind(status_json)
+        .bind(output_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to checkpoint task {task_id} of dag run {run_id}: {e}")))?;
+
+        debug!("Checkpointed task {} of dag run {} as {:?}", task_id, run_id, status);
+        Ok(())
+    }
+
+    /// Load the DAG definition plus whatever node statuses/outputs were
+    /// checkpointed for `run_id`.
+    #[instrument(skip(self))]
+    pub async fn load(
+        &self,
+        run_id: DagRunId,
+    ) -> Result<(TaskDag, HashMap<TaskId, TaskStatus>, HashMap<TaskId, LlmResponse>)> {
+        let dag_row = sqlx::query("SELECT dag FROM dag_runs WHERE run_id = ?")
+            .bind(run_id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to load dag run {run_id}: {e}")))?
+            .ok_or_else(|| Error::Internal(format!("no checkpointed dag run {run_id}")))?;
+
+        let dag_payload: String = dag_row
+            .try_get("dag")
+            .map_err(|e| Error::Internal(format!("malformed dag run row: {e}")))?;
+        let dag: TaskDag = serde_json::from_str(&dag_payload)?;
+
+        let checkpoint_rows = sqlx::query("SELECT task_id, status, output FROM dag_checkpoints WHERE run_id = ?")
+            .bind(run_id.to_string())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to load checkpoints for dag run {run_id}: {e}")))?;
+
+        let mut statuses = HashMap::new();
+        let mut outputs = HashMap::new();
+
+        for row in checkpoint_rows {
+            let task_id: String = row.try_get("task_id").map_err(|e| Error::Internal(format!("malformed checkpoint row: {e}")))?;
+            let task_id: TaskId = task_id
+                .parse()
+                .map_err(|e| Error::Internal(format!("malformed task id in checkpoint: {e}")))?;
+            let status_json: String = row.try_get("status").map_err(|e| Error::Internal(format!("malformed checkpoint row: {e}")))?;
+            let status: TaskStatus = serde_json::from_str(&status_json)?;
+            statuses.insert(task_id, status);
+
+            let output_json: Option<String> = row.try_get("output").map_err(|e| Error::Internal(format!("malformed checkpoint row: {e}")))?;
+            if let Some(output_json) = output_json {
+                outputs.insert(task_id, serde_json::from_str(&output_json)?);
+            }
+        }
+
+        Ok((dag, statuses, outputs))
+    }
+}
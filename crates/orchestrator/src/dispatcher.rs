@@ -3,23 +3,406 @@
 use dashmap::DashMap;
 use meta_ai_common::{
     error::{Error, Result},
-    types::{LlmRequest, LlmResponse, TaskId, LlmProvider},
+    types::{LlmRequest, LlmResponse, Metadata, Priority, RequestId, TaskId, LlmProvider},
     metrics::MetricsCollector,
 };
-use meta_ai_core::agent::{Agent, SelectionStrategy};
+use meta_ai_core::agent::{Agent, AgentCapabilities, SelectionStrategy, TaskType};
+use meta_ai_core::evaluation::{Evaluator, EvaluationMetrics, ValidationResult};
+use meta_ai_core::model_catalog::ModelCatalog;
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::{
-    sync::{Arc, atomic::{AtomicU64, Ordering}},
-    time::Instant,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, atomic::{AtomicU64, AtomicUsize, Ordering}},
+    time::{Duration, Instant},
 };
 use tokio::sync::Semaphore;
 use tracing::{info, warn, instrument};
 
+use crate::ab_test::AbTestEngine;
+use crate::canary_router::{CanaryRouter, RoutingArm};
+use crate::latency_slo::{LatencyHistogram, LatencySlo};
+use crate::routing_rules::{capability_satisfied, RoutingRulesEngine};
+
 /// Task dispatcher for routing requests to agents
 pub struct TaskDispatcher {
     active_requests: Arc<DashMap<TaskId, DispatchedRequest>>,
     request_counter: Arc<AtomicU64>,
     max_concurrent: usize,
     semaphore: Arc<Semaphore>,
+    /// Minimum acceptable `provider_pricing` quality score for
+    /// `SelectionStrategy::CostOptimized`, keyed by task type. A task type
+    /// with no entry has no floor (the cheapest available agent always
+    /// qualifies). Set via `set_quality_floor`.
+    quality_floors: Arc<DashMap<TaskType, f64>>,
+    /// Live per-provider quality score overriding `provider_pricing`'s static
+    /// figure for `SelectionStrategy::CostOptimized`, e.g. fed from
+    /// `Evaluator`-tracked human feedback via `set_provider_quality_score`. A
+    /// provider with no entry falls back to `provider_pricing`.
+    provider_quality_scores: Arc<DashMap<LlmProvider, f64>>,
+    /// How `dispatch` handles a prompt that doesn't fit the selected agent's
+    /// context window. Set via `set_truncation_strategy`.
+    truncation_strategy: Mutex<TruncationStrategy>,
+    /// Weighted/canary provider split used by `SelectionStrategy::Canary`.
+    canary_router: CanaryRouter,
+    /// Named experiments used by `SelectionStrategy::AbTest`. Set via
+    /// `ab_test_engine`.
+    ab_test_engine: AbTestEngine,
+    /// How many additional agents `dispatch` will try after a retryable
+    /// failure before giving up, excluding the ones that already failed.
+    /// Set via `set_max_failover_attempts`. Defaults to 2.
+    max_failover_attempts: AtomicU64,
+    /// Shadow traffic target, if configured. Set via `set_shadow_target`.
+    shadow: Mutex<Option<ShadowTarget>>,
+    /// Results of mirrored shadow requests, keyed by the original request's
+    /// id. Grows without bound, same tradeoff as `MetaAIOrchestrator`'s
+    /// `dead_letter_queue`; an operator reads `shadow_results` periodically
+    /// and is expected to clear/export what they've seen.
+    shadow_results: Arc<DashMap<RequestId, ShadowResult>>,
+    /// Provider a given `LlmRequest.session_id` was last routed to, so later
+    /// turns in the same conversation land on the same provider (and can
+    /// reuse provider-side cached context) instead of bouncing around per
+    /// the configured `SelectionStrategy`. Updated whenever a sticky session
+    /// is (re-)routed; entries are never evicted (see `session_id_from_metadata`
+    /// for how a session id can reach a request built from a `Task`).
+    session_affinity: Arc<DashMap<String, LlmProvider>>,
+    /// Per-provider concurrency bulkheads, so a stalled provider can't
+    /// consume every permit in `semaphore` and starve dispatches bound for
+    /// other providers. Lazily created on first use per provider, sized with
+    /// `default_bulkhead_limit` unless `set_provider_bulkhead_limit` set an
+    /// override for that provider first.
+    provider_bulkheads: Arc<DashMap<LlmProvider, ProviderBulkhead>>,
+    /// Bulkhead limit used the first time a provider is dispatched to,
+    /// absent an override in `bulkhead_limits`. Defaults to `max_concurrent`.
+    default_bulkhead_limit: usize,
+    /// Per-provider overrides of `default_bulkhead_limit`, set via
+    /// `set_provider_bulkhead_limit`. Only takes effect if set before that
+    /// provider's bulkhead is first created; `tokio::sync::Semaphore` can
+    /// only grow, not shrink, so an already-created bulkhead keeps its
+    /// original limit.
+    bulkhead_limits: Arc<DashMap<LlmProvider, usize>>,
+    /// Recent per-attempt latencies (ms) per provider, capped at
+    /// `LATENCY_SAMPLE_CAP` samples each. Updated after every completed
+    /// dispatch attempt (success or failure) via `record_latency`;
+    /// `SelectionStrategy::LowestLatency` reads a cached P50 off this
+    /// instead of calling `health_check` on every candidate per request.
+    latency_samples: Arc<DashMap<LlmProvider, Mutex<VecDeque<u64>>>>,
+    /// Real percentile/SLO-burn-rate tracking per `(provider, task type)`
+    /// pair, fed from the same completed dispatch attempts `latency_samples`
+    /// is, via `record_latency`. Finer-grained than `latency_samples`
+    /// (which `LowestLatency` selection reads a cached P50/P90 off of) since
+    /// `EvaluationMetrics.p95_latency_ms`/`p99_latency_ms` and SLO
+    /// burn-rate need a real percentile, not a rough cost-routing signal.
+    latency_histograms: Arc<DashMap<(LlmProvider, TaskType), Mutex<LatencyHistogram>>>,
+    /// Running cost totals per `(provider, task type)` pair, fed from
+    /// `estimated_cost` on every completed dispatch attempt via
+    /// `record_cost`. Backs `cost_evaluation_metrics`'s
+    /// `cost_per_request`/`cost_per_successful_task`.
+    cost_totals: Arc<DashMap<(LlmProvider, TaskType), Mutex<CostAccumulator>>>,
+    /// Real per-model context window, pricing, and capability data, used by
+    /// `effective_capabilities`/`cost_for` in place of `agent.capabilities()`/
+    /// `estimated_cost`'s coarser figures when both this and `agent_models`
+    /// have an entry for the dispatched provider. Set via
+    /// `set_model_catalog`. `None` (the default) keeps the old behavior.
+    model_catalog: Mutex<Option<Arc<ModelCatalog>>>,
+    /// Catalog model name each provider is configured to use (normally
+    /// `AgentConfig.model`), so `model_catalog` lookups know which entry
+    /// applies. Set via `set_agent_model`.
+    agent_models: Arc<DashMap<LlmProvider, String>>,
+    /// Config-driven rules used by `SelectionStrategy::RuleBased`. Set via
+    /// `set_routing_rules`.
+    routing_rules: RoutingRulesEngine,
+    /// Deadline `dispatch` enforces on `agent.submit` when a request doesn't
+    /// set its own `timeout_ms`. Set via `set_default_timeout_ms`.
+    default_timeout_ms: AtomicU64,
+    /// How long `dispatch` will wait for a free `semaphore` permit before
+    /// giving up with `Error::Overloaded`, instead of queueing indefinitely.
+    /// Set via `set_queue_acquire_timeout_ms`.
+    queue_acquire_timeout_ms: AtomicU64,
+    /// Number of dispatches currently waiting on a permit, used to report a
+    /// request's position in the wait queue if it times out. Incremented
+    /// when a dispatch starts waiting and decremented once it stops, whether
+    /// it acquired a permit or timed out.
+    queue_depth: AtomicUsize,
+    /// Reserved permit pool that only `Priority::High`/`Priority::Critical`
+    /// requests (see `priority_from_metadata`) may draw on once `semaphore`
+    /// is exhausted, so bulk low-priority work can never consume every
+    /// dispatch permit and starve higher-priority requests of one to run in.
+    /// Carved out of `max_concurrent` at construction, mirroring
+    /// `MetaAIOrchestrator`'s `task_semaphore`/`priority_semaphore` split.
+    priority_semaphore: Arc<Semaphore>,
+}
+
+/// Fallback deadline for a dispatch attempt when neither the request nor
+/// `set_default_timeout_ms` has configured one.
+const DEFAULT_DISPATCH_TIMEOUT_MS: u64 = 30_000;
+
+/// Fallback deadline for how long `dispatch` waits for a free `semaphore`
+/// permit before giving up with `Error::Overloaded`.
+const DEFAULT_QUEUE_ACQUIRE_TIMEOUT_MS: u64 = 10_000;
+
+/// Fraction of `max_concurrent` carved out into `priority_semaphore`,
+/// mirroring `OrchestratorConfig::default().reserved_priority_permits` being
+/// roughly a tenth of `max_concurrent_tasks`.
+const RESERVED_PRIORITY_FRACTION: f64 = 0.1;
+
+/// How many recent latency samples `record_latency` keeps per provider
+/// before dropping the oldest.
+const LATENCY_SAMPLE_CAP: usize = 200;
+
+/// Cached P50/P90 latency snapshot for one provider, as returned by
+/// `TaskDispatcher::latency_stats`.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencyStats {
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub sample_count: usize,
+}
+
+/// Running cost totals for a `(provider, task type)` pair, accumulated by
+/// `TaskDispatcher::record_cost`.
+#[derive(Debug, Default)]
+struct CostAccumulator {
+    total_cost: f64,
+    total_requests: u64,
+    successful_requests: u64,
+}
+
+/// One provider's concurrency bulkhead: a semaphore plus the limit it was
+/// created with, so `provider_in_flight` can report usage without relying
+/// on a limit that might since have been overridden.
+struct ProviderBulkhead {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+}
+
+/// A secondary agent that mirrors a sampled fraction of live traffic for
+/// offline comparison, without its response ever being returned to the
+/// caller.
+#[derive(Clone)]
+struct ShadowTarget {
+    agent: Arc<dyn Agent>,
+    evaluator: Option<Arc<dyn Evaluator>>,
+    /// Fraction of dispatched requests to mirror, in `0.0..=1.0`.
+    sample_rate: f64,
+}
+
+/// Outcome of one mirrored shadow request, as returned by
+/// `TaskDispatcher::shadow_results`.
+#[derive(Debug, Clone)]
+pub struct ShadowResult {
+    pub request_id: RequestId,
+    pub shadow_provider: LlmProvider,
+    pub response: Option<LlmResponse>,
+    pub validation: Option<ValidationResult>,
+    pub error: Option<String>,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How `TaskDispatcher::dispatch` handles a prompt that's estimated to
+/// exceed the selected agent's remaining context budget
+/// (`AgentCapabilities.context_window - AgentCapabilities.max_tokens`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruncationStrategy {
+    /// Fail the dispatch with `Error::Agent` instead of sending an
+    /// over-budget prompt.
+    Reject,
+    /// Drop the oldest paragraphs (blocks separated by a blank line) first,
+    /// keeping the most recent ones, until the prompt fits.
+    TruncateOldest,
+    /// Keep the first and last paragraphs (typically the system
+    /// instructions and the latest message) and drop from the middle.
+    TruncateMiddleOut,
+}
+
+/// Marker inserted where `TruncationStrategy::TruncateMiddleOut` removed
+/// text, so the provider (and anyone reading a transcript later) can tell
+/// truncation happened.
+const TRUNCATION_MARKER: &str = "\n\n[... truncated for context window ...]\n\n";
+
+/// Drop the oldest paragraphs first, keeping the most recent ones, until the
+/// remainder fits `char_budget`. Falls back to a hard suffix truncation if a
+/// single paragraph is already over budget.
+fn truncate_oldest(prompt: &str, char_budget: usize) -> String {
+    if prompt.len() <= char_budget {
+        return prompt.to_string();
+    }
+
+    let paragraphs: Vec<&str> = prompt.split("\n\n").collect();
+    let mut start = 0;
+    while start + 1 < paragraphs.len() && paragraphs[start..].join("\n\n").len() > char_budget {
+        start += 1;
+    }
+
+    let kept = paragraphs[start..].join("\n\n");
+    if kept.len() <= char_budget {
+        kept
+    } else {
+        kept.chars().rev().take(char_budget).collect::<Vec<_>>().into_iter().rev().collect()
+    }
+}
+
+/// Keep as many leading and trailing paragraphs as fit in `char_budget`,
+/// preferring to extend the tail (the most recent context) first, and
+/// collapse whatever's left in the middle behind `TRUNCATION_MARKER`.
+fn truncate_middle_out(prompt: &str, char_budget: usize) -> String {
+    if prompt.len() <= char_budget {
+        return prompt.to_string();
+    }
+
+    let paragraphs: Vec<&str> = prompt.split("\n\n").collect();
+    if paragraphs.len() < 3 {
+        return truncate_oldest(prompt, char_budget);
+    }
+
+    let mut head_end = 1; // exclusive
+    let mut tail_start = paragraphs.len() - 1; // inclusive
+    let mut kept_len =
+        paragraphs[..head_end].join("\n\n").len() + paragraphs[tail_start..].join("\n\n").len() + TRUNCATION_MARKER.len();
+
+    loop {
+        let can_grow_tail = tail_start > head_end;
+        let next_tail_len = if can_grow_tail { paragraphs[tail_start - 1].len() + 2 } else { 0 };
+        if can_grow_tail && kept_len + next_tail_len <= char_budget {
+            tail_start -= 1;
+            kept_len += next_tail_len;
+            continue;
+        }
+
+        let can_grow_head = head_end < tail_start;
+        let next_head_len = if can_grow_head { paragraphs[head_end].len() + 2 } else { 0 };
+        if can_grow_head && kept_len + next_head_len <= char_budget {
+            head_end += 1;
+            kept_len += next_head_len;
+            continue;
+        }
+
+        break;
+    }
+
+    if head_end >= tail_start {
+        return paragraphs.join("\n\n");
+    }
+
+    format!("{}{}{}", paragraphs[..head_end].join("\n\n"), TRUNCATION_MARKER, paragraphs[tail_start..].join("\n\n"))
+}
+
+/// Rough per-1M-token USD pricing and a coarse quality score (0.0-1.0) for
+/// each provider, used by `SelectionStrategy::CostOptimized`. These are
+/// illustrative, not live pricing-API data; update alongside provider
+/// contract changes.
+fn provider_pricing(provider: LlmProvider) -> (f64, f64) {
+    match provider {
+        LlmProvider::OpenAI => (5.00, 0.90),
+        LlmProvider::Claude => (6.00, 0.95),
+        LlmProvider::Copilot => (2.00, 0.75),
+        LlmProvider::Cursor => (3.00, 0.80),
+        LlmProvider::CodeWhisperer => (1.50, 0.70),
+        LlmProvider::Local => (0.0, 0.60),
+    }
+}
+
+/// Rough token estimate from prompt length: ~4 characters per token, the
+/// commonly used approximation for English text under GPT/Claude-style BPE
+/// tokenizers. Good enough for comparing providers' relative cost; not
+/// meant to predict exact billed usage.
+fn estimate_prompt_tokens(prompt: &str) -> u32 {
+    ((prompt.len() as f64 / 4.0).ceil() as u32).max(1)
+}
+
+/// Estimated USD cost of `prompt_tokens` against `provider`'s pricing.
+pub(crate) fn estimated_cost(provider: LlmProvider, prompt_tokens: u32) -> f64 {
+    let (cost_per_million, _quality) = provider_pricing(provider);
+    (prompt_tokens as f64 / 1_000_000.0) * cost_per_million
+}
+
+/// Prometheus label for `TaskType`, matching its `#[serde(rename_all =
+/// "snake_case")]` representation.
+pub fn task_type_label(task_type: TaskType) -> &'static str {
+    match task_type {
+        TaskType::Reasoning => "reasoning",
+        TaskType::CodeGeneration => "code_generation",
+        TaskType::Documentation => "documentation",
+        TaskType::Analysis => "analysis",
+        TaskType::Creative => "creative",
+        TaskType::Translation => "translation",
+        TaskType::Summarization => "summarization",
+        TaskType::QA => "qa",
+    }
+}
+
+/// Best-effort session id for a request, read from `metadata["session_id"]`
+/// (set by callers that know it) for call sites that build an `LlmRequest`
+/// from a `Task` and have no dedicated session field to thread through.
+pub fn session_id_from_metadata(metadata: &Metadata) -> Option<String> {
+    metadata.get("session_id")?.as_str().map(|s| s.to_string())
+}
+
+/// Per-request override of the configured `SelectionStrategy`, read from
+/// `metadata["selection_strategy"]`. Lets one caller ask for e.g.
+/// cost-optimized selection without changing the orchestrator-wide default.
+pub fn selection_strategy_from_metadata(metadata: &Metadata) -> Option<SelectionStrategy> {
+    match metadata.get("selection_strategy")?.as_str()? {
+        "round_robin" => Some(SelectionStrategy::RoundRobin),
+        "lowest_latency" => Some(SelectionStrategy::LowestLatency),
+        "best_match" => Some(SelectionStrategy::BestMatch),
+        "cost_optimized" => Some(SelectionStrategy::CostOptimized),
+        "random" => Some(SelectionStrategy::Random),
+        "canary" => Some(SelectionStrategy::Canary),
+        "rule_based" => Some(SelectionStrategy::RuleBased),
+        "ab_test" => Some(SelectionStrategy::AbTest),
+        _ => None,
+    }
+}
+
+/// Per-request priority, read from `metadata["priority"]` (e.g. set from
+/// `Task.priority` by `execute_task_with_retry`). Defaults to `Priority::Medium`
+/// for requests that don't carry one, so `dispatch`'s priority-aware permit
+/// acquisition still has a well-defined tier to place them in.
+pub fn priority_from_metadata(metadata: &Metadata) -> Priority {
+    metadata
+        .get("priority")
+        .and_then(|v| serde_json::from_value::<Priority>(v.clone()).ok())
+        .unwrap_or(Priority::Medium)
+}
+
+/// Per-request provider pin, read from `metadata["pinned_provider"]`. Takes
+/// priority over `selection_strategy_from_metadata` when both are set;
+/// callers are expected to validate the result against whatever allow-list
+/// applies before using it (see `OrchestratorConfig.allowed_override_providers`).
+pub fn pinned_provider_from_metadata(metadata: &Metadata) -> Option<LlmProvider> {
+    match metadata.get("pinned_provider")?.as_str()? {
+        "openai" => Some(LlmProvider::OpenAI),
+        "claude" => Some(LlmProvider::Claude),
+        "copilot" => Some(LlmProvider::Copilot),
+        "cursor" => Some(LlmProvider::Cursor),
+        "codewhisperer" => Some(LlmProvider::CodeWhisperer),
+        "local" => Some(LlmProvider::Local),
+        _ => None,
+    }
+}
+
+/// Best-effort `TaskType` for a request, read from `metadata["task_type"]`
+/// (set by callers that know it). `None` if absent or unrecognized.
+pub(crate) fn task_type_from_metadata(metadata: &Metadata) -> Option<TaskType> {
+    match metadata.get("task_type")?.as_str()? {
+        "reasoning" => Some(TaskType::Reasoning),
+        "code_generation" => Some(TaskType::CodeGeneration),
+        "documentation" => Some(TaskType::Documentation),
+        "analysis" => Some(TaskType::Analysis),
+        "creative" => Some(TaskType::Creative),
+        "translation" => Some(TaskType::Translation),
+        "summarization" => Some(TaskType::Summarization),
+        "qa" => Some(TaskType::QA),
+        _ => None,
+    }
+}
+
+/// Name of the running `AbTestEngine` experiment `SelectionStrategy::AbTest`
+/// should assign this request to, read from `metadata["ab_test"]`.
+pub(crate) fn ab_test_name_from_metadata(metadata: &Metadata) -> Option<String> {
+    metadata.get("ab_test")?.as_str().map(|s| s.to_string())
 }
 
 /// Dispatched request tracking
@@ -30,17 +413,340 @@ struct DispatchedRequest {
     attempt: u32,
 }
 
+/// How many attempts' worth of history `dispatch` keeps in
+/// `LlmResponse.metadata["dispatch_history"]`. Bounded independently of
+/// `max_failover_attempts` so a future increase to that limit can't grow the
+/// response payload unboundedly.
+const MAX_DISPATCH_HISTORY: usize = 10;
+
+/// One attempt's outcome within a single `dispatch` call, recorded in
+/// `LlmResponse.metadata["dispatch_history"]` so callers can see which
+/// providers were tried, how long each took, and why a failover happened,
+/// without digging through logs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DispatchAttemptRecord {
+    attempt: u32,
+    provider: LlmProvider,
+    latency_ms: u64,
+    outcome: DispatchOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DispatchOutcome {
+    Success,
+    Failed { error: String },
+}
+
 impl TaskDispatcher {
     pub fn new(max_concurrent: usize) -> Self {
+        let reserved_priority_permits = ((max_concurrent as f64 * RESERVED_PRIORITY_FRACTION).floor() as usize).min(max_concurrent);
         Self {
             active_requests: Arc::new(DashMap::new()),
             request_counter: Arc::new(AtomicU64::new(0)),
             max_concurrent,
-            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            semaphore: Arc::new(Semaphore::new(max_concurrent - reserved_priority_permits)),
+            priority_semaphore: Arc::new(Semaphore::new(reserved_priority_permits)),
+            quality_floors: Arc::new(DashMap::new()),
+            provider_quality_scores: Arc::new(DashMap::new()),
+            truncation_strategy: Mutex::new(TruncationStrategy::TruncateOldest),
+            canary_router: CanaryRouter::new(),
+            ab_test_engine: AbTestEngine::new(),
+            max_failover_attempts: AtomicU64::new(2),
+            shadow: Mutex::new(None),
+            shadow_results: Arc::new(DashMap::new()),
+            session_affinity: Arc::new(DashMap::new()),
+            provider_bulkheads: Arc::new(DashMap::new()),
+            default_bulkhead_limit: max_concurrent,
+            bulkhead_limits: Arc::new(DashMap::new()),
+            latency_samples: Arc::new(DashMap::new()),
+            latency_histograms: Arc::new(DashMap::new()),
+            cost_totals: Arc::new(DashMap::new()),
+            model_catalog: Mutex::new(None),
+            agent_models: Arc::new(DashMap::new()),
+            routing_rules: RoutingRulesEngine::new(Vec::new()),
+            default_timeout_ms: AtomicU64::new(DEFAULT_DISPATCH_TIMEOUT_MS),
+            queue_acquire_timeout_ms: AtomicU64::new(DEFAULT_QUEUE_ACQUIRE_TIMEOUT_MS),
+            queue_depth: AtomicUsize::new(0),
         }
     }
-    
-    /// Dispatch request to best available agent
+
+    /// Set how long `dispatch` will wait for a free permit before giving up
+    /// with `Error::Overloaded`, instead of queueing indefinitely.
+    pub fn set_queue_acquire_timeout_ms(&self, timeout_ms: u64) {
+        self.queue_acquire_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+    }
+
+    /// Mirror `sample_rate` (`0.0..=1.0`) of dispatched requests to `agent`,
+    /// scoring its responses with `evaluator` if given, without ever
+    /// returning them to the caller. Overrides any previous shadow target.
+    pub fn set_shadow_target(&self, agent: Arc<dyn Agent>, evaluator: Option<Arc<dyn Evaluator>>, sample_rate: f64) {
+        *self.shadow.lock() = Some(ShadowTarget { agent, evaluator, sample_rate: sample_rate.clamp(0.0, 1.0) });
+    }
+
+    /// Stop mirroring shadow traffic.
+    pub fn clear_shadow_target(&self) {
+        *self.shadow.lock() = None;
+    }
+
+    /// Recorded outcomes of mirrored shadow requests so far (see
+    /// `set_shadow_target`).
+    pub fn shadow_results(&self) -> Vec<ShadowResult> {
+        self.shadow_results.iter().map(|entry| entry.value().clone()).collect()
+    }
+
+    /// If a shadow target is configured, sample `request` against its rate
+    /// and fire-and-forget a mirrored call whose outcome lands in
+    /// `shadow_results`. Never affects the primary dispatch.
+    fn maybe_mirror_shadow_traffic(&self, request: &LlmRequest) {
+        let Some(shadow) = self.shadow.lock().clone() else {
+            return;
+        };
+        if rand::thread_rng().gen::<f64>() >= shadow.sample_rate {
+            return;
+        }
+
+        let shadow_request = request.clone();
+        let shadow_results = Arc::clone(&self.shadow_results);
+
+        tokio::spawn(async move {
+            let request_id = shadow_request.id;
+            let provider = shadow.agent.provider();
+
+            let result = match shadow.agent.submit(shadow_request).await {
+                Ok(response) => {
+                    let validation = match &shadow.evaluator {
+                        Some(evaluator) => evaluator.validate_response(&response).await.ok(),
+                        None => None,
+                    };
+                    ShadowResult {
+                        request_id,
+                        shadow_provider: provider,
+                        response: Some(response),
+                        validation,
+                        error: None,
+                        recorded_at: chrono::Utc::now(),
+                    }
+                }
+                Err(e) => ShadowResult {
+                    request_id,
+                    shadow_provider: provider,
+                    response: None,
+                    validation: None,
+                    error: Some(e.to_string()),
+                    recorded_at: chrono::Utc::now(),
+                },
+            };
+
+            shadow_results.insert(request_id, result);
+        });
+    }
+
+    /// Configure (or replace) the weighted provider split `SelectionStrategy::
+    /// Canary` uses for `task_type`. Pass `None` to set the fallback route
+    /// for task types with no dedicated split.
+    pub fn set_canary_route(&self, task_type: Option<TaskType>, arms: Vec<RoutingArm>) {
+        self.canary_router.set_route(task_type, arms);
+    }
+
+    /// Experiments `SelectionStrategy::AbTest` assigns requests to, keyed by
+    /// `metadata["ab_test"]`. Use this to create/list/conclude experiments.
+    pub fn ab_test_engine(&self) -> &AbTestEngine {
+        &self.ab_test_engine
+    }
+
+    /// Set how many additional agents `dispatch` will try after a retryable
+    /// failure before giving up. Defaults to 2.
+    pub fn set_max_failover_attempts(&self, attempts: u64) {
+        self.max_failover_attempts.store(attempts, Ordering::Relaxed);
+    }
+
+    /// Replace the rule set `SelectionStrategy::RuleBased` evaluates.
+    pub fn set_routing_rules(&self, rules: Vec<meta_ai_core::agent::RoutingRule>) {
+        self.routing_rules.reload(rules);
+    }
+
+    /// Set the deadline `dispatch` enforces on `agent.submit` for requests
+    /// that don't set their own `timeout_ms`. Defaults to
+    /// `DEFAULT_DISPATCH_TIMEOUT_MS`.
+    pub fn set_default_timeout_ms(&self, timeout_ms: u64) {
+        self.default_timeout_ms.store(timeout_ms, Ordering::Relaxed);
+    }
+
+    /// Override the concurrency limit for `provider`'s bulkhead. Only takes
+    /// effect if `provider`'s bulkhead hasn't been created yet (see
+    /// `provider_bulkheads`); call this during setup, before dispatching any
+    /// requests for that provider. Defaults to the dispatcher's
+    /// `max_concurrent` for every provider that hasn't been overridden.
+    pub fn set_provider_bulkhead_limit(&self, provider: LlmProvider, limit: usize) {
+        self.bulkhead_limits.insert(provider, limit);
+    }
+
+    /// Current in-flight request count for every provider whose bulkhead has
+    /// been used so far, for `DispatchStats::provider_in_flight`.
+    pub fn provider_in_flight(&self) -> HashMap<LlmProvider, usize> {
+        self.provider_bulkheads
+            .iter()
+            .map(|entry| {
+                let bulkhead = entry.value();
+                (*entry.key(), bulkhead.limit.saturating_sub(bulkhead.semaphore.available_permits()))
+            })
+            .collect()
+    }
+
+    /// The semaphore guarding `provider`'s bulkhead, creating it on first use
+    /// with `default_bulkhead_limit` (or the override set via
+    /// `set_provider_bulkhead_limit`).
+    fn bulkhead_semaphore(&self, provider: LlmProvider) -> Arc<Semaphore> {
+        let limit = self.bulkhead_limits.get(&provider).map(|l| *l).unwrap_or(self.default_bulkhead_limit);
+        Arc::clone(
+            &self
+                .provider_bulkheads
+                .entry(provider)
+                .or_insert_with(|| ProviderBulkhead { semaphore: Arc::new(Semaphore::new(limit)), limit })
+                .semaphore,
+        )
+    }
+
+    /// Per-`(task_type, provider)` selection counts recorded by
+    /// `SelectionStrategy::Canary`, so a rollout's actual traffic split can
+    /// be verified against what was configured.
+    pub fn canary_stats(&self) -> Vec<crate::canary_router::CanaryArmStats> {
+        self.canary_router.arm_stats()
+    }
+
+    /// Set the minimum `provider_pricing` quality score `SelectionStrategy::
+    /// CostOptimized` will accept for `task_type`. Overrides any previous
+    /// floor for that task type.
+    pub fn set_quality_floor(&self, task_type: TaskType, floor: f64) {
+        self.quality_floors.insert(task_type, floor);
+    }
+
+    /// Override `provider`'s `provider_pricing` quality score for
+    /// `SelectionStrategy::CostOptimized` with a live reading, e.g.
+    /// `Evaluator::provider_accuracy` informed by recorded human feedback.
+    /// Intended to be refreshed periodically by the caller, the same way
+    /// `SelfCheckLoop` periodically refreshes its `HealthGauge`.
+    pub fn set_provider_quality_score(&self, provider: LlmProvider, score: f64) {
+        self.provider_quality_scores.insert(provider, score);
+    }
+
+    /// Current quality score `SelectionStrategy::CostOptimized` uses for
+    /// `provider`: the live override from `set_provider_quality_score` if
+    /// one has been set, otherwise `provider_pricing`'s static figure.
+    fn provider_quality(&self, provider: LlmProvider) -> f64 {
+        self.provider_quality_scores
+            .get(&provider)
+            .map(|score| *score)
+            .unwrap_or_else(|| provider_pricing(provider).1)
+    }
+
+    /// Set how `dispatch` handles a prompt that doesn't fit the selected
+    /// agent's context window. Defaults to `TruncationStrategy::
+    /// TruncateOldest`.
+    pub fn set_truncation_strategy(&self, strategy: TruncationStrategy) {
+        *self.truncation_strategy.lock() = strategy;
+    }
+
+    /// Install a model catalog (e.g. `ModelCatalog::from_config(&config.
+    /// model_catalog)`) so `effective_capabilities`'s context-window guard
+    /// and capability checks, and `cost_for`'s cost estimation, use real
+    /// per-model data instead of `agent.capabilities()`/`provider_pricing`'s
+    /// coarser figures - for whichever providers also have a model name
+    /// registered via `set_agent_model`.
+    pub fn set_model_catalog(&self, catalog: Arc<ModelCatalog>) {
+        *self.model_catalog.lock() = Some(catalog);
+    }
+
+    /// Record which catalog model name `provider` is configured to use
+    /// (normally `AgentConfig.model`), so `model_catalog` lookups know which
+    /// entry to use for it.
+    pub fn set_agent_model(&self, provider: LlmProvider, model: impl Into<String>) {
+        self.agent_models.insert(provider, model.into());
+    }
+
+    /// The catalog model name registered for `provider` via `set_agent_model`,
+    /// if any - e.g. to label cost metrics with the specific model billed
+    /// rather than just the provider.
+    pub fn agent_model(&self, provider: LlmProvider) -> Option<String> {
+        self.agent_models.get(&provider).map(|m| m.clone())
+    }
+
+    /// Capabilities to guard/check `provider` against: its model catalog
+    /// entry if both `model_catalog` and `agent_models` have one registered
+    /// for it, otherwise `fallback` (normally `agent.capabilities()`).
+    fn effective_capabilities(&self, provider: LlmProvider, fallback: AgentCapabilities) -> AgentCapabilities {
+        let catalog = self.model_catalog.lock().clone();
+        let model = self.agent_models.get(&provider).map(|m| m.clone());
+        match (catalog, model) {
+            (Some(catalog), Some(model)) => {
+                catalog.lookup(provider, &model).map(|info| info.capabilities.clone()).unwrap_or(fallback)
+            }
+            _ => fallback,
+        }
+    }
+
+    /// `prompt_tokens`' USD cost for `provider`: its model catalog entry's
+    /// `input_cost_per_million` if both `model_catalog` and `agent_models`
+    /// have one registered for it, otherwise `estimated_cost`'s static
+    /// per-provider pricing.
+    fn cost_for(&self, provider: LlmProvider, prompt_tokens: u32) -> f64 {
+        let catalog = self.model_catalog.lock().clone();
+        let model = self.agent_models.get(&provider).map(|m| m.clone());
+        if let (Some(catalog), Some(model)) = (catalog, model) {
+            if let Some(info) = catalog.lookup(provider, &model) {
+                return (prompt_tokens as f64 / 1_000_000.0) * info.input_cost_per_million;
+            }
+        }
+        estimated_cost(provider, prompt_tokens)
+    }
+
+    /// Public wrapper around `cost_for`, for callers outside this module
+    /// (e.g. `execute_task_with_retry`'s cost metrics) that want the same
+    /// model-catalog-aware estimate `record_cost` uses internally.
+    pub fn estimate_cost(&self, provider: LlmProvider, prompt_tokens: u32) -> f64 {
+        self.cost_for(provider, prompt_tokens)
+    }
+
+    /// Estimate `request`'s prompt against `capabilities.context_window`
+    /// (minus `capabilities.max_tokens`, reserved for the completion) and
+    /// apply `self.truncation_strategy` if it doesn't fit, instead of
+    /// letting the provider reject it with an opaque 400.
+    fn guard_context_window(&self, mut request: LlmRequest, capabilities: &AgentCapabilities) -> Result<LlmRequest> {
+        let token_budget = capabilities.context_window.saturating_sub(capabilities.max_tokens);
+        let estimated_tokens = estimate_prompt_tokens(&request.prompt);
+        if estimated_tokens <= token_budget {
+            return Ok(request);
+        }
+
+        let char_budget = (token_budget as usize) * 4;
+        match *self.truncation_strategy.lock() {
+            TruncationStrategy::Reject => Err(Error::Agent(format!(
+                "prompt estimated at {estimated_tokens} tokens exceeds the {token_budget} token context budget for the selected agent"
+            ))),
+            TruncationStrategy::TruncateOldest => {
+                warn!(
+                    estimated_tokens,
+                    token_budget, "truncating oldest context to fit the selected agent's context window"
+                );
+                request.prompt = truncate_oldest(&request.prompt, char_budget);
+                Ok(request)
+            }
+            TruncationStrategy::TruncateMiddleOut => {
+                warn!(
+                    estimated_tokens,
+                    token_budget, "truncating middle-out to fit the selected agent's context window"
+                );
+                request.prompt = truncate_middle_out(&request.prompt, char_budget);
+                Ok(request)
+            }
+        }
+    }
+
+    /// Dispatch request to best available agent. On a retryable failure
+    /// (see `Error::is_retryable`), automatically fails over to the
+    /// next-best agent excluding ones that already failed, up to
+    /// `max_failover_attempts` additional tries, before giving up.
     #[instrument(skip(self, request, agents))]
     pub async fn dispatch(
         &self,
@@ -48,48 +754,182 @@ impl TaskDispatcher {
         agents: &[Box<dyn Agent>],
         selection_strategy: SelectionStrategy,
     ) -> Result<LlmResponse> {
-        // Acquire semaphore permit
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| Error::Internal("Failed to acquire dispatcher permit".to_string()))?;
-        
+        // Acquire a dispatch permit, bounded by `queue_acquire_timeout_ms` so
+        // a caller doesn't wait indefinitely when the dispatcher is
+        // saturated. `queue_depth` tracks this request's position in the
+        // wait queue for `Error::Overloaded` to report if the wait times
+        // out. `High`/`Critical` requests (see `priority_from_metadata`) try
+        // the main pool first but fall back to the reserved
+        // `priority_semaphore` pool instead of queueing behind saturating
+        // lower-priority work, the same way `MetaAIOrchestrator`'s
+        // `task_semaphore`/`priority_semaphore` split already does at the
+        // task layer.
+        let priority = priority_from_metadata(&request.metadata);
+        let queue_position = self.queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
+        let acquire_timeout_ms = self.queue_acquire_timeout_ms.load(Ordering::Relaxed);
+        let permit_result = if priority >= Priority::High {
+            match Arc::clone(&self.semaphore).try_acquire_owned() {
+                Ok(permit) => Ok(Ok(permit)),
+                Err(_) => tokio::time::timeout(
+                    Duration::from_millis(acquire_timeout_ms),
+                    Arc::clone(&self.priority_semaphore).acquire_owned(),
+                )
+                .await,
+            }
+        } else {
+            tokio::time::timeout(
+                Duration::from_millis(acquire_timeout_ms),
+                Arc::clone(&self.semaphore).acquire_owned(),
+            )
+            .await
+        };
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+
+        let _permit = match permit_result {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(_)) => return Err(Error::Internal("Failed to acquire dispatcher permit".to_string())),
+            Err(_) => {
+                return Err(Error::Overloaded { queue_position, capacity: self.max_concurrent });
+            }
+        };
+
         let request_id = request.id;
         let task_id = request.task_id;
-        
-        // Select agent based on strategy
-        let agent = self.select_agent(&request, agents, selection_strategy).await?;
-        
-        // Track request
-        let dispatched = DispatchedRequest {
-            agent_provider: agent.provider(),
-            started_at: Instant::now(),
-            attempt: 1,
-        };
-        self.active_requests.insert(task_id, dispatched);
-        
-        // Execute request
-        let result = agent.submit(request).await;
-        
-        // Remove from tracking
-        self.active_requests.remove(&task_id);
-        
-        match result {
-            Ok(response) => {
-                info!("Request {} completed successfully", request_id);
-                Ok(response)
+        let max_attempts = self.max_failover_attempts.load(Ordering::Relaxed);
+
+        let mut excluded_providers: Vec<LlmProvider> = Vec::new();
+        let mut last_error: Option<Error> = None;
+        let mut attempt: u32 = 0;
+        let mut history: Vec<DispatchAttemptRecord> = Vec::new();
+
+        loop {
+            attempt += 1;
+
+            // Select agent based on strategy, excluding providers that
+            // already failed this dispatch.
+            let agent = match self.select_agent_excluding(&request, agents, selection_strategy, &excluded_providers).await {
+                Ok(agent) => agent,
+                Err(e) => return Err(last_error.unwrap_or(e)),
+            };
+
+            // Acquire this provider's bulkhead permit, independent of the
+            // global semaphore above, so a stalled provider can only exhaust
+            // its own slice of capacity and not every dispatcher permit.
+            // Bounded by the same `queue_acquire_timeout_ms` as the global
+            // permit above, so a saturated/stalled bulkhead can't hold this
+            // global permit (released by returning here, dropping `_permit`)
+            // indefinitely instead of freeing it for other requests.
+            let _provider_permit = match tokio::time::timeout(
+                Duration::from_millis(acquire_timeout_ms),
+                self.bulkhead_semaphore(agent.provider()).acquire_owned(),
+            )
+            .await
+            {
+                Ok(Ok(permit)) => permit,
+                Ok(Err(_)) => return Err(Error::Internal("Failed to acquire provider bulkhead permit".to_string())),
+                Err(_) => return Err(Error::Overloaded { queue_position, capacity: self.max_concurrent }),
+            };
+
+            // Guard against the prompt overflowing the selected agent's
+            // context window before it ever reaches the provider.
+            let capabilities = self.effective_capabilities(agent.provider(), agent.capabilities());
+            let attempt_request = self.guard_context_window(request.clone(), &capabilities)?;
+            let attempt_timeout_ms = attempt_request.timeout_ms;
+
+            // Mirror a sample of traffic to the shadow target, if
+            // configured, without affecting what's returned to the caller.
+            self.maybe_mirror_shadow_traffic(&attempt_request);
+
+            // Track request
+            let attempt_started_at = Instant::now();
+            let dispatched = DispatchedRequest {
+                agent_provider: agent.provider(),
+                started_at: attempt_started_at,
+                attempt,
+            };
+            self.active_requests.insert(task_id, dispatched);
+
+            // Execute request, enforcing a deadline so a stalled provider
+            // can't hold the permit and tracking entry forever.
+            let timeout_ms = attempt_timeout_ms.unwrap_or_else(|| self.default_timeout_ms.load(Ordering::Relaxed));
+            let provider = agent.provider();
+            let result = match tokio::time::timeout(Duration::from_millis(timeout_ms), agent.submit(attempt_request)).await {
+                Ok(result) => result,
+                Err(_) => Err(Error::Timeout(format!(
+                    "agent {provider:?} did not respond within {timeout_ms}ms (attempt {attempt}, elapsed {}ms)",
+                    attempt_started_at.elapsed().as_millis()
+                ))),
+            };
+
+            // Remove from tracking and record latency regardless of outcome,
+            // so a timed-out permit/entry is always released.
+            self.active_requests.remove(&task_id);
+            let latency_ms = attempt_started_at.elapsed().as_millis() as u64;
+            let task_type = task_type_from_metadata(&request.metadata);
+            self.record_latency(provider, task_type, latency_ms);
+            self.record_cost(
+                provider,
+                task_type,
+                result.as_ref().ok().map(|response| self.cost_for(provider, response.usage.prompt_tokens)),
+            );
+
+            history.push(DispatchAttemptRecord {
+                attempt,
+                provider,
+                latency_ms,
+                outcome: match &result {
+                    Ok(_) => DispatchOutcome::Success,
+                    Err(e) => DispatchOutcome::Failed { error: e.to_string() },
+                },
+            });
+            if history.len() > MAX_DISPATCH_HISTORY {
+                history.remove(0);
             }
-            Err(e) => {
-                warn!("Request {} failed: {}", request_id, e);
-                Err(e)
+
+            match result {
+                Ok(mut response) => {
+                    info!("Request {} completed successfully via {:?} (attempt {})", request_id, agent.provider(), attempt);
+                    response.metadata.insert(
+                        "dispatch_history".to_string(),
+                        serde_json::to_value(&history).unwrap_or(serde_json::Value::Null),
+                    );
+                    return Ok(response);
+                }
+                Err(e) => {
+                    warn!("Request {} failed via {:?} (attempt {}): {}", request_id, agent.provider(), attempt, e);
+                    if e.is_retryable() && excluded_providers.len() < max_attempts as usize {
+                        excluded_providers.push(agent.provider());
+                        last_error = Some(e);
+                        continue;
+                    }
+                    return Err(e);
+                }
             }
         }
     }
     
-    /// Select agent based on strategy
-    async fn select_agent(
+    /// Select agent based on strategy. `pub(crate)` so other orchestrator
+    /// components (e.g. per-request strategy overrides in `lib.rs`) can
+    /// reuse the full strategy set without going through `dispatch`'s
+    /// context-window guard, shadow mirroring, and request tracking.
+    pub(crate) async fn select_agent(
         &self,
         request: &LlmRequest,
         agents: &[Box<dyn Agent>],
         strategy: SelectionStrategy,
+    ) -> Result<&Box<dyn Agent>> {
+        self.select_agent_excluding(request, agents, strategy, &[]).await
+    }
+
+    /// Same as `select_agent`, but never considers a provider in `excluded`.
+    /// Used by `dispatch`'s failover loop to pick a different agent after a
+    /// retryable failure without re-trying the one that just failed.
+    async fn select_agent_excluding(
+        &self,
+        request: &LlmRequest,
+        agents: &[Box<dyn Agent>],
+        strategy: SelectionStrategy,
+        excluded: &[LlmProvider],
     ) -> Result<&Box<dyn Agent>> {
         let available_agents: Vec<_> = futures::future::join_all(
             agents.iter().map(|agent| async {
@@ -97,13 +937,29 @@ impl TaskDispatcher {
             })
         ).await.into_iter()
             .filter_map(|(agent, available)| if available { Some(agent) } else { None })
+            .filter(|agent| !excluded.contains(&agent.provider()))
             .collect();
-        
+
         if available_agents.is_empty() {
             return Err(Error::Agent("No available agents".to_string()));
         }
-        
-        match strategy {
+
+        // Sticky sessions take priority over the configured strategy: once a
+        // session has been routed to a provider, keep sending it there so
+        // follow-up turns can reuse provider-side cached context. If that
+        // provider has since gone unavailable, fall through to the normal
+        // strategy below (which also re-pins the session to whatever it
+        // picks).
+        if let Some(session_id) = &request.session_id {
+            if let Some(sticky_provider) = self.session_affinity.get(session_id).map(|p| *p) {
+                if let Some(agent) = available_agents.iter().find(|agent| agent.provider() == sticky_provider) {
+                    return Ok(*agent);
+                }
+                warn!(session_id, ?sticky_provider, "sticky session's provider is unavailable, re-selecting");
+            }
+        }
+
+        let selected = match strategy {
             SelectionStrategy::RoundRobin => {
                 let count = self.request_counter.fetch_add(1, Ordering::Relaxed);
                 let index = (count as usize) % available_agents.len();
@@ -111,63 +967,282 @@ impl TaskDispatcher {
             }
             
             SelectionStrategy::LowestLatency => {
-                // Get health info for all agents
+                // Pick on cached P50 latency from recent completions
+                // (`record_latency`) instead of calling `health_check` on
+                // every candidate per request. An agent with no samples yet
+                // sorts last rather than winning by default.
                 let mut best_agent = available_agents[0];
-                let mut best_latency = f64::MAX;
-                
-                for agent in available_agents {
-                    if let Ok(health) = agent.health_check().await {
-                        if health.average_latency_ms < best_latency {
-                            best_latency = health.average_latency_ms;
-                            best_agent = agent;
-                        }
+                let mut best_latency = self.latency_stats(best_agent.provider()).map_or(f64::MAX, |s| s.p50_ms);
+
+                for agent in available_agents.iter().skip(1) {
+                    let latency = self.latency_stats(agent.provider()).map_or(f64::MAX, |s| s.p50_ms);
+                    if latency < best_latency {
+                        best_latency = latency;
+                        best_agent = *agent;
                     }
                 }
-                
+
                 Ok(best_agent)
             }
             
             SelectionStrategy::BestMatch => {
-                // Select based on provider preferences
+                // Select based on provider preferences, falling back to the
+                // first available agent if none match exactly.
                 let preferred_provider = request.provider;
-                
-                // Try to find exact match first
-                for agent in &available_agents {
-                    if agent.provider() == preferred_provider {
-                        return Ok(agent);
-                    }
-                }
-                
-                // Fall back to first available
-                Ok(available_agents[0])
+                let agent = available_agents
+                    .iter()
+                    .find(|agent| agent.provider() == preferred_provider)
+                    .copied()
+                    .unwrap_or(available_agents[0]);
+                Ok(agent)
             }
             
             SelectionStrategy::CostOptimized => {
-                // For now, use round-robin (would need cost data)
-                let count = self.request_counter.fetch_add(1, Ordering::Relaxed);
-                let index = (count as usize) % available_agents.len();
-                Ok(available_agents[index])
+                let quality_floor = task_type_from_metadata(&request.metadata)
+                    .and_then(|task_type| self.quality_floors.get(&task_type).map(|floor| *floor))
+                    .unwrap_or(0.0);
+
+                let capable_agents: Vec<_> = available_agents
+                    .into_iter()
+                    .filter(|agent| self.provider_quality(agent.provider()) >= quality_floor)
+                    .collect();
+
+                if capable_agents.is_empty() {
+                    return Err(Error::Agent(
+                        "No available agent meets the configured quality floor for cost-optimized selection".to_string(),
+                    ));
+                }
+
+                let prompt_tokens = estimate_prompt_tokens(&request.prompt);
+                let cheapest = capable_agents
+                    .into_iter()
+                    .min_by(|a, b| {
+                        let cost_a = self.cost_for(a.provider(), prompt_tokens);
+                        let cost_b = self.cost_for(b.provider(), prompt_tokens);
+                        cost_a.partial_cmp(&cost_b).unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .expect("capable_agents is non-empty");
+
+                Ok(cheapest)
             }
             
             SelectionStrategy::Random => {
-                use rand::Rng;
                 let mut rng = rand::thread_rng();
                 let index = rng.gen_range(0..available_agents.len());
                 Ok(available_agents[index])
             }
+
+            SelectionStrategy::Canary => {
+                let task_type = task_type_from_metadata(&request.metadata);
+                match self.canary_router.choose_provider(task_type) {
+                    Some(provider) => {
+                        let agent = available_agents
+                            .iter()
+                            .find(|agent| agent.provider() == provider)
+                            .copied()
+                            .unwrap_or(available_agents[0]);
+                        self.canary_router.record_selection(task_type, agent.provider());
+                        Ok(agent)
+                    }
+                    // No route configured for this task type (or at all):
+                    // fall back to round-robin instead of always hitting
+                    // the same first agent.
+                    None => {
+                        let count = self.request_counter.fetch_add(1, Ordering::Relaxed);
+                        let index = (count as usize) % available_agents.len();
+                        Ok(available_agents[index])
+                    }
+                }
+            }
+
+            SelectionStrategy::RuleBased => {
+                let task_type = task_type_from_metadata(&request.metadata);
+                match self.routing_rules.evaluate(request, task_type) {
+                    Some(decision) => {
+                        let capable_agents: Vec<_> = available_agents
+                            .iter()
+                            .filter(|agent| {
+                                let capabilities = self.effective_capabilities(agent.provider(), agent.capabilities());
+                                decision.required_capabilities.iter().all(|cap| capability_satisfied(&capabilities, cap))
+                            })
+                            .copied()
+                            .collect();
+
+                        if capable_agents.is_empty() {
+                            return Err(Error::Agent(
+                                "No available agent meets the matched routing rule's required capabilities".to_string(),
+                            ));
+                        }
+
+                        let agent = capable_agents
+                            .iter()
+                            .find(|agent| agent.provider() == decision.preferred_provider)
+                            .copied()
+                            .unwrap_or(capable_agents[0]);
+                        Ok(agent)
+                    }
+                    // No rule matched: fall back to round-robin, same as
+                    // Canary's unconfigured-route fallback above.
+                    None => {
+                        let count = self.request_counter.fetch_add(1, Ordering::Relaxed);
+                        let index = (count as usize) % available_agents.len();
+                        Ok(available_agents[index])
+                    }
+                }
+            }
+
+            SelectionStrategy::AbTest => {
+                let experiment = ab_test_name_from_metadata(&request.metadata)
+                    .and_then(|name| self.ab_test_engine.get(&name));
+                match experiment {
+                    Some(experiment) => {
+                        let provider = experiment.provider_for(request.task_id);
+                        let agent = available_agents
+                            .iter()
+                            .find(|agent| agent.provider() == provider)
+                            .copied()
+                            .unwrap_or(available_agents[0]);
+                        Ok(agent)
+                    }
+                    // No experiment named by this request (or it's already
+                    // concluded): fall back to round-robin, same as Canary's
+                    // and RuleBased's unconfigured fallback above.
+                    None => {
+                        let count = self.request_counter.fetch_add(1, Ordering::Relaxed);
+                        let index = (count as usize) % available_agents.len();
+                        Ok(available_agents[index])
+                    }
+                }
+            }
+        };
+
+        if let (Some(session_id), Ok(agent)) = (&request.session_id, &selected) {
+            self.session_affinity.insert(session_id.clone(), agent.provider());
+        }
+
+        selected
+    }
+
+    /// Record one completed dispatch attempt's latency for `provider`,
+    /// dropping the oldest sample once more than `LATENCY_SAMPLE_CAP` have
+    /// accumulated, and fold it into the finer-grained `(provider, task
+    /// type)` histogram backing `latency_percentile`/`latency_burn_rate`.
+    /// `task_type` is `None` when the request's metadata didn't carry one
+    /// (see `task_type_from_metadata`); such samples still count toward
+    /// `latency_samples`'s provider-only P50/P90 but aren't attributed to
+    /// any histogram.
+    fn record_latency(&self, provider: LlmProvider, task_type: Option<TaskType>, latency_ms: u64) {
+        let samples = self.latency_samples.entry(provider).or_insert_with(|| Mutex::new(VecDeque::new()));
+        let mut samples = samples.lock();
+        samples.push_back(latency_ms);
+        if samples.len() > LATENCY_SAMPLE_CAP {
+            samples.pop_front();
+        }
+        drop(samples);
+
+        if let Some(task_type) = task_type {
+            self.latency_histograms
+                .entry((provider, task_type))
+                .or_insert_with(|| Mutex::new(LatencyHistogram::new()))
+                .lock()
+                .record(latency_ms);
         }
     }
-    
+
+    /// Estimated `p`-th percentile (`p` in `[0.0, 1.0]`) latency in
+    /// milliseconds for `(provider, task_type)`, or `None` if no dispatch
+    /// for that pair has completed yet.
+    pub fn latency_percentile(&self, provider: LlmProvider, task_type: TaskType, p: f64) -> Option<f64> {
+        self.latency_histograms.get(&(provider, task_type))?.lock().percentile(p)
+    }
+
+    /// SLO burn-rate for `(provider, task_type)` against `slo`: the fraction
+    /// of recent dispatches exceeding `slo.target_ms`, divided by the error
+    /// budget `slo` allows. `0.0` if no dispatch for that pair has completed
+    /// yet. See `LatencyHistogram::burn_rate` for how to read the result.
+    pub fn latency_burn_rate(&self, provider: LlmProvider, task_type: TaskType, slo: LatencySlo) -> f64 {
+        self.latency_histograms
+            .get(&(provider, task_type))
+            .map(|histogram| histogram.lock().burn_rate(&slo))
+            .unwrap_or(0.0)
+    }
+
+    /// Fill in `EvaluationMetrics.p95_latency_ms`/`p99_latency_ms` for
+    /// `(provider, task_type)` from the `LatencyHistogram` - the one part of
+    /// that struct this dispatcher has the data for.
+    /// `total_requests`/`accuracy`/etc need an `Evaluator` lookup this
+    /// dispatcher doesn't have, so a caller after the full struct merges
+    /// this into one sourced from `MetaEvaluator`.
+    pub fn latency_evaluation_metrics(&self, provider: LlmProvider, task_type: TaskType) -> EvaluationMetrics {
+        let mut metrics = EvaluationMetrics::default();
+        if let Some(histogram) = self.latency_histograms.get(&(provider, task_type)) {
+            let histogram = histogram.lock();
+            metrics.p95_latency_ms = histogram.percentile(0.95).unwrap_or_default();
+            metrics.p99_latency_ms = histogram.percentile(0.99).unwrap_or_default();
+        }
+        metrics
+    }
+
+    /// Fold one completed dispatch attempt's cost into `(provider,
+    /// task_type)`'s running totals, backing `cost_evaluation_metrics`.
+    /// `cost` is `None` for a failed attempt (no tokens were billed for it),
+    /// `Some(estimated_cost(...))` for a successful one. `task_type` is
+    /// `None` when the request's metadata didn't carry one, in which case
+    /// the attempt isn't attributed to any total.
+    fn record_cost(&self, provider: LlmProvider, task_type: Option<TaskType>, cost: Option<f64>) {
+        let Some(task_type) = task_type else { return };
+        let mut totals =
+            self.cost_totals.entry((provider, task_type)).or_insert_with(|| Mutex::new(CostAccumulator::default())).lock();
+        totals.total_requests += 1;
+        if let Some(cost) = cost {
+            totals.successful_requests += 1;
+            totals.total_cost += cost;
+        }
+    }
+
+    /// Fill in `EvaluationMetrics.cost_per_request`/
+    /// `cost_per_successful_task` for `(provider, task_type)` from the
+    /// running cost totals `record_cost` has accumulated - the other part of
+    /// that struct this dispatcher has the data for (see
+    /// `latency_evaluation_metrics` for the latency half).
+    pub fn cost_evaluation_metrics(&self, provider: LlmProvider, task_type: TaskType) -> EvaluationMetrics {
+        let mut metrics = EvaluationMetrics::default();
+        if let Some(totals) = self.cost_totals.get(&(provider, task_type)) {
+            let totals = totals.lock();
+            if totals.total_requests > 0 {
+                metrics.cost_per_request = totals.total_cost / totals.total_requests as f64;
+            }
+            if totals.successful_requests > 0 {
+                metrics.cost_per_successful_task = totals.total_cost / totals.successful_requests as f64;
+            }
+        }
+        metrics
+    }
+
+    /// Cached P50/P90 latency for `provider` from its recent dispatch
+    /// attempts, or `None` if none have completed yet.
+    pub fn latency_stats(&self, provider: LlmProvider) -> Option<LatencyStats> {
+        let mut sorted: Vec<u64> = self.latency_samples.get(&provider)?.lock().iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+
+        let percentile = |p: f64| sorted[(((sorted.len() - 1) as f64) * p).round() as usize] as f64;
+        Some(LatencyStats { p50_ms: percentile(0.5), p90_ms: percentile(0.9), sample_count: sorted.len() })
+    }
+
     /// Get current dispatch statistics
     pub fn get_stats(&self) -> DispatchStats {
         let active_count = self.active_requests.len();
-        let available_permits = self.semaphore.available_permits();
-        
+        let available_permits = self.semaphore.available_permits() + self.priority_semaphore.available_permits();
+
         DispatchStats {
             active_requests: active_count,
             available_capacity: available_permits,
             total_capacity: self.max_concurrent,
             utilization: (self.max_concurrent - available_permits) as f64 / self.max_concurrent as f64,
+            provider_in_flight: self.provider_in_flight(),
         }
     }
 }
@@ -179,6 +1254,9 @@ pub struct DispatchStats {
     pub available_capacity: usize,
     pub total_capacity: usize,
     pub utilization: f64,
+    /// In-flight request count per provider bulkhead, so an operator can
+    /// tell a single stalled provider apart from genuine global saturation.
+    pub provider_in_flight: HashMap<LlmProvider, usize>,
 }
 
 /// Load balancer for multiple dispatchers
@@ -208,7 +1286,14 @@ impl LoadBalancer {
             .min_by_key(|d| d.get_stats().active_requests)
             .unwrap_or(&self.dispatchers[0])
     }
-    
+
+    /// All dispatcher shards, e.g. to apply a config change (routing rules,
+    /// shadow target) uniformly across every shard.
+    pub fn dispatchers(&self) -> &[Arc<TaskDispatcher>] {
+        &self.dispatchers
+    }
+
+
     /// Get overall stats
     pub fn get_overall_stats(&self) -> LoadBalancerStats {
         let stats: Vec<_> = self.dispatchers.iter().map(|d| d.get_stats()).collect();
@@ -269,8 +1354,618 @@ mod tests {
         assert_eq!(stats.available_capacity, 10);
     }
     
+    #[tokio::test]
+    async fn test_dispatch_fails_over_to_next_agent_on_retryable_error() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut flaky = MockTestAgent::new();
+        flaky.expect_provider().return_const(LlmProvider::Claude);
+        flaky.expect_is_available().returning(|| true);
+        flaky.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        flaky.expect_submit().returning(|_| Err(Error::Timeout("provider unavailable".to_string())));
+
+        let mut healthy = MockTestAgent::new();
+        healthy.expect_provider().return_const(LlmProvider::OpenAI);
+        healthy.expect_is_available().returning(|| true);
+        healthy.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        healthy.expect_submit().returning(|request| {
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "served by fallback".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 1,
+                provider: LlmProvider::OpenAI,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(flaky), Box::new(healthy)];
+        let request = test_request("hello", Metadata::new());
+
+        let response = dispatcher.dispatch(request, &agents, SelectionStrategy::BestMatch).await.unwrap();
+        assert_eq!(response.provider, LlmProvider::OpenAI);
+        assert_eq!(response.content, "served by fallback");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_gives_up_after_max_failover_attempts() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_max_failover_attempts(0);
+
+        let mut flaky = MockTestAgent::new();
+        flaky.expect_provider().return_const(LlmProvider::Claude);
+        flaky.expect_is_available().returning(|| true);
+        flaky.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        flaky.expect_submit().returning(|_| Err(Error::Timeout("provider unavailable".to_string())));
+
+        let mut healthy = MockTestAgent::new();
+        healthy.expect_provider().return_const(LlmProvider::OpenAI);
+        healthy.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(flaky), Box::new(healthy)];
+        let request = test_request("hello", Metadata::new());
+
+        let result = dispatcher.dispatch(request, &agents, SelectionStrategy::BestMatch).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_agent_selection() {
         // Test implementation would go here
     }
+
+    fn test_request(prompt: &str, metadata: Metadata) -> LlmRequest {
+        LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider: LlmProvider::Claude,
+            prompt: prompt.to_string(),
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: vec![],
+            metadata,
+            session_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cost_optimized_selects_cheapest_available_agent() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut expensive = MockTestAgent::new();
+        expensive.expect_provider().return_const(LlmProvider::Claude);
+        expensive.expect_is_available().returning(|| true);
+
+        let mut cheap = MockTestAgent::new();
+        cheap.expect_provider().return_const(LlmProvider::CodeWhisperer);
+        cheap.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(expensive), Box::new(cheap)];
+        let request = test_request(&"x".repeat(400), Metadata::new());
+
+        let agent = dispatcher
+            .select_agent(&request, &agents, SelectionStrategy::CostOptimized)
+            .await
+            .unwrap();
+        assert_eq!(agent.provider(), LlmProvider::CodeWhisperer);
+    }
+
+    #[tokio::test]
+    async fn test_cost_optimized_respects_quality_floor() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_quality_floor(meta_ai_core::agent::TaskType::CodeGeneration, 0.9);
+
+        let mut expensive = MockTestAgent::new();
+        expensive.expect_provider().return_const(LlmProvider::Claude);
+        expensive.expect_is_available().returning(|| true);
+
+        let mut cheap = MockTestAgent::new();
+        cheap.expect_provider().return_const(LlmProvider::CodeWhisperer);
+        cheap.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(expensive), Box::new(cheap)];
+        let mut metadata = Metadata::new();
+        metadata.insert("task_type".to_string(), serde_json::json!("code_generation"));
+        let request = test_request(&"x".repeat(400), metadata);
+
+        // The cheapest agent (CodeWhisperer, quality 0.70) doesn't meet the
+        // 0.9 floor configured for code generation, so Claude wins despite
+        // costing more.
+        let agent = dispatcher
+            .select_agent(&request, &agents, SelectionStrategy::CostOptimized)
+            .await
+            .unwrap();
+        assert_eq!(agent.provider(), LlmProvider::Claude);
+    }
+
+    fn small_capabilities() -> meta_ai_core::agent::AgentCapabilities {
+        meta_ai_core::agent::AgentCapabilities {
+            max_tokens: 100,
+            context_window: 200,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_guard_context_window_passes_through_when_within_budget() {
+        let dispatcher = TaskDispatcher::new(10);
+        let request = test_request("short prompt", Metadata::new());
+        let guarded = dispatcher.guard_context_window(request.clone(), &small_capabilities()).unwrap();
+        assert_eq!(guarded.prompt, request.prompt);
+    }
+
+    #[test]
+    fn test_guard_context_window_rejects_when_configured() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_truncation_strategy(TruncationStrategy::Reject);
+        let request = test_request(&"x".repeat(2000), Metadata::new());
+        assert!(dispatcher.guard_context_window(request, &small_capabilities()).is_err());
+    }
+
+    #[test]
+    fn test_guard_context_window_truncates_oldest() {
+        let dispatcher = TaskDispatcher::new(10);
+        let prompt = format!("{}\n\n{}\n\n{}", "old context".repeat(50), "middle context".repeat(50), "latest question");
+        let request = test_request(&prompt, Metadata::new());
+
+        let guarded = dispatcher.guard_context_window(request, &small_capabilities()).unwrap();
+        assert!(guarded.prompt.len() < prompt.len());
+        assert!(guarded.prompt.ends_with("latest question"));
+    }
+
+    #[test]
+    fn test_truncate_middle_out_keeps_head_and_tail() {
+        let paragraphs = vec!["system instructions", "turn one", "turn two", "turn three", "latest question"];
+        let prompt = paragraphs.join("\n\n");
+
+        let truncated = truncate_middle_out(&prompt, 60);
+        assert!(truncated.starts_with("system instructions"));
+        assert!(truncated.ends_with("latest question"));
+        assert!(truncated.contains(TRUNCATION_MARKER));
+    }
+
+    #[tokio::test]
+    async fn test_canary_routing_sends_all_traffic_to_configured_arm() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_canary_route(None, vec![RoutingArm::new(LlmProvider::OpenAI, 1)]);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let mut openai = MockTestAgent::new();
+        openai.expect_provider().return_const(LlmProvider::OpenAI);
+        openai.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude), Box::new(openai)];
+        let request = test_request("hello", Metadata::new());
+
+        for _ in 0..5 {
+            let agent =
+                dispatcher.select_agent(&request, &agents, SelectionStrategy::Canary).await.unwrap();
+            assert_eq!(agent.provider(), LlmProvider::OpenAI);
+        }
+
+        let stats = dispatcher.canary_stats();
+        let openai_selections: u64 = stats
+            .iter()
+            .filter(|s| s.provider == LlmProvider::OpenAI)
+            .map(|s| s.selections)
+            .sum();
+        assert_eq!(openai_selections, 5);
+    }
+
+    #[tokio::test]
+    async fn test_canary_routing_falls_back_to_round_robin_when_unconfigured() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude)];
+        let request = test_request("hello", Metadata::new());
+
+        let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::Canary).await.unwrap();
+        assert_eq!(agent.provider(), LlmProvider::Claude);
+        assert!(dispatcher.canary_stats().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shadow_traffic_mirrors_and_records_without_affecting_primary() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut shadow_agent = MockTestAgent::new();
+        shadow_agent.expect_provider().return_const(LlmProvider::OpenAI);
+        shadow_agent.expect_submit().returning(|request| {
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "shadow response".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 1,
+                provider: LlmProvider::OpenAI,
+                metadata: Metadata::new(),
+            })
+        });
+        let shadow_agent: Arc<dyn Agent> = Arc::new(shadow_agent);
+
+        dispatcher.set_shadow_target(shadow_agent, None, 1.0);
+
+        let request = test_request("hello", Metadata::new());
+        let request_id = request.id;
+        dispatcher.maybe_mirror_shadow_traffic(&request);
+
+        // The mirrored call runs on a spawned task; give it a moment to land.
+        for _ in 0..50 {
+            if !dispatcher.shadow_results().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let results = dispatcher.shadow_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].request_id, request_id);
+        assert_eq!(results[0].shadow_provider, LlmProvider::OpenAI);
+        assert_eq!(results[0].response.as_ref().unwrap().content, "shadow response");
+    }
+
+    #[tokio::test]
+    async fn test_sticky_session_stays_on_same_provider() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let mut openai = MockTestAgent::new();
+        openai.expect_provider().return_const(LlmProvider::OpenAI);
+        openai.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude), Box::new(openai)];
+
+        let mut request = test_request("turn one", Metadata::new());
+        request.session_id = Some("session-1".to_string());
+
+        let first = dispatcher.select_agent(&request, &agents, SelectionStrategy::RoundRobin).await.unwrap();
+        let first_provider = first.provider();
+
+        for _ in 0..5 {
+            let mut request = test_request("follow-up turn", Metadata::new());
+            request.session_id = Some("session-1".to_string());
+            let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::RoundRobin).await.unwrap();
+            assert_eq!(agent.provider(), first_provider);
+        }
+    }
+
+    #[test]
+    fn test_selection_strategy_from_metadata_parses_known_values() {
+        let mut metadata = Metadata::new();
+        metadata.insert("selection_strategy".to_string(), serde_json::json!("cost_optimized"));
+        assert!(matches!(selection_strategy_from_metadata(&metadata), Some(SelectionStrategy::CostOptimized)));
+
+        let mut metadata = Metadata::new();
+        metadata.insert("selection_strategy".to_string(), serde_json::json!("not_a_strategy"));
+        assert!(selection_strategy_from_metadata(&metadata).is_none());
+
+        assert!(selection_strategy_from_metadata(&Metadata::new()).is_none());
+    }
+
+    #[test]
+    fn test_pinned_provider_from_metadata_parses_known_values() {
+        let mut metadata = Metadata::new();
+        metadata.insert("pinned_provider".to_string(), serde_json::json!("codewhisperer"));
+        assert_eq!(pinned_provider_from_metadata(&metadata), Some(LlmProvider::CodeWhisperer));
+        assert_eq!(pinned_provider_from_metadata(&Metadata::new()), None);
+    }
+
+    #[tokio::test]
+    async fn test_sticky_session_reroutes_when_provider_unavailable() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        dispatcher.session_affinity.insert("session-1".to_string(), LlmProvider::OpenAI);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        // Only Claude is available; OpenAI (the sticky pin) is not in the
+        // agent list at all, simulating it having gone unavailable.
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude)];
+
+        let mut request = test_request("hello", Metadata::new());
+        request.session_id = Some("session-1".to_string());
+
+        let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::RoundRobin).await.unwrap();
+        assert_eq!(agent.provider(), LlmProvider::Claude);
+    }
+
+    #[tokio::test]
+    async fn test_shadow_traffic_respects_zero_sample_rate() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut shadow_agent = MockTestAgent::new();
+        shadow_agent.expect_provider().return_const(LlmProvider::OpenAI);
+        let shadow_agent: Arc<dyn Agent> = Arc::new(shadow_agent);
+
+        dispatcher.set_shadow_target(shadow_agent, None, 0.0);
+
+        let request = test_request("hello", Metadata::new());
+        dispatcher.maybe_mirror_shadow_traffic(&request);
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(dispatcher.shadow_results().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_provider_bulkhead_limits_concurrency_independently() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_provider_bulkhead_limit(LlmProvider::OpenAI, 1);
+
+        let openai_permit = dispatcher.bulkhead_semaphore(LlmProvider::OpenAI).acquire_owned().await.unwrap();
+        // Claude's bulkhead is independent of OpenAI's, so acquiring it must
+        // not block even though OpenAI's single permit is held.
+        let claude_permit = tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            dispatcher.bulkhead_semaphore(LlmProvider::Claude).acquire_owned(),
+        )
+        .await;
+        assert!(claude_permit.is_ok());
+
+        let in_flight = dispatcher.provider_in_flight();
+        assert_eq!(in_flight[&LlmProvider::OpenAI], 1);
+        assert_eq!(in_flight[&LlmProvider::Claude], 1);
+
+        drop(openai_permit);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_reports_provider_in_flight_in_stats() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut agent = MockTestAgent::new();
+        agent.expect_provider().return_const(LlmProvider::Claude);
+        agent.expect_is_available().returning(|| true);
+        agent.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        agent.expect_submit().returning(|request| {
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "ok".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 1,
+                provider: LlmProvider::Claude,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(agent)];
+        let request = test_request("hello", Metadata::new());
+        dispatcher.dispatch(request, &agents, SelectionStrategy::BestMatch).await.unwrap();
+
+        let stats = dispatcher.get_stats();
+        assert_eq!(stats.provider_in_flight[&LlmProvider::Claude], 0);
+    }
+
+    #[tokio::test]
+    async fn test_lowest_latency_selects_cached_p50_without_health_check() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.record_latency(LlmProvider::Claude, None, 500);
+        dispatcher.record_latency(LlmProvider::OpenAI, None, 50);
+
+        // Neither mock has `expect_health_check` configured, so a call to it
+        // would panic: this asserts the cached-latency path never calls it.
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let mut openai = MockTestAgent::new();
+        openai.expect_provider().return_const(LlmProvider::OpenAI);
+        openai.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude), Box::new(openai)];
+        let request = test_request("hello", Metadata::new());
+
+        let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::LowestLatency).await.unwrap();
+        assert_eq!(agent.provider(), LlmProvider::OpenAI);
+    }
+
+    #[test]
+    fn test_latency_stats_computes_percentiles() {
+        let dispatcher = TaskDispatcher::new(10);
+        for ms in [10, 20, 30, 40, 50] {
+            dispatcher.record_latency(LlmProvider::Claude, Some(TaskType::Reasoning), ms);
+        }
+
+        let stats = dispatcher.latency_stats(LlmProvider::Claude).unwrap();
+        assert_eq!(stats.sample_count, 5);
+        assert_eq!(stats.p50_ms, 30.0);
+        assert_eq!(stats.p90_ms, 50.0);
+
+        assert!(dispatcher.latency_stats(LlmProvider::OpenAI).is_none());
+
+        let p99 = dispatcher.latency_percentile(LlmProvider::Claude, TaskType::Reasoning, 0.99).unwrap();
+        assert!(p99 >= 50.0);
+        assert!(dispatcher.latency_percentile(LlmProvider::Claude, TaskType::CodeGeneration, 0.99).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rule_based_selects_matched_rule_preferred_provider() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_routing_rules(vec![meta_ai_core::agent::RoutingRule {
+            pattern: "legal contract".to_string(),
+            preferred_provider: LlmProvider::Claude,
+            required_capabilities: vec![],
+            priority: 0,
+        }]);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let mut openai = MockTestAgent::new();
+        openai.expect_provider().return_const(LlmProvider::OpenAI);
+        openai.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(openai), Box::new(claude)];
+        let request = test_request("please review this legal contract", Metadata::new());
+
+        let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::RuleBased).await.unwrap();
+        assert_eq!(agent.provider(), LlmProvider::Claude);
+    }
+
+    #[tokio::test]
+    async fn test_rule_based_falls_back_to_round_robin_when_unmatched() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut claude = MockTestAgent::new();
+        claude.expect_provider().return_const(LlmProvider::Claude);
+        claude.expect_is_available().returning(|| true);
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(claude)];
+        let request = test_request("hello", Metadata::new());
+
+        let agent = dispatcher.select_agent(&request, &agents, SelectionStrategy::RuleBased).await.unwrap();
+        assert_eq!(agent.provider(), LlmProvider::Claude);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_dispatch_times_out_and_releases_permit_and_tracking() {
+        let dispatcher = TaskDispatcher::new(10);
+        dispatcher.set_max_failover_attempts(0);
+
+        let mut slow = MockTestAgent::new();
+        slow.expect_provider().return_const(LlmProvider::Claude);
+        slow.expect_is_available().returning(|| true);
+        slow.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        slow.expect_submit().returning(|request| {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "too slow".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 100,
+                provider: LlmProvider::Claude,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(slow)];
+        let mut request = test_request("hello", Metadata::new());
+        request.timeout_ms = Some(10);
+
+        let result = dispatcher.dispatch(request, &agents, SelectionStrategy::BestMatch).await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+
+        let stats = dispatcher.get_stats();
+        assert_eq!(stats.active_requests, 0);
+        assert_eq!(stats.available_capacity, stats.total_capacity);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_attaches_history_with_failed_and_successful_attempts() {
+        let dispatcher = TaskDispatcher::new(10);
+
+        let mut flaky = MockTestAgent::new();
+        flaky.expect_provider().return_const(LlmProvider::Claude);
+        flaky.expect_is_available().returning(|| true);
+        flaky.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        flaky.expect_submit().returning(|_| Err(Error::Timeout("provider unavailable".to_string())));
+
+        let mut healthy = MockTestAgent::new();
+        healthy.expect_provider().return_const(LlmProvider::OpenAI);
+        healthy.expect_is_available().returning(|| true);
+        healthy.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        healthy.expect_submit().returning(|request| {
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "served by fallback".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 1,
+                provider: LlmProvider::OpenAI,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(flaky), Box::new(healthy)];
+        let request = test_request("hello", Metadata::new());
+
+        let response = dispatcher.dispatch(request, &agents, SelectionStrategy::BestMatch).await.unwrap();
+        let history = response.metadata.get("dispatch_history").unwrap().as_array().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0]["provider"], serde_json::to_value(LlmProvider::Claude).unwrap());
+        assert_eq!(history[0]["outcome"]["failed"]["error"], serde_json::json!("Operation timed out: provider unavailable"));
+        assert_eq!(history[1]["provider"], serde_json::to_value(LlmProvider::OpenAI).unwrap());
+        assert_eq!(history[1]["outcome"], serde_json::json!("success"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_dispatch_returns_overloaded_when_queue_acquire_times_out() {
+        let dispatcher = Arc::new(TaskDispatcher::new(1));
+        dispatcher.set_queue_acquire_timeout_ms(10);
+
+        let mut slow = MockTestAgent::new();
+        slow.expect_provider().return_const(LlmProvider::Claude);
+        slow.expect_is_available().returning(|| true);
+        slow.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        slow.expect_submit().returning(|request| {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "slow".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 200,
+                provider: LlmProvider::Claude,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Arc<Vec<Box<dyn Agent>>> = Arc::new(vec![Box::new(slow)]);
+
+        let holder = {
+            let dispatcher = Arc::clone(&dispatcher);
+            let agents = Arc::clone(&agents);
+            tokio::spawn(async move {
+                dispatcher.dispatch(test_request("hello", Metadata::new()), &agents, SelectionStrategy::BestMatch).await
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let result = dispatcher.dispatch(test_request("hello", Metadata::new()), &agents, SelectionStrategy::BestMatch).await;
+        assert!(matches!(result, Err(Error::Overloaded { queue_position: 1, capacity: 1 })));
+
+        holder.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_request_draws_on_reserved_pool_when_main_pool_saturated() {
+        let dispatcher = TaskDispatcher::new(10); // reserved_priority_permits = 1, main pool = 9
+        dispatcher.set_queue_acquire_timeout_ms(10);
+
+        let _held: Vec<_> = (0..9).map(|_| Arc::clone(&dispatcher.semaphore).try_acquire_owned().unwrap()).collect();
+
+        let mut healthy = MockTestAgent::new();
+        healthy.expect_provider().return_const(LlmProvider::Claude);
+        healthy.expect_is_available().returning(|| true);
+        healthy.expect_capabilities().returning(meta_ai_core::agent::AgentCapabilities::default);
+        healthy.expect_submit().returning(|request| {
+            Ok(LlmResponse {
+                request_id: request.id,
+                content: "ok".to_string(),
+                usage: TokenUsage::default(),
+                latency_ms: 1,
+                provider: LlmProvider::Claude,
+                metadata: Metadata::new(),
+            })
+        });
+
+        let agents: Vec<Box<dyn Agent>> = vec![Box::new(healthy)];
+
+        let mut high_priority = Metadata::new();
+        high_priority.insert("priority".to_string(), serde_json::json!("high"));
+        let result = dispatcher.dispatch(test_request("hello", high_priority), &agents, SelectionStrategy::BestMatch).await;
+        assert!(result.is_ok(), "high priority request should draw on the reserved pool: {result:?}");
+
+        let result = dispatcher.dispatch(test_request("hello", Metadata::new()), &agents, SelectionStrategy::BestMatch).await;
+        assert!(matches!(result, Err(Error::Overloaded { .. })), "medium priority request must not draw on the reserved pool");
+    }
 }
\ No newline at end of file
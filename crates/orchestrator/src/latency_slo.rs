@@ -0,0 +1,177 @@
+//! Bounded-memory latency histogram per `(LlmProvider, TaskType)` pair, real
+//! percentile computation, and SLO burn-rate derived from it. No
+//! `hdrhistogram` crate exists anywhere in this workspace, so this is a
+//! hand-rolled log-linear bucketed histogram in the same spirit (roughly
+//! constant relative resolution, bounded memory) rather than a literal port
+//! of that crate — the same "cheap proxy over the real algorithm" tradeoff
+//! `drift`'s population-stability-index and `groundedness`'s token-overlap
+//! scoring already make.
+
+use std::collections::VecDeque;
+
+/// Smallest latency (ms) the histogram resolves; anything below is folded
+/// into the first bucket.
+const MIN_MS: u64 = 1;
+/// Number of octaves (power-of-two doublings from `MIN_MS`) tracked, so the
+/// histogram covers up to `MIN_MS << OCTAVES` ms (~131 seconds) before
+/// everything above overflows into the last bucket.
+const OCTAVES: usize = 17;
+/// Linear subdivisions per octave. Higher means finer resolution at the
+/// cost of more buckets; 16 gives roughly +/-3% relative error per bucket,
+/// plenty for SLO-grade percentiles.
+const SUB_BUCKETS: usize = 16;
+const BUCKET_COUNT: usize = OCTAVES * SUB_BUCKETS;
+/// How many recent samples feed the histogram before the oldest is evicted,
+/// mirroring `TaskDispatcher::LATENCY_SAMPLE_CAP` and `OutcomeTracker`'s
+/// `WINDOW_SIZE` rolling-window convention.
+const WINDOW_SIZE: usize = 2000;
+
+fn bucket_index(latency_ms: u64) -> usize {
+    let value = latency_ms.max(MIN_MS);
+    let octave = (value / MIN_MS).ilog2() as usize;
+    if octave >= OCTAVES {
+        return BUCKET_COUNT - 1;
+    }
+    let octave_start = MIN_MS << octave;
+    let octave_end = octave_start << 1;
+    let position = ((value - octave_start) as f64 / (octave_end - octave_start) as f64 * SUB_BUCKETS as f64) as usize;
+    octave * SUB_BUCKETS + position.min(SUB_BUCKETS - 1)
+}
+
+fn bucket_upper_bound_ms(index: usize) -> f64 {
+    let octave = index / SUB_BUCKETS;
+    let position = index % SUB_BUCKETS;
+    let octave_start = (MIN_MS << octave) as f64;
+    let octave_end = (MIN_MS << (octave + 1)) as f64;
+    let width = (octave_end - octave_start) / SUB_BUCKETS as f64;
+    octave_start + width * (position + 1) as f64
+}
+
+/// A latency objective: no more than `1.0 - target_percentile` of requests
+/// should exceed `target_ms`. Fed to `LatencyHistogram::burn_rate`.
+#[derive(Debug, Clone, Copy)]
+pub struct LatencySlo {
+    pub target_ms: u64,
+    pub target_percentile: f64,
+}
+
+/// Rolling, bounded-memory latency histogram for one `(provider, task type)`
+/// pair. Backs `TaskDispatcher::latency_percentile`/`latency_burn_rate`.
+pub struct LatencyHistogram {
+    window: VecDeque<usize>,
+    counts: Vec<u64>,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self { window: VecDeque::with_capacity(WINDOW_SIZE), counts: vec![0; BUCKET_COUNT] }
+    }
+
+    /// Record one observed latency, dropping the oldest sample once more
+    /// than `WINDOW_SIZE` have accumulated.
+    pub fn record(&mut self, latency_ms: u64) {
+        let index = bucket_index(latency_ms);
+        if self.window.len() == WINDOW_SIZE {
+            if let Some(evicted) = self.window.pop_front() {
+                self.counts[evicted] -= 1;
+            }
+        }
+        self.window.push_back(index);
+        self.counts[index] += 1;
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Estimated `p`-th percentile (`p` in `[0.0, 1.0]`) latency in
+    /// milliseconds, taken from the bucket whose cumulative count first
+    /// reaches `p * sample_count`. `None` if no samples have been recorded.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        let total = self.window.len();
+        if total == 0 {
+            return None;
+        }
+        let target = (p * total as f64).ceil().max(1.0) as u64;
+        let mut cumulative = 0u64;
+        for (index, &count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Some(bucket_upper_bound_ms(index));
+            }
+        }
+        Some(bucket_upper_bound_ms(BUCKET_COUNT - 1))
+    }
+
+    /// Fraction of recorded samples whose bucket's range reaches
+    /// `threshold_ms`, i.e. breaching it. Slightly conservative (a sample
+    /// just under `threshold_ms` but sharing its bucket with one just over
+    /// still counts as breaching) the same way every bucketed-histogram
+    /// percentile estimate here trades exactness for bounded memory.
+    fn breach_fraction(&self, threshold_ms: u64) -> f64 {
+        let total = self.window.len();
+        if total == 0 {
+            return 0.0;
+        }
+        let threshold_index = bucket_index(threshold_ms);
+        let breaching: u64 = self.counts[threshold_index..].iter().sum();
+        breaching as f64 / total as f64
+    }
+
+    /// SLO burn-rate: the observed fraction of requests breaching
+    /// `slo.target_ms`, divided by the error budget `slo` allows
+    /// (`1.0 - slo.target_percentile`). `1.0` means burning the budget
+    /// exactly as fast as the SLO's window allows; above `1.0` means the
+    /// budget will be exhausted early, mirroring the multi-window
+    /// burn-rate alerting SRE practice uses for error budgets.
+    pub fn burn_rate(&self, slo: &LatencySlo) -> f64 {
+        let budget = (1.0 - slo.target_percentile).max(f64::EPSILON);
+        self.breach_fraction(slo.target_ms) / budget
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_tracks_uniform_samples() {
+        let mut histogram = LatencyHistogram::new();
+        for ms in 1..=1000u64 {
+            histogram.record(ms);
+        }
+        let p50 = histogram.percentile(0.5).unwrap();
+        let p99 = histogram.percentile(0.99).unwrap();
+        assert!((450.0..=560.0).contains(&p50), "p50 was {p50}");
+        assert!((980.0..=1050.0).contains(&p99), "p99 was {p99}");
+    }
+
+    #[test]
+    fn burn_rate_above_one_when_slo_breached() {
+        let mut histogram = LatencyHistogram::new();
+        for _ in 0..80 {
+            histogram.record(100);
+        }
+        for _ in 0..20 {
+            histogram.record(5000);
+        }
+        let slo = LatencySlo { target_ms: 1000, target_percentile: 0.95 };
+        // 20% of requests breach a target that should hold for 95%, i.e. a
+        // 5% budget - well over twice the allowed rate.
+        assert!(histogram.burn_rate(&slo) > 2.0);
+    }
+
+    #[test]
+    fn empty_histogram_has_no_percentile_and_zero_burn_rate() {
+        let histogram = LatencyHistogram::new();
+        assert!(histogram.percentile(0.5).is_none());
+        let slo = LatencySlo { target_ms: 1000, target_percentile: 0.95 };
+        assert_eq!(histogram.burn_rate(&slo), 0.0);
+    }
+}
@@ -8,44 +8,112 @@ use async_trait::async_trait;
 use dashmap::DashMap;
 use meta_ai_common::{
     error::{Error, Result},
-    types::{LlmRequest, LlmResponse, Task, TaskId, TaskStatus, Priority},
+    types::{LlmRequest, LlmResponse, Task, TaskId, TaskStatus, Priority, LlmProvider, Embedding},
     metrics::{MetricsCollector, DefaultMetricsCollector},
 };
+use meta_ai_eval::{reconcile, ReconciliationStrategy};
 use meta_ai_core::{
-    agent::{Agent, AgentSelector, SelectionStrategy},
-    orchestrator::{Orchestrator, TaskScheduler, DagExecutor, ExecutionStrategy, ResourceConstraints},
+    agent::{Agent, AgentSelector, SelectionStrategy, TaskType},
+    evaluation::QualityMetric,
+    orchestrator::{
+        Orchestrator, TaskScheduler, DagExecutor, ExecutionStrategy, ResourceConstraints,
+        TaskEvent, TaskEventStream, TaskResult,
+    },
 };
-use parking_lot::RwLock;
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::{mpsc, Semaphore};
-use tracing::{info, warn, error, instrument};
+use tokio::sync::{broadcast, mpsc, watch, Semaphore};
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn, error, instrument};
 use uuid::Uuid;
 
+pub mod ab_test;
+pub mod admin_api;
+pub mod batch;
+pub mod canary_router;
+pub mod context_augmenter;
+pub mod cron_scheduler;
 pub mod dag;
+pub mod dag_checkpoint;
+pub mod latency_slo;
+pub mod provider_lanes;
+pub mod quality_gate;
+pub mod resource_governor;
+pub mod routing_rules;
 pub mod scheduler;
 pub mod dispatcher;
+pub mod batching_dispatcher;
+pub mod persistent_scheduler;
 
+use batch::BatchHandle;
+use context_augmenter::ContextAugmenter;
+use cron_scheduler::{CronScheduler, ScheduleId, ScheduleInfo};
 use dag::DagExecutorImpl;
-use scheduler::PriorityScheduler;
-use dispatcher::TaskDispatcher;
+use dag_checkpoint::DagCheckpointStore;
+use provider_lanes::ProviderLanes;
+use resource_governor::ResourceGovernor;
+use scheduler::{EdfScheduler, FairShareScheduler, PriorityScheduler, ShardedScheduler};
+use dispatcher::{LoadBalancer, TaskDispatcher};
+use batching_dispatcher::BatchingDispatcher;
+use persistent_scheduler::PersistentScheduler;
 
 /// Main orchestrator implementation
 pub struct MetaAIOrchestrator {
     agents: Arc<Vec<Box<dyn Agent>>>,
     agent_selector: Arc<Box<dyn AgentSelector>>,
-    scheduler: Arc<RwLock<Box<dyn TaskScheduler>>>,
+    scheduler: Arc<dyn TaskScheduler>,
     dag_executor: Arc<Box<dyn DagExecutor>>,
-    dispatcher: Arc<TaskDispatcher>,
+    /// Shards dispatch across `OrchestratorConfig.dispatcher_shards`
+    /// independent `TaskDispatcher`s via `LoadBalancer::least_loaded_dispatcher`,
+    /// so one saturated dispatcher's wait queue doesn't block tasks that a
+    /// less-loaded shard could have served immediately.
+    load_balancer: Arc<LoadBalancer>,
+    /// Coalesces concurrent `embed` calls for the same provider within
+    /// `OrchestratorConfig.embed_batch_window` into a single
+    /// `Agent::batch_embed` call. See `batching_dispatcher::BatchingDispatcher`.
+    batching_dispatcher: Arc<BatchingDispatcher>,
     active_tasks: Arc<DashMap<TaskId, Task>>,
+    task_results: Arc<DashMap<TaskId, TaskResult>>,
+    cancellation_tokens: Arc<DashMap<TaskId, CancellationToken>>,
+    /// Recent `Task.idempotency_key` submissions, so a duplicate within
+    /// `OrchestratorConfig.idempotency_window` can be answered with the
+    /// original task id instead of enqueuing a second execution.
+    idempotency_keys: Arc<DashMap<String, (TaskId, Instant)>>,
+    /// Tasks held out of the scheduler because `Task.depends_on` isn't fully
+    /// `Completed` yet. Re-checked every time a task completes.
+    pending_dependencies: Arc<DashMap<TaskId, Task>>,
+    /// Tasks that failed with a retryable error often enough to exhaust
+    /// `OrchestratorConfig.max_requeue_attempts`, kept alongside their final
+    /// `TaskResult` for operator inspection via `dead_letters`.
+    dead_letter_queue: Arc<DashMap<TaskId, (Task, TaskResult)>>,
     task_semaphore: Arc<Semaphore>,
+    /// Reserved permit pool that only `Priority::High`/`Priority::Critical`
+    /// tasks may draw on once `task_semaphore` is exhausted.
+    priority_semaphore: Arc<Semaphore>,
     metrics: Arc<dyn MetricsCollector>,
     config: OrchestratorConfig,
+    event_bus: broadcast::Sender<TaskEvent>,
+    cron_scheduler: Arc<CronScheduler>,
+    resource_governor: Arc<ResourceGovernor>,
+    provider_lanes: Arc<ProviderLanes>,
+    /// Flips to `true` whenever `enqueue` is rejected with `Error::QueueFull`
+    /// and back to `false` on the next successful enqueue, so upstream API
+    /// layers can watch it and shed load instead of hammering a full queue.
+    backpressure: watch::Sender<bool>,
+    /// Injects retrieved RAG context into a task's prompt before dispatch,
+    /// for tasks that opt in via metadata (see `ContextAugmenter`). `None`
+    /// (the default) leaves every prompt untouched.
+    context_augmenter: Option<Arc<ContextAugmenter>>,
 }
 
+/// Capacity of the task lifecycle event bus; subscribers that fall this far
+/// behind miss the oldest events instead of blocking publishers.
+const EVENT_BUS_CAPACITY: usize = 1024;
+
 /// Orchestrator configuration
 #[derive(Debug, Clone)]
 pub struct OrchestratorConfig {
@@ -55,6 +123,111 @@ pub struct OrchestratorConfig {
     pub retry_delay: Duration,
     pub execution_strategy: ExecutionStrategy,
     pub resource_constraints: ResourceConstraints,
+    pub scheduler_backend: SchedulerBackend,
+    pub dag_checkpoint_backend: DagCheckpointBackend,
+    pub hedging: HedgingConfig,
+    /// Ordered fallback providers, keyed by a task's preferred provider. When
+    /// set, each retry attempt moves one step further along the chain
+    /// instead of retrying the same provider (e.g. `Claude -> [Claude,
+    /// OpenAI, Local]` tries Claude first, then OpenAI, then Local).
+    pub fallback_chains: HashMap<meta_ai_common::types::LlmProvider, Vec<meta_ai_common::types::LlmProvider>>,
+    /// Concurrency permits set aside exclusively for `Priority::High` and
+    /// `Priority::Critical` tasks. Carved out of `max_concurrent_tasks` (not
+    /// in addition to it): `Low`/`Medium` tasks can only draw on the
+    /// remaining permits, so bulk low-priority work can never consume 100%
+    /// of dispatch capacity and starve higher-priority tasks of a slot to
+    /// run in. E.g. setting this to 20% of `max_concurrent_tasks` guarantees
+    /// High/Critical tasks always have a fifth of capacity available.
+    pub reserved_priority_permits: usize,
+    /// Per-provider concurrency limit, overriding `default_provider_concurrency`
+    /// for the given provider. Enforced in addition to (not instead of) the
+    /// global `task_semaphore`/`priority_semaphore`, so one slow provider
+    /// can't consume every permit and starve the others.
+    pub provider_concurrency: HashMap<meta_ai_common::types::LlmProvider, usize>,
+    /// Default per-provider concurrency limit for providers not listed in
+    /// `provider_concurrency`.
+    pub default_provider_concurrency: usize,
+    /// Per-provider requests-per-minute limit, overriding
+    /// `default_provider_rate_limit` for the given provider.
+    pub provider_rate_limits: HashMap<meta_ai_common::types::LlmProvider, u32>,
+    /// Default per-provider requests-per-minute limit for providers not
+    /// listed in `provider_rate_limits`.
+    pub default_provider_rate_limit: u32,
+    /// How long a `Task.idempotency_key` is remembered for. A second
+    /// `execute_task`/`execute_batch` submission with the same key inside
+    /// this window returns the original task's id instead of enqueuing a
+    /// duplicate.
+    pub idempotency_window: Duration,
+    /// How many times a task may be handed back to the scheduler via
+    /// `requeue_task` after a retryable failure before it's left `Failed`
+    /// and recorded in the dead-letter queue instead.
+    pub max_requeue_attempts: u32,
+    /// Base delay before a requeued task becomes eligible again, doubled
+    /// per additional attempt (capped at 2^10x) and applied via
+    /// `Task.not_before`.
+    pub requeue_backoff: Duration,
+    /// Providers a per-request `metadata["pinned_provider"]` override is
+    /// allowed to pin to. `None` allows pinning to any provider with a
+    /// registered agent (the same set `fallback_chains` can already reach);
+    /// `Some(list)` additionally restricts overrides to that list, so a
+    /// caller can't steer a task onto an expensive or untrusted provider
+    /// just by setting task metadata.
+    pub allowed_override_providers: Option<Vec<meta_ai_common::types::LlmProvider>>,
+    /// Quality gates evaluated against each task's observed metrics
+    /// (accuracy, bug rate, latency, error rate, token usage, cost) once it
+    /// finishes. Checked in order; the first gate whose condition triggers
+    /// decides the `GateAction` (warn, block, retry, or fall back to the
+    /// next provider in `fallback_chains`). Empty by default.
+    pub quality_gates: Vec<meta_ai_core::evaluation::QualityGate>,
+    /// Seed rule set for `SelectionStrategy::RuleBased`, matching a request's
+    /// prompt/metadata/task type to a preferred provider and required
+    /// capabilities (see `routing_rules::RoutingRulesEngine`). Replace it at
+    /// runtime via `MetaAIOrchestrator::reload_routing_rules` without
+    /// restarting.
+    pub routing_rules: Vec<meta_ai_core::agent::RoutingRule>,
+    /// Number of independent `TaskDispatcher`s to shard dispatch across (see
+    /// `dispatcher::LoadBalancer`), each sized to `max_concurrent_tasks` the
+    /// same way `SchedulerBackend::Sharded`'s shards each get the full
+    /// `max_queue_size`. Defaults to 1 (a single dispatcher, matching prior
+    /// behavior). Raise this when a single dispatcher's permit-wait queue
+    /// becomes the bottleneck rather than per-provider capacity.
+    pub dispatcher_shards: usize,
+    /// How long `MetaAIOrchestrator::embed` waits after the first call for a
+    /// given provider before flushing the batch (see
+    /// `batching_dispatcher::BatchingDispatcher`). Callers within this window
+    /// share a single `Agent::batch_embed` call.
+    pub embed_batch_window: Duration,
+    /// Maximum number of texts coalesced into a single `Agent::batch_embed`
+    /// call by `MetaAIOrchestrator::embed`.
+    pub embed_batch_max_size: usize,
+    /// Latency objective fed into each task's `gate_metrics` as
+    /// `QualityMetric::LatencyBurnRate`, read from the dispatching
+    /// `TaskDispatcher`'s per-`(provider, task type)` latency histogram (see
+    /// `latency_slo::LatencyHistogram::burn_rate`). `None` (the default)
+    /// skips computing it, so `quality_gates` configured against
+    /// `LatencyBurnRate` simply never trigger.
+    pub latency_slo: Option<latency_slo::LatencySlo>,
+    /// Default `LlmParameters` and preferred provider order for a request
+    /// whose metadata names a `TaskType` (see
+    /// `dispatcher::task_type_from_metadata`), e.g. pinning code generation
+    /// to `temperature: 0.2` on `Cursor`. Lets operators tune this centrally
+    /// instead of every caller setting it per request. A task type with no
+    /// entry here falls back to `LlmParameters::default()` and whatever
+    /// provider `fallback_chains`/pinning/the selector would have picked
+    /// anyway.
+    pub task_type_presets: HashMap<TaskType, TaskTypePreset>,
+}
+
+/// One `OrchestratorConfig.task_type_presets` entry.
+#[derive(Debug, Clone, Default)]
+pub struct TaskTypePreset {
+    /// Overrides `LlmParameters::default()` for requests of this task type.
+    pub parameters: meta_ai_common::types::LlmParameters,
+    /// Providers to prefer, in order, ahead of the configured selection
+    /// strategy - but still after an explicit per-request pin or an
+    /// in-progress `fallback_chains` retry. The first one with a registered
+    /// agent wins; empty defers entirely to normal selection.
+    pub preferred_providers: Vec<meta_ai_common::types::LlmProvider>,
 }
 
 impl Default for OrchestratorConfig {
@@ -66,91 +239,785 @@ impl Default for OrchestratorConfig {
             retry_delay: Duration::from_secs(1),
             execution_strategy: ExecutionStrategy::Adaptive,
             resource_constraints: ResourceConstraints::default(),
+            scheduler_backend: SchedulerBackend::InMemory,
+            dag_checkpoint_backend: DagCheckpointBackend::None,
+            hedging: HedgingConfig::default(),
+            fallback_chains: HashMap::new(),
+            reserved_priority_permits: 10,
+            provider_concurrency: HashMap::new(),
+            default_provider_concurrency: 20,
+            provider_rate_limits: HashMap::new(),
+            default_provider_rate_limit: 1_000,
+            idempotency_window: Duration::from_secs(300),
+            max_requeue_attempts: 3,
+            requeue_backoff: Duration::from_secs(2),
+            allowed_override_providers: None,
+            quality_gates: Vec::new(),
+            routing_rules: Vec::new(),
+            dispatcher_shards: 1,
+            embed_batch_window: Duration::from_millis(10),
+            embed_batch_max_size: 32,
+            latency_slo: None,
+            task_type_presets: HashMap::new(),
         }
     }
 }
 
+/// Speculative ("hedged") request configuration: for priorities with a
+/// configured latency budget, if the first agent hasn't answered within that
+/// budget, a second agent is asked the same thing and whichever responds
+/// first wins. Cuts tail latency at the cost of sometimes double-billing a
+/// request, so it's opt-in per priority rather than global.
+#[derive(Debug, Clone)]
+pub struct HedgingConfig {
+    pub latency_budget_by_priority: HashMap<Priority, Duration>,
+}
+
+impl HedgingConfig {
+    fn budget_for(&self, priority: Priority) -> Option<Duration> {
+        self.latency_budget_by_priority.get(&priority).copied()
+    }
+}
+
+impl Default for HedgingConfig {
+    /// Hedging is off by default except for `Critical` tasks, which get a
+    /// 1s budget before a speculative second request fires.
+    fn default() -> Self {
+        let mut latency_budget_by_priority = HashMap::new();
+        latency_budget_by_priority.insert(Priority::Critical, Duration::from_millis(1000));
+        Self { latency_budget_by_priority }
+    }
+}
+
+/// Which scheduler implementation (and, for in-memory backends, which
+/// ordering) dispatch draws tasks from.
+#[derive(Debug, Clone)]
+pub enum SchedulerBackend {
+    /// Keep the queue in memory only, ordered by `Priority`; pending and
+    /// running tasks are lost on restart.
+    InMemory,
+    /// Keep the queue in memory only, ordered by earliest deadline
+    /// (`created_at + timeout_ms`), falling back to `Priority` for tasks
+    /// without one. Better suited to latency-SLO workloads than `InMemory`.
+    InMemoryEdf,
+    /// Keep the queue in memory only, interleaved across `Task.tenant`
+    /// values by weighted fair share so one noisy tenant can't monopolize
+    /// dispatch. `tenant_weights` overrides `default_weight` per tenant.
+    FairShare { default_weight: u32, tenant_weights: HashMap<String, u32> },
+    /// Persist the queue to a SQLite database (e.g. `"sqlite://queue.db"`),
+    /// recovering in-flight tasks on startup.
+    Sqlite { database_url: String },
+    /// Keep the queue in memory, split across `shard_count` independent
+    /// priority-ordered shards with work-stealing `next_task`, for
+    /// submission rates high enough that a single queue's mutex becomes the
+    /// bottleneck. See `ShardedScheduler`.
+    Sharded { shard_count: usize },
+}
+
+/// Which backend DAG execution checkpoints per-node progress to.
+#[derive(Debug, Clone)]
+pub enum DagCheckpointBackend {
+    /// Don't checkpoint; `execute_dag` runs to completion or not at all, and
+    /// `resume_dag` is unavailable.
+    None,
+    /// Checkpoint per-node completion state to a SQLite database (e.g.
+    /// `"sqlite://dag_checkpoints.db"`), so a crashed run can be resumed.
+    Sqlite { database_url: String },
+}
+
 impl MetaAIOrchestrator {
-    /// Create new orchestrator instance
-    pub fn new(
+    /// Create new orchestrator instance, connecting to the configured
+    /// scheduler backend (and recovering in-flight tasks if it's persistent).
+    pub async fn new(
         agents: Vec<Box<dyn Agent>>,
         agent_selector: Box<dyn AgentSelector>,
         config: OrchestratorConfig,
-    ) -> Self {
+    ) -> Result<Self> {
         let max_concurrent = config.max_concurrent_tasks;
-        
-        Self {
-            agents: Arc::new(agents),
-            agent_selector: Arc::new(agent_selector),
-            scheduler: Arc::new(RwLock::new(Box::new(PriorityScheduler::new(1000)))),
-            dag_executor: Arc::new(Box::new(DagExecutorImpl::new())),
-            dispatcher: Arc::new(TaskDispatcher::new(max_concurrent)),
+        let reserved_priority_permits = config.reserved_priority_permits.min(max_concurrent);
+        let (event_bus, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        let agents = Arc::new(agents);
+        let agent_selector = Arc::new(agent_selector);
+
+        let scheduler: Arc<dyn TaskScheduler> = match &config.scheduler_backend {
+            SchedulerBackend::InMemory => Arc::new(PriorityScheduler::new(1000)),
+            SchedulerBackend::InMemoryEdf => Arc::new(EdfScheduler::new(1000)),
+            SchedulerBackend::FairShare { default_weight, tenant_weights } => {
+                Arc::new(FairShareScheduler::new(1000, *default_weight, tenant_weights.clone()))
+            }
+            SchedulerBackend::Sqlite { database_url } => {
+                Arc::new(PersistentScheduler::connect(database_url).await?)
+            }
+            SchedulerBackend::Sharded { shard_count } => Arc::new(ShardedScheduler::new(*shard_count, 1000)),
+        };
+
+        let dag_checkpoint = match &config.dag_checkpoint_backend {
+            DagCheckpointBackend::None => None,
+            DagCheckpointBackend::Sqlite { database_url } => {
+                Some(Arc::new(DagCheckpointStore::connect(database_url).await?))
+            }
+        };
+
+        let shard_count = config.dispatcher_shards.max(1);
+        let dispatcher_shards: Vec<Arc<TaskDispatcher>> = (0..shard_count)
+            .map(|_| {
+                let shard = Arc::new(TaskDispatcher::new(max_concurrent));
+                shard.set_routing_rules(config.routing_rules.clone());
+                shard
+            })
+            .collect();
+        let load_balancer = Arc::new(LoadBalancer::new(dispatcher_shards));
+        let batching_dispatcher = Arc::new(BatchingDispatcher::new(
+            config.embed_batch_window,
+            config.embed_batch_max_size.max(1),
+        ));
+
+        let resource_governor = Arc::new(ResourceGovernor::new(config.resource_constraints.clone()));
+        let provider_lanes = Arc::new(ProviderLanes::new(
+            config.default_provider_concurrency,
+            config.provider_concurrency.clone(),
+            config.default_provider_rate_limit,
+            config.provider_rate_limits.clone(),
+        ));
+
+        Ok(Self {
+            dag_executor: Arc::new(Box::new(DagExecutorImpl::new(
+                Arc::clone(&agents),
+                Arc::clone(&agent_selector),
+                dag_checkpoint,
+            ))),
+            agents,
+            agent_selector,
+            scheduler,
+            load_balancer,
+            batching_dispatcher,
             active_tasks: Arc::new(DashMap::new()),
-            task_semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            task_results: Arc::new(DashMap::new()),
+            cancellation_tokens: Arc::new(DashMap::new()),
+            idempotency_keys: Arc::new(DashMap::new()),
+            pending_dependencies: Arc::new(DashMap::new()),
+            dead_letter_queue: Arc::new(DashMap::new()),
+            task_semaphore: Arc::new(Semaphore::new(max_concurrent - reserved_priority_permits)),
+            priority_semaphore: Arc::new(Semaphore::new(reserved_priority_permits)),
             metrics: Arc::new(DefaultMetricsCollector),
             config,
+            event_bus,
+            cron_scheduler: Arc::new(CronScheduler::new()),
+            resource_governor,
+            provider_lanes,
+            backpressure: watch::channel(false).0,
+            context_augmenter: None,
+        })
+    }
+
+    /// Inject retrieved RAG context into the prompt of any task that opts in
+    /// via metadata (see `context_augmenter::ContextAugmenter`).
+    pub fn with_context_augmenter(mut self, context_augmenter: Arc<ContextAugmenter>) -> Self {
+        self.context_augmenter = Some(context_augmenter);
+        self
+    }
+
+    /// Subscribe to the queue-full backpressure signal. API layers can poll
+    /// or watch this to shed load (e.g. reject new requests with a 503)
+    /// instead of enqueuing into an already-full scheduler.
+    pub fn backpressure(&self) -> watch::Receiver<bool> {
+        self.backpressure.subscribe()
+    }
+
+    /// Register a recurring schedule. `cron_expr` is a standard 6-field cron
+    /// expression (seconds first), e.g. `"0 0 * * * *"` for hourly. The
+    /// resulting tasks are fed into this orchestrator's scheduler once
+    /// `start_cron_scheduler` is running.
+    pub fn add_cron_schedule(&self, task_template: Task, cron_expr: &str) -> Result<ScheduleId> {
+        self.cron_scheduler.add_cron(task_template, cron_expr)
+    }
+
+    /// Register a one-off schedule that fires once at `run_at`.
+    pub fn add_scheduled_task(&self, task_template: Task, run_at: chrono::DateTime<chrono::Utc>) -> Result<ScheduleId> {
+        self.cron_scheduler.add_once(task_template, run_at)
+    }
+
+    /// List all registered schedules (both recurring and one-off).
+    pub fn list_schedules(&self) -> Vec<ScheduleInfo> {
+        self.cron_scheduler.list()
+    }
+
+    /// Pause a schedule; it stays registered but stops firing until resumed.
+    pub fn pause_schedule(&self, id: ScheduleId) -> Result<()> {
+        self.cron_scheduler.pause(id)
+    }
+
+    /// Resume a previously paused schedule.
+    pub fn resume_schedule(&self, id: ScheduleId) -> Result<()> {
+        self.cron_scheduler.resume(id)
+    }
+
+    /// Permanently remove a schedule.
+    pub fn delete_schedule(&self, id: ScheduleId) -> Result<()> {
+        self.cron_scheduler.delete(id)
+    }
+
+    /// Start the background loop that polls for due schedules and feeds
+    /// them into this orchestrator's scheduler. Independent of `start()`,
+    /// which processes tasks once they're in the queue.
+    pub fn start_cron_scheduler(&self, poll_interval: Duration) -> tokio::task::JoinHandle<()> {
+        self.cron_scheduler.start(Arc::clone(&self.scheduler), poll_interval)
+    }
+
+    /// Current queue statistics, overlaid with whether dispatch is currently
+    /// throttled by `OrchestratorConfig.resource_constraints`. The scheduler
+    /// only knows what's queued, not what's running or finished, so
+    /// `running_tasks`/`completed_tasks`/`failed_tasks`/
+    /// `average_execution_time_ms` are overlaid here from `active_tasks` and
+    /// `task_results`, the same bookkeeping `get_task_status`/
+    /// `get_task_result` already read from.
+    pub async fn queue_stats(&self) -> meta_ai_core::orchestrator::QueueStats {
+        let mut stats = self.scheduler.queue_stats().await;
+        stats.throttled = self.resource_governor.is_throttled();
+        stats.provider_utilization = self.provider_lanes.utilization();
+
+        stats.running_tasks =
+            self.active_tasks.iter().filter(|entry| entry.value().status == TaskStatus::Running).count();
+
+        let mut completed = 0usize;
+        let mut failed = 0usize;
+        let mut execution_time_total = 0u64;
+        let mut execution_time_count = 0u64;
+        for entry in self.task_results.iter() {
+            match entry.value().status {
+                TaskStatus::Completed => completed += 1,
+                TaskStatus::Failed | TaskStatus::Timeout => failed += 1,
+                _ => {}
+            }
+            if let Some(ms) = entry.value().execution_time_ms {
+                execution_time_total += ms;
+                execution_time_count += 1;
+            }
+        }
+        stats.completed_tasks = completed;
+        stats.failed_tasks = failed;
+        stats.average_execution_time_ms = if execution_time_count > 0 {
+            execution_time_total as f64 / execution_time_count as f64
+        } else {
+            0.0
+        };
+
+        stats
+    }
+
+    /// Snapshot of queued (not yet dispatched) tasks matching `filter`, for
+    /// operator introspection when `queue_stats`'s aggregate counts aren't
+    /// enough detail to tell what's stuck.
+    pub async fn list_queued_tasks(
+        &self,
+        filter: &meta_ai_core::orchestrator::QueuedTaskFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Vec<meta_ai_core::orchestrator::QueuedTaskInfo> {
+        self.scheduler.list_queued_tasks(filter, limit, offset).await
+    }
+
+    /// Tasks that exhausted `OrchestratorConfig.max_requeue_attempts` after
+    /// repeated retryable failures, along with the `TaskResult` from their
+    /// final attempt.
+    pub fn dead_letters(&self) -> Vec<(Task, TaskResult)> {
+        self.dead_letter_queue.iter().map(|entry| entry.value().clone()).collect()
+    }
+
+    /// Mirror `sample_rate` (`0.0..=1.0`) of dispatched requests to `agent`
+    /// for offline comparison, scoring its responses with `evaluator` if
+    /// given. The shadow agent's response is never returned to callers; see
+    /// `shadow_results`.
+    pub fn set_shadow_target(
+        &self,
+        agent: Arc<dyn Agent>,
+        evaluator: Option<Arc<dyn meta_ai_core::evaluation::Evaluator>>,
+        sample_rate: f64,
+    ) {
+        for shard in self.load_balancer.dispatchers() {
+            shard.set_shadow_target(Arc::clone(&agent), evaluator.clone(), sample_rate);
+        }
+    }
+
+    /// Stop mirroring shadow traffic (see `set_shadow_target`).
+    pub fn clear_shadow_target(&self) {
+        for shard in self.load_balancer.dispatchers() {
+            shard.clear_shadow_target();
         }
     }
+
+    /// Recorded outcomes of mirrored shadow requests so far, across every
+    /// dispatcher shard.
+    pub fn shadow_results(&self) -> Vec<dispatcher::ShadowResult> {
+        self.load_balancer.dispatchers().iter().flat_map(|shard| shard.shadow_results()).collect()
+    }
+
+    /// Replace the rule set `SelectionStrategy::RuleBased` evaluates,
+    /// seeded from `OrchestratorConfig.routing_rules` at startup, without
+    /// restarting the orchestrator. Applied to every dispatcher shard.
+    pub fn reload_routing_rules(&self, rules: Vec<meta_ai_core::agent::RoutingRule>) {
+        for shard in self.load_balancer.dispatchers() {
+            shard.set_routing_rules(rules.clone());
+        }
+    }
+
+    /// Current load across every dispatcher shard (see
+    /// `OrchestratorConfig.dispatcher_shards`).
+    pub fn dispatch_stats(&self) -> dispatcher::LoadBalancerStats {
+        self.load_balancer.get_overall_stats()
+    }
+
+    /// Embed `text` against `provider`, transparently coalesced with other
+    /// concurrent `embed` calls for the same provider within
+    /// `OrchestratorConfig.embed_batch_window` (see
+    /// `batching_dispatcher::BatchingDispatcher`).
+    pub async fn embed(&self, provider: LlmProvider, text: String) -> Result<Embedding> {
+        self.batching_dispatcher.embed(Arc::clone(&self.agents), provider, text).await
+    }
+
+    /// Stop handing out new work from the scheduler, e.g. to drain the queue
+    /// during a provider incident or deployment without killing the process.
+    /// Already-running tasks finish normally; queued tasks stay queued.
+    pub fn pause(&self) {
+        self.scheduler.pause();
+    }
+
+    /// Resume dispatching after `pause`.
+    pub fn resume(&self) {
+        self.scheduler.resume();
+    }
+
+    /// Whether the scheduler is currently paused (see `pause`).
+    pub fn is_paused(&self) -> bool {
+        self.scheduler.is_paused()
+    }
+
+    /// Stop dispatching to a single provider's lane, e.g. while it's having
+    /// an incident, without pausing dispatch to every other provider.
+    pub fn pause_provider(&self, provider: meta_ai_common::types::LlmProvider) {
+        self.provider_lanes.pause(provider);
+    }
+
+    /// Resume dispatching to `provider` after `pause_provider`.
+    pub fn resume_provider(&self, provider: meta_ai_common::types::LlmProvider) {
+        self.provider_lanes.resume(provider);
+    }
+
+    /// Whether `provider`'s lane is currently paused (see `pause_provider`).
+    pub fn is_provider_paused(&self, provider: meta_ai_common::types::LlmProvider) -> bool {
+        self.provider_lanes.is_paused(provider)
+    }
+
+    /// Fan `task` out to `agent_count` distinct agents and reconcile their
+    /// answers with `reconciliation`. Returns the winning response, with the
+    /// other providers' responses recorded under the `"consensus_responses"`
+    /// metadata key.
+    #[instrument(skip(self, task))]
+    pub async fn execute_consensus(
+        &self,
+        task: Task,
+        agent_count: usize,
+        reconciliation: ReconciliationStrategy,
+    ) -> Result<LlmResponse> {
+        let mut chosen_agents: Vec<&Box<dyn Agent>> = Vec::new();
+        let base_request = LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: task.id,
+            provider: task.provider.unwrap_or(meta_ai_common::types::LlmProvider::OpenAI),
+            prompt: task.description.clone().unwrap_or_default(),
+            parameters: Default::default(),
+            timeout_ms: task.timeout_ms,
+            attachments: Vec::new(),
+            session_id: dispatcher::session_id_from_metadata(&task.metadata),
+            metadata: task.metadata.clone(),
+        };
+
+        for _ in 0..agent_count {
+            let candidates: Vec<&Box<dyn Agent>> =
+                self.agents.iter().filter(|a| !chosen_agents.iter().any(|c| c.name() == a.name())).collect();
+            let Some(&first_candidate) = candidates.first() else { break };
+
+            // `select_agent` needs the original contiguous slice to apply its
+            // own ranking (e.g. LowestLatency); fall back to the first
+            // not-yet-chosen agent if it picks one we already have.
+            let agent = match self.agent_selector.select_agent(&base_request, &self.agents).await {
+                Ok(agent) if !chosen_agents.iter().any(|c| c.name() == agent.name()) => agent,
+                _ => first_candidate,
+            };
+            chosen_agents.push(agent);
+        }
+
+        if chosen_agents.is_empty() {
+            return Err(Error::Internal("no agents available for consensus execution".to_string()));
+        }
+
+        let submissions = futures::future::join_all(chosen_agents.iter().map(|agent| {
+            let mut request = base_request.clone();
+            request.id = Uuid::new_v4();
+            request.provider = agent.provider();
+            agent.submit(request)
+        }))
+        .await;
+
+        let responses: Vec<LlmResponse> = submissions.into_iter().filter_map(std::result::Result::ok).collect();
+        if responses.is_empty() {
+            return Err(Error::Internal("all agents failed during consensus execution".to_string()));
+        }
+
+        let winner = reconcile(&responses, reconciliation);
+        let mut chosen = responses[winner].clone();
+        let all_responses: Vec<_> = responses
+            .iter()
+            .map(|r| serde_json::json!({ "provider": r.provider, "content": r.content }))
+            .collect();
+        chosen.metadata.insert("consensus_responses".to_string(), serde_json::json!(all_responses));
+        Ok(chosen)
+    }
+
+    /// Publish a task lifecycle event to all current subscribers. Errors
+    /// (no active subscribers) are expected and silently ignored.
+    fn publish_event(&self, task_id: TaskId, status: TaskStatus, result: Option<TaskResult>) {
+        let _ = self.event_bus.send(TaskEvent {
+            task_id,
+            status,
+            result,
+            timestamp: chrono::Utc::now(),
+        });
+    }
     
     /// Start background task processing
     pub fn start(&self) -> tokio::task::JoinHandle<()> {
         let scheduler = Arc::clone(&self.scheduler);
-        let dispatcher = Arc::clone(&self.dispatcher);
+        let load_balancer = Arc::clone(&self.load_balancer);
         let active_tasks = Arc::clone(&self.active_tasks);
+        let task_results = Arc::clone(&self.task_results);
+        let cancellation_tokens = Arc::clone(&self.cancellation_tokens);
         let task_semaphore = Arc::clone(&self.task_semaphore);
+        let priority_semaphore = Arc::clone(&self.priority_semaphore);
+        let event_bus = self.event_bus.clone();
         let agents = Arc::clone(&self.agents);
         let agent_selector = Arc::clone(&self.agent_selector);
         let metrics = Arc::clone(&self.metrics);
         let config = self.config.clone();
-        
+        let resource_governor = Arc::clone(&self.resource_governor);
+        let provider_lanes = Arc::clone(&self.provider_lanes);
+        let pending_dependencies = Arc::clone(&self.pending_dependencies);
+        let dead_letter_queue = Arc::clone(&self.dead_letter_queue);
+        let context_augmenter = self.context_augmenter.clone();
+
         tokio::spawn(async move {
             loop {
+                if let Some(reason) = resource_governor.throttle_reason() {
+                    metrics.record_throttle(reason, true);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+                metrics.record_throttle("tokens_per_minute", false);
+                metrics.record_throttle("memory", false);
+                for (shard, stats) in load_balancer.dispatchers().iter().map(|d| d.get_stats()).enumerate() {
+                    metrics.record_dispatch_shard_utilization(shard, stats.utilization);
+                }
+
                 // Get next task from scheduler
-                let task = {
-                    let mut sched = scheduler.write();
-                    sched.next_task().await
-                };
-                
+                let task = scheduler.next_task().await;
+
                 match task {
-                    Ok(Some(task)) => {
+                    Ok(Some(mut task)) => {
                         let task_id = task.id;
+
+                        let effective_timeout = task.timeout_ms.map(Duration::from_millis).unwrap_or(config.task_timeout);
+                        let age = chrono::Utc::now()
+                            .signed_duration_since(task.created_at)
+                            .to_std()
+                            .unwrap_or(Duration::ZERO);
+
+                        if age >= effective_timeout {
+                            warn!("Task {} exceeded its deadline while queued; expiring", task_id);
+                            task.status = TaskStatus::Timeout;
+                            active_tasks.insert(task_id, task.clone());
+                            let task_result = TaskResult {
+                                task_id,
+                                status: TaskStatus::Timeout,
+                                response: None,
+                                error: Some(format!("Task {task_id} exceeded its deadline while queued")),
+                                completed_at: chrono::Utc::now(),
+                                execution_time_ms: None,
+                            };
+                            task_results.insert(task_id, task_result.clone());
+                            let _ = event_bus.send(TaskEvent {
+                                task_id,
+                                status: TaskStatus::Timeout,
+                                result: Some(task_result),
+                                timestamp: chrono::Utc::now(),
+                            });
+                            continue;
+                        }
+                        let deadline = Instant::now() + (effective_timeout - age);
+
+                        task.status = TaskStatus::Pending;
                         active_tasks.insert(task_id, task.clone());
-                        
-                        // Acquire semaphore permit
-                        let permit = task_semaphore.clone().acquire_owned().await.unwrap();
-                        
+                        if let Err(e) = transition_task(&active_tasks, task_id, TaskStatus::Running) {
+                            error!("Failed to mark task {} running: {}", task_id, e);
+                            continue;
+                        }
+                        let token = cancellation_tokens
+                            .entry(task_id)
+                            .or_insert_with(CancellationToken::new)
+                            .clone();
+                        let _ = event_bus.send(TaskEvent {
+                            task_id,
+                            status: TaskStatus::Running,
+                            result: None,
+                            timestamp: chrono::Utc::now(),
+                        });
+
+                        // Acquire a concurrency permit. `High`/`Critical` tasks
+                        // try the main pool first but fall back to the reserved
+                        // pool instead of queueing behind saturating `Low`/
+                        // `Medium` work, so they're never starved by it.
+                        let permit = if task.priority >= Priority::High {
+                            match task_semaphore.clone().try_acquire_owned() {
+                                Ok(permit) => permit,
+                                Err(_) => priority_semaphore.clone().acquire_owned().await.unwrap(),
+                            }
+                        } else {
+                            task_semaphore.clone().acquire_owned().await.unwrap()
+                        };
+
+                        // Also acquire this task's provider lane permit, so a
+                        // slow/rate-limited provider can't consume every
+                        // global permit and starve the others.
+                        let provider = task.provider.unwrap_or(meta_ai_common::types::LlmProvider::OpenAI);
+                        let lane_permit = provider_lanes.acquire(provider).await;
+
                         // Spawn task execution
                         let agents = Arc::clone(&agents);
                         let agent_selector = Arc::clone(&agent_selector);
                         let active_tasks = Arc::clone(&active_tasks);
+                        let task_results = Arc::clone(&task_results);
+                        let cancellation_tokens = Arc::clone(&cancellation_tokens);
                         let metrics = Arc::clone(&metrics);
                         let config = config.clone();
-                        
+                        let event_bus = event_bus.clone();
+                        let resource_governor = Arc::clone(&resource_governor);
+                        let pending_dependencies = Arc::clone(&pending_dependencies);
+                        let scheduler = Arc::clone(&scheduler);
+                        let dead_letter_queue = Arc::clone(&dead_letter_queue);
+                        let context_augmenter = context_augmenter.clone();
+                        let task_snapshot = task.clone();
+                        // Picked per task (not once for the whole loop) so the
+                        // load snapshot backing `least_loaded_dispatcher` stays
+                        // fresh as shards fill up and drain.
+                        let dispatcher = Arc::clone(load_balancer.least_loaded_dispatcher());
+
+                        resource_governor.task_started();
                         tokio::spawn(async move {
                             let start = Instant::now();
                             let result = execute_task_with_retry(
                                 task,
                                 &agents,
                                 &*agent_selector,
+                                &dispatcher,
                                 &config,
                                 &*metrics,
+                                &token,
+                                deadline,
+                                context_augmenter.as_deref(),
                             ).await;
-                            
-                            // Update task status
-                            if let Some((_, mut task)) = active_tasks.remove(&task_id) {
-                                task.status = match result {
+                            let was_cancelled = token.is_cancelled();
+                            let was_timed_out = matches!(result, Err(Error::Timeout(_)));
+                            cancellation_tokens.remove(&task_id);
+                            resource_governor.task_finished();
+                            if let Ok(response) = &result {
+                                resource_governor.record_tokens(response.usage.total_tokens);
+
+                                let cost = dispatcher.estimate_cost(provider, response.usage.prompt_tokens);
+                                let model = dispatcher.agent_model(provider).unwrap_or_else(|| "unknown".to_string());
+                                let task_type_label = dispatcher::task_type_from_metadata(&task_snapshot.metadata)
+                                    .map(dispatcher::task_type_label)
+                                    .unwrap_or("unknown");
+                                metrics.record_cost(provider.as_str(), &model, task_type_label, cost);
+                                metrics.record_tenant_cost(task_snapshot.tenant.as_deref().unwrap_or("none"), cost);
+                            }
+
+                            // Evaluate configured quality gates against this task's
+                            // observed metrics. Accuracy/bug rate aren't included
+                            // here since they're rolling, cross-task signals that
+                            // would need an `Evaluator` lookup; only the
+                            // per-task metrics gates can actually see are used.
+                            let gate_outcome = if config.quality_gates.is_empty() {
+                                quality_gate::GateOutcome::Pass
+                            } else {
+                                let latency_burn_rate = config.latency_slo.and_then(|slo| {
+                                    let task_type = dispatcher::task_type_from_metadata(&task_snapshot.metadata)?;
+                                    Some(dispatcher.latency_burn_rate(provider, task_type, slo))
+                                });
+                                let gate_metrics = quality_gate::GateMetrics {
+                                    latency_ms: Some(start.elapsed().as_millis() as f64),
+                                    error_rate: Some(if result.is_err() { 1.0 } else { 0.0 }),
+                                    token_usage: result.as_ref().ok().map(|r| f64::from(r.usage.total_tokens)),
+                                    cost: result
+                                        .as_ref()
+                                        .ok()
+                                        .map(|r| dispatcher::estimated_cost(provider, r.usage.prompt_tokens)),
+                                    latency_burn_rate,
+                                    ..Default::default()
+                                };
+                                let fallback_provider =
+                                    config.fallback_chains.get(&provider).and_then(|chain| chain.get(1).copied());
+                                quality_gate::evaluate_gates(&config.quality_gates, &gate_metrics, fallback_provider)
+                            };
+
+                            if let quality_gate::GateOutcome::Warn { ref description, .. } = gate_outcome {
+                                warn!("{}", description);
+                                metrics.record_error("quality_gate_warn", "warning", provider.as_str());
+                            }
+
+                            // Feed this task's outcome into its A/B experiment,
+                            // if `metadata["ab_test"]` named one that's still
+                            // running. Accuracy isn't recorded here for the
+                            // same reason it's absent from `gate_metrics` above:
+                            // scoring it needs an `Evaluator`, which this
+                            // closure doesn't have.
+                            if let Some(experiment_name) = dispatcher::ab_test_name_from_metadata(&task_snapshot.metadata) {
+                                if let Some(experiment) = dispatcher.ab_test_engine().get(&experiment_name) {
+                                    let arm = experiment.assign(task_id);
+                                    experiment.record(arm, QualityMetric::Latency, start.elapsed().as_millis() as f64);
+                                    experiment.record(
+                                        arm,
+                                        QualityMetric::ErrorRate,
+                                        if result.is_err() { 1.0 } else { 0.0 },
+                                    );
+                                    if let Ok(response) = &result {
+                                        experiment.record(
+                                            arm,
+                                            QualityMetric::Cost,
+                                            dispatcher::estimated_cost(provider, response.usage.prompt_tokens),
+                                        );
+                                    }
+                                }
+                            }
+
+                            let gate_blocks = matches!(gate_outcome, quality_gate::GateOutcome::Block { .. });
+                            let gate_forces_retry = matches!(
+                                gate_outcome,
+                                quality_gate::GateOutcome::Retry { .. } | quality_gate::GateOutcome::Fallback { .. }
+                            );
+                            let gate_description = match &gate_outcome {
+                                quality_gate::GateOutcome::Block { description, .. }
+                                | quality_gate::GateOutcome::Retry { description, .. }
+                                | quality_gate::GateOutcome::Fallback { description, .. } => Some(description.clone()),
+                                _ => None,
+                            };
+
+                            let final_status = if was_cancelled {
+                                TaskStatus::Cancelled
+                            } else if was_timed_out {
+                                TaskStatus::Timeout
+                            } else if gate_blocks || gate_forces_retry {
+                                TaskStatus::Failed
+                            } else {
+                                match &result {
                                     Ok(_) => TaskStatus::Completed,
                                     Err(_) => TaskStatus::Failed,
+                                }
+                            };
+
+                            // A retryable failure gets one more lap through the
+                            // scheduler (with backoff) before it's treated as
+                            // terminal, up to `max_requeue_attempts`.
+                            let is_retryable =
+                                result.as_ref().err().is_some_and(|e| e.is_retryable()) || gate_forces_retry;
+                            let eligible_for_requeue = final_status == TaskStatus::Failed
+                                && is_retryable
+                                && !gate_blocks
+                                && task_snapshot.requeue_attempts < config.max_requeue_attempts;
+
+                            let requeued = if eligible_for_requeue
+                                && transition_task(&active_tasks, task_id, TaskStatus::Pending).is_ok()
+                            {
+                                let mut retry_task = task_snapshot.clone();
+                                retry_task.requeue_attempts += 1;
+                                retry_task.status = TaskStatus::Pending;
+                                retry_task.updated_at = chrono::Utc::now();
+                                if let quality_gate::GateOutcome::Fallback { provider: Some(next), .. } = gate_outcome {
+                                    retry_task.provider = Some(next);
+                                }
+                                let backoff = config.requeue_backoff
+                                    * 2u32.pow(retry_task.requeue_attempts.saturating_sub(1).min(10));
+                                retry_task.not_before =
+                                    Some(chrono::Utc::now() + chrono::Duration::from_std(backoff).unwrap_or_default());
+
+                                match scheduler.requeue_task(retry_task.clone()).await {
+                                    Ok(()) => {
+                                        info!(
+                                            "Requeued task {} (attempt {}) after a retryable failure",
+                                            task_id, retry_task.requeue_attempts
+                                        );
+                                        active_tasks.insert(task_id, retry_task);
+                                        let _ = event_bus.send(TaskEvent {
+                                            task_id,
+                                            status: TaskStatus::Pending,
+                                            result: None,
+                                            timestamp: chrono::Utc::now(),
+                                        });
+                                        true
+                                    }
+                                    Err(e) => {
+                                        warn!("Failed to requeue task {}: {}", task_id, e);
+                                        false
+                                    }
+                                }
+                            } else {
+                                false
+                            };
+
+                            if !requeued {
+                                // Update task status in place so `get_task_status`
+                                // keeps reporting it after the task finishes.
+                                if let Err(e) = transition_task(&active_tasks, task_id, final_status) {
+                                    warn!("Task {} ended in an unexpected state: {}", task_id, e);
+                                }
+
+                                // Store the result so callers can retrieve it later
+                                let task_result = TaskResult {
+                                    task_id,
+                                    status: final_status,
+                                    response: result.as_ref().ok().cloned(),
+                                    error: result.as_ref().err().map(|e| e.to_string()).or(gate_description),
+                                    completed_at: chrono::Utc::now(),
+                                    execution_time_ms: Some(start.elapsed().as_millis() as u64),
                                 };
-                                task.updated_at = chrono::Utc::now();
+                                task_results.insert(task_id, task_result.clone());
+                                if final_status == TaskStatus::Completed {
+                                    promote_ready_dependents(&pending_dependencies, &task_results, &scheduler).await;
+                                }
+                                if final_status == TaskStatus::Failed {
+                                    dead_letter_queue.insert(task_id, (task_snapshot.clone(), task_result.clone()));
+                                }
+                                let _ = event_bus.send(TaskEvent {
+                                    task_id,
+                                    status: final_status,
+                                    result: Some(task_result),
+                                    timestamp: chrono::Utc::now(),
+                                });
                             }
-                            
-                            // Release permit
+
+                            // Release permits
                             drop(permit);
-                            
+                            drop(lane_permit);
+
                             // Record metrics
                             let duration = start.elapsed().as_secs_f64();
-                            let status = if result.is_ok() { "success" } else { "failed" };
+                            let status = if was_cancelled {
+                                "cancelled"
+                            } else if was_timed_out {
+                                "timeout"
+                            } else if result.is_ok() {
+                                "success"
+                            } else {
+                                "failed"
+                            };
                             metrics.record_request("orchestrator", status, duration);
                         });
                     }
@@ -168,30 +1035,123 @@ impl MetaAIOrchestrator {
     }
 }
 
+impl MetaAIOrchestrator {
+    /// Schedule `task` and register the bookkeeping (cancellation token,
+    /// `Pending` event) shared by every entry point that feeds the queue.
+    async fn enqueue(&self, task: Task) -> Result<TaskId> {
+        if let Some(key) = &task.idempotency_key {
+            if let Some(existing_id) = self.reserve_idempotency_key(key, task.id) {
+                debug!("Idempotency key {} already submitted as task {}; skipping duplicate", key, existing_id);
+                return Ok(existing_id);
+            }
+        }
+
+        if !task.depends_on.is_empty() && !dependencies_met(&task.depends_on, &self.task_results) {
+            let task_id = task.id;
+            self.pending_dependencies.insert(task_id, task.clone());
+            self.cancellation_tokens
+                .entry(task_id)
+                .or_insert_with(CancellationToken::new);
+            self.publish_event(task_id, TaskStatus::Pending, None);
+            return Ok(task_id);
+        }
+
+        let outcome = self.scheduler.schedule_task(task.clone()).await;
+        // A full queue is a signal, not just an error: flip the backpressure
+        // watch so upstream API layers can shed load, and clear it again as
+        // soon as the queue has room.
+        let _ = self.backpressure.send(matches!(outcome, Err(Error::QueueFull { .. })));
+        if let Err(e) = outcome {
+            // Scheduling failed, so this submission never actually happened;
+            // release the reservation so a retry with the same key isn't
+            // stuck deduplicating against a task that doesn't exist.
+            if let Some(key) = &task.idempotency_key {
+                self.idempotency_keys.remove(key);
+            }
+            return Err(e);
+        }
+        self.cancellation_tokens
+            .entry(task.id)
+            .or_insert_with(CancellationToken::new);
+        self.publish_event(task.id, TaskStatus::Pending, None);
+        Ok(task.id)
+    }
+
+    /// Atomically claim `key` for `task_id`, treating an entry older than
+    /// `idempotency_window` as expired rather than live. Returns the task id
+    /// already holding a live reservation, or `None` if this call claimed
+    /// the key itself. Doing the check-and-claim as a single `entry` call
+    /// (instead of a separate `get` followed by `insert`) is what keeps two
+    /// concurrent submissions of the same key from both observing "unseen"
+    /// and both proceeding to schedule.
+    fn reserve_idempotency_key(&self, key: &str, task_id: TaskId) -> Option<TaskId> {
+        // Opportunistically sweep expired entries so the map doesn't grow
+        // unboundedly over the life of the process, mirroring how
+        // `ResourceGovernor::record_tokens` trims its own trailing window.
+        self.idempotency_keys.retain(|_, (_, seen_at)| seen_at.elapsed() < self.config.idempotency_window);
+
+        match self.idempotency_keys.entry(key.to_string()) {
+            // The sweep above already evicted anything older than
+            // `idempotency_window`, so a surviving entry is still live.
+            dashmap::mapref::entry::Entry::Occupied(entry) => Some(entry.get().0),
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                entry.insert((task_id, Instant::now()));
+                None
+            }
+        }
+    }
+
+    /// Like `enqueue`, but when the queue is full, retry with a short sleep
+    /// instead of failing immediately, up to `timeout`. Lets a caller that
+    /// can tolerate a little latency ride out a transient burst rather than
+    /// reacting to `Error::QueueFull` itself.
+    pub async fn enqueue_blocking(&self, task: Task, timeout: Duration) -> Result<TaskId> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.enqueue(task.clone()).await {
+                Err(Error::QueueFull { depth, capacity }) => {
+                    if Instant::now() >= deadline {
+                        return Err(Error::QueueFull { depth, capacity });
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Schedule many tasks at once and return a handle for tracking their
+    /// aggregate progress (and waiting for all of them to finish), without
+    /// blocking on any individual task the way `execute_task` does.
+    #[instrument(skip(self, tasks))]
+    pub async fn execute_batch(&self, tasks: Vec<Task>) -> Result<BatchHandle> {
+        let batch_id = Uuid::new_v4();
+        info!("Scheduling batch {} of {} tasks", batch_id, tasks.len());
+
+        let mut task_ids = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            task_ids.push(self.enqueue(task).await?);
+        }
+
+        Ok(BatchHandle::new(batch_id, task_ids, Arc::clone(&self.task_results)))
+    }
+}
+
 #[async_trait]
 impl Orchestrator for MetaAIOrchestrator {
     #[instrument(skip(self))]
     async fn execute_task(&self, task: Task) -> Result<TaskStatus> {
         info!("Executing task: {} ({})", task.id, task.name);
-        
-        // Add task to scheduler
-        {
-            let mut scheduler = self.scheduler.write();
-            scheduler.schedule_task(task.clone()).await?;
-        }
-        
+        let task_id = self.enqueue(task.clone()).await?;
+
         // Wait for task completion (simplified for now)
-        let task_id = task.id;
-        let timeout = self.config.task_timeout;
-        
+        let timeout = task.timeout_ms.map(Duration::from_millis).unwrap_or(self.config.task_timeout);
+
         tokio::time::timeout(timeout, async {
             loop {
                 if let Some(task) = self.active_tasks.get(&task_id) {
-                    match task.status {
-                        TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled => {
-                            return Ok(task.status);
-                        }
-                        _ => {}
+                    if task.status.is_terminal() {
+                        return Ok(task.status);
                     }
                 }
                 tokio::time::sleep(Duration::from_millis(100)).await;
@@ -232,60 +1192,234 @@ impl Orchestrator for MetaAIOrchestrator {
             .ok_or_else(|| Error::Internal(format!("Task {} not found", task_id)))
     }
     
+    async fn get_task_result(&self, task_id: TaskId) -> Result<TaskResult> {
+        self.task_results
+            .get(&task_id)
+            .map(|result| result.clone())
+            .ok_or_else(|| Error::Internal(format!("No result stored for task {}", task_id)))
+    }
+
+    async fn subscribe(&self, task_id: TaskId) -> Result<TaskEventStream> {
+        let receiver = self.event_bus.subscribe();
+        let stream = BroadcastStream::new(receiver)
+            .filter_map(|event| event.ok())
+            .filter(move |event| event.task_id == task_id);
+        Ok(Box::pin(stream))
+    }
+
     async fn cancel_task(&mut self, task_id: TaskId) -> Result<()> {
-        if let Some(mut task) = self.active_tasks.get_mut(&task_id) {
-            task.status = TaskStatus::Cancelled;
-            task.updated_at = chrono::Utc::now();
-            Ok(())
-        } else {
-            Err(Error::Internal(format!("Task {} not found", task_id)))
-        }
+        let Some(token) = self.cancellation_tokens.get(&task_id).map(|t| t.clone()) else {
+            return Err(Error::Internal(format!("Task {} not found", task_id)));
+        };
+        token.cancel();
+
+        // The task's own execution loop also transitions it to `Cancelled`
+        // once it observes the token; if it already finished some other way
+        // (or hasn't started yet), ignore the now-invalid transition here.
+        let _ = transition_task(&self.active_tasks, task_id, TaskStatus::Cancelled);
+        Ok(())
     }
     
     async fn list_active_tasks(&self) -> Result<Vec<Task>> {
         Ok(self.active_tasks
             .iter()
+            .filter(|entry| !entry.value().status.is_terminal())
             .map(|entry| entry.value().clone())
             .collect())
     }
+
+    async fn execute_dag(&self, dag: &meta_ai_core::orchestrator::TaskDag) -> Result<meta_ai_core::orchestrator::DagExecutionResult> {
+        self.dag_executor.execute_dag(dag).await
+    }
+
+    async fn resume_dag(&self, dag_run_id: meta_ai_core::orchestrator::DagRunId) -> Result<meta_ai_core::orchestrator::DagExecutionResult> {
+        self.dag_executor.resume_dag(dag_run_id).await
+    }
+}
+
+/// Whether every id in `depends_on` has a stored result with
+/// `TaskStatus::Completed`. An unknown dependency (not in `task_results` yet)
+/// counts as unmet.
+fn dependencies_met(depends_on: &[TaskId], task_results: &DashMap<TaskId, TaskResult>) -> bool {
+    depends_on
+        .iter()
+        .all(|id| task_results.get(id).is_some_and(|r| r.status == TaskStatus::Completed))
+}
+
+/// Re-check every task in `pending_dependencies` against `task_results` and
+/// hand any whose `depends_on` are now all `Completed` off to `scheduler`.
+/// Called whenever a task finishes, since that's the only time a dependent's
+/// eligibility can change.
+async fn promote_ready_dependents(
+    pending_dependencies: &DashMap<TaskId, Task>,
+    task_results: &DashMap<TaskId, TaskResult>,
+    scheduler: &Arc<dyn TaskScheduler>,
+) {
+    let ready: Vec<TaskId> = pending_dependencies
+        .iter()
+        .filter(|entry| dependencies_met(&entry.depends_on, task_results))
+        .map(|entry| *entry.key())
+        .collect();
+
+    for task_id in ready {
+        let Some((_, task)) = pending_dependencies.remove(&task_id) else { continue };
+        if let Err(e) = scheduler.schedule_task(task).await {
+            warn!("Failed to schedule dependent task {} once its dependencies completed: {}", task_id, e);
+        }
+    }
+}
+
+/// Move `task_id`'s status to `next`, validating the transition against
+/// `TaskStatus::can_transition_to` and updating the entry in place (never
+/// removed, so it stays the authoritative record for `get_task_status` even
+/// after the task finishes).
+fn transition_task(active_tasks: &DashMap<TaskId, Task>, task_id: TaskId, next: TaskStatus) -> Result<()> {
+    let mut entry = active_tasks
+        .get_mut(&task_id)
+        .ok_or_else(|| Error::Internal(format!("Task {task_id} not found")))?;
+    let current = entry.status;
+    if !current.can_transition_to(next) {
+        return Err(Error::Validation(format!(
+            "invalid task transition for {task_id}: {current:?} -> {next:?}"
+        )));
+    }
+    entry.status = next;
+    entry.updated_at = chrono::Utc::now();
+    Ok(())
 }
 
-/// Execute task with retry logic
+/// Execute task with retry logic. Cooperatively aborts as soon as
+/// `cancellation_token` fires or `deadline` passes, dropping any in-flight
+/// agent request instead of waiting for it to finish.
 async fn execute_task_with_retry(
     task: Task,
     agents: &[Box<dyn Agent>],
     agent_selector: &dyn AgentSelector,
+    dispatcher: &TaskDispatcher,
     config: &OrchestratorConfig,
     metrics: &dyn MetricsCollector,
+    cancellation_token: &CancellationToken,
+    deadline: Instant,
+    context_augmenter: Option<&ContextAugmenter>,
 ) -> Result<LlmResponse> {
     let mut attempts = 0;
     let mut last_error = None;
-    
+    let tokio_deadline = tokio::time::Instant::from_std(deadline);
+
     while attempts < config.retry_attempts {
+        if cancellation_token.is_cancelled() {
+            return Err(Error::Internal(format!("Task {} cancelled", task.id)));
+        }
+        if Instant::now() >= deadline {
+            return Err(Error::Timeout(format!("Task {} exceeded its deadline", task.id)));
+        }
         attempts += 1;
-        
-        // Create LLM request from task
+
+        // Create LLM request from task, telling the agent how much time is left.
+        let remaining_ms = deadline.saturating_duration_since(Instant::now()).as_millis() as u64;
+        let mut metadata = task.metadata.clone();
+        metadata.insert("deadline_remaining_ms".to_string(), serde_json::json!(remaining_ms));
+        // Lets the dispatcher's priority-aware permit acquisition (see
+        // `dispatcher::priority_from_metadata`) give this task's priority its
+        // due, instead of treating every request as equally first-come.
+        metadata.insert("priority".to_string(), serde_json::to_value(task.priority).unwrap_or(serde_json::json!("medium")));
+
+        // If the task's preferred provider has a configured fallback chain,
+        // walk it one step per attempt instead of retrying the same
+        // provider; otherwise fall through to the configured selector.
+        let fallback_provider = task
+            .provider
+            .and_then(|preferred| config.fallback_chains.get(&preferred))
+            .filter(|chain| !chain.is_empty())
+            .map(|chain| chain[(attempts as usize - 1).min(chain.len() - 1)]);
+
+        let prompt = task.description.clone().unwrap_or_default();
+        let prompt = match context_augmenter {
+            Some(augmenter) => augmenter.augment(&task, prompt).await?,
+            None => prompt,
+        };
+
+        // Centrally-configured default `LlmParameters` (and provider
+        // preference) for this request's `TaskType`, if the caller set one
+        // in metadata and an operator configured a preset for it.
+        let task_type_preset =
+            dispatcher::task_type_from_metadata(&metadata).and_then(|task_type| config.task_type_presets.get(&task_type));
+        let parameters = task_type_preset.map(|preset| preset.parameters.clone()).unwrap_or_default();
+
         let request = LlmRequest {
             id: Uuid::new_v4(),
             task_id: task.id,
-            provider: task.provider.unwrap_or(meta_ai_common::types::LlmProvider::OpenAI),
-            prompt: task.description.unwrap_or_default(),
-            parameters: Default::default(),
-            timeout_ms: Some(config.task_timeout.as_millis() as u64),
-            metadata: task.metadata.clone(),
+            provider: fallback_provider.or(task.provider).unwrap_or(meta_ai_common::types::LlmProvider::OpenAI),
+            prompt,
+            parameters,
+            timeout_ms: Some(remaining_ms),
+            attachments: Vec::new(),
+            session_id: dispatcher::session_id_from_metadata(&metadata),
+            metadata,
         };
-        
-        // Select agent and execute
-        match agent_selector.select_agent(&request, agents).await {
+
+        // Select agent and execute. A fallback chain pins the exact provider
+        // for this attempt; otherwise per-request metadata may pin a
+        // provider, or the task type's preset may prefer one, falling back
+        // to the orchestrator-wide configured selector if none of those
+        // apply.
+        let pinned_provider = dispatcher::pinned_provider_from_metadata(&request.metadata);
+        let strategy_override = dispatcher::selection_strategy_from_metadata(&request.metadata);
+        let preset_provider = task_type_preset.and_then(|preset| {
+            preset.preferred_providers.iter().copied().find(|provider| agents.iter().any(|a| a.provider() == *provider))
+        });
+
+        let override_not_allowed = pinned_provider.zip(config.allowed_override_providers.as_ref()).and_then(
+            |(provider, allowed)| {
+                (fallback_provider.is_none() && !allowed.contains(&provider)).then_some(provider)
+            },
+        );
+
+        let selected = if let Some(provider) = override_not_allowed {
+            Err(Error::Validation(format!(
+                "task {} requested provider override {provider:?}, which is not in the configured allow-list",
+                task.id
+            )))
+        } else {
+            match fallback_provider.or(pinned_provider).or(preset_provider) {
+                Some(provider) => agents
+                    .iter()
+                    .find(|a| a.provider() == provider)
+                    .ok_or_else(|| Error::Agent(format!("no agent registered for provider {provider:?}"))),
+                None => match strategy_override {
+                    Some(strategy) => dispatcher.select_agent(&request, agents, strategy).await,
+                    None => agent_selector.select_agent(&request, agents).await,
+                },
+            }
+        };
+
+        match selected {
             Ok(agent) => {
-                match agent.submit(request).await {
+                let submission = tokio::select! {
+                    result = submit_with_hedging(agent, request, agents, config.hedging.budget_for(task.priority)) => result,
+                    () = cancellation_token.cancelled() => {
+                        return Err(Error::Internal(format!("Task {} cancelled", task.id)));
+                    }
+                    () = tokio::time::sleep_until(tokio_deadline) => {
+                        return Err(Error::Timeout(format!("Task {} exceeded its deadline", task.id)));
+                    }
+                };
+                match submission {
                     Ok(response) => return Ok(response),
                     Err(e) => {
                         warn!("Task {} attempt {} failed: {}", task.id, attempts, e);
                         last_error = Some(e);
-                        
+
                         if attempts < config.retry_attempts {
-                            tokio::time::sleep(config.retry_delay).await;
+                            tokio::select! {
+                                () = tokio::time::sleep(config.retry_delay) => {}
+                                () = cancellation_token.cancelled() => {
+                                    return Err(Error::Internal(format!("Task {} cancelled", task.id)));
+                                }
+                                () = tokio::time::sleep_until(tokio_deadline) => {
+                                    return Err(Error::Timeout(format!("Task {} exceeded its deadline", task.id)));
+                                }
+                            }
                         }
                     }
                 }
@@ -296,10 +1430,47 @@ async fn execute_task_with_retry(
             }
         }
     }
-    
+
     Err(last_error.unwrap_or_else(|| Error::Internal("Max retry attempts reached".to_string())))
 }
 
+/// Submit `request` to `primary`. If `hedge_after` is set and the primary
+/// hasn't answered by then, also submit the same request to a different
+/// agent and take whichever responds first; the loser keeps running but its
+/// result is discarded.
+async fn submit_with_hedging(
+    primary: &Box<dyn Agent>,
+    request: LlmRequest,
+    agents: &[Box<dyn Agent>],
+    hedge_after: Option<Duration>,
+) -> Result<LlmResponse> {
+    let Some(hedge_after) = hedge_after else {
+        return primary.submit(request).await;
+    };
+
+    let primary_fut = primary.submit(request.clone());
+    tokio::pin!(primary_fut);
+
+    tokio::select! {
+        result = &mut primary_fut => return result,
+        () = tokio::time::sleep(hedge_after) => {}
+    }
+
+    match agents.iter().find(|a| a.name() != primary.name()) {
+        Some(hedge) => {
+            debug!(
+                "Primary agent {} exceeded its {:?} latency budget for request {}; hedging to {}",
+                primary.name(), hedge_after, request.id, hedge.name()
+            );
+            tokio::select! {
+                result = &mut primary_fut => result,
+                result = hedge.submit(request) => result,
+            }
+        }
+        None => primary_fut.await,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,216 @@
+//! SQLite-backed task scheduler
+//!
+//! `PriorityScheduler` keeps its queue in memory, so a restart loses every
+//! pending and in-flight task. `PersistentScheduler` mirrors the queue into a
+//! SQLite database instead, and recovers anything left `running` from a
+//! previous process back onto the `pending` queue on connect.
+
+use async_trait::async_trait;
+use meta_ai_common::{
+    error::{Error, Result},
+    types::Task,
+};
+use meta_ai_core::orchestrator::{QueueStats, QueuedTaskFilter, QueuedTaskInfo, TaskScheduler};
+use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing::{debug, info, instrument};
+
+/// SQLite-backed task scheduler that survives process restarts.
+pub struct PersistentScheduler {
+    pool: SqlitePool,
+    total_completed: AtomicU64,
+    total_failed: AtomicU64,
+    paused: AtomicBool,
+}
+
+impl PersistentScheduler {
+    /// Connect to the SQLite database at `database_url` (created if it does
+    /// not exist), and recover any tasks left `running` by a previous
+    /// process back onto the pending queue.
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(database_url)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to connect to task queue database: {e}")))?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS tasks (
+                id TEXT PRIMARY KEY,
+                payload TEXT NOT NULL,
+                priority INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                scheduled_at TEXT NOT NULL,
+                not_before TEXT
+            )",
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to initialize task queue schema: {e}")))?;
+
+        let recovered = sqlx::query("UPDATE tasks SET status = 'pending' WHERE status = 'running'")
+            .execute(&pool)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to recover in-flight tasks: {e}")))?
+            .rows_affected();
+
+        if recovered > 0 {
+            info!("Recovered {} in-flight task(s) from a previous run", recovered);
+        }
+
+        Ok(Self {
+            pool,
+            total_completed: AtomicU64::new(0),
+            total_failed: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+        })
+    }
+}
+
+#[async_trait]
+impl TaskScheduler for PersistentScheduler {
+    #[instrument(skip(self, task))]
+    async fn schedule_task(&self, task: Task) -> Result<()> {
+        let payload = serde_json::to_string(&task)?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO tasks (id, payload, priority, status, scheduled_at, not_before)
+             VALUES (?, ?, ?, 'pending', ?, ?)",
+        )
+        .bind(task.id.to_string())
+        .bind(payload)
+        .bind(task.priority as i64)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(task.not_before.map(|t| t.to_rfc3339()))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to persist task {}: {e}", task.id)))?;
+
+        debug!("Persisted task {} with priority {:?}", task.id, task.priority);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn next_task(&self) -> Result<Option<Task>> {
+        if self.is_paused() {
+            return Ok(None);
+        }
+
+        let row = sqlx::query(
+            "SELECT id, payload FROM tasks WHERE status = 'pending'
+             AND (not_before IS NULL OR not_before <= ?)
+             ORDER BY priority DESC, scheduled_at ASC LIMIT 1",
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Internal(format!("failed to fetch next task: {e}")))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let id: String = row
+            .try_get("id")
+            .map_err(|e| Error::Internal(format!("malformed task row: {e}")))?;
+        let payload: String = row
+            .try_get("payload")
+            .map_err(|e| Error::Internal(format!("malformed task row: {e}")))?;
+        let task: Task = serde_json::from_str(&payload)?;
+
+        sqlx::query("UPDATE tasks SET status = 'running' WHERE id = ?")
+            .bind(&id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Internal(format!("failed to mark task {id} running: {e}")))?;
+
+        debug!("Dequeued task {}", id);
+        Ok(Some(task))
+    }
+
+    #[instrument(skip(self, task))]
+    async fn requeue_task(&self, task: Task) -> Result<()> {
+        self.schedule_task(task).await
+    }
+
+    async fn queue_stats(&self) -> QueueStats {
+        let count = |status: &'static str| {
+            let pool = self.pool.clone();
+            async move {
+                sqlx::query("SELECT COUNT(*) as count FROM tasks WHERE status = ?")
+                    .bind(status)
+                    .fetch_one(&pool)
+                    .await
+                    .ok()
+                    .and_then(|row| row.try_get::<i64, _>("count").ok())
+                    .unwrap_or(0) as usize
+            }
+        };
+
+        QueueStats {
+            pending_tasks: count("pending").await,
+            running_tasks: count("running").await,
+            completed_tasks: self.total_completed.load(Ordering::Relaxed) as usize,
+            failed_tasks: self.total_failed.load(Ordering::Relaxed) as usize,
+            average_wait_time_ms: 0.0,
+            average_execution_time_ms: 0.0,
+            p50_wait_time_ms: 0.0,
+            p95_wait_time_ms: 0.0,
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
+        }
+    }
+
+    fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let rows = sqlx::query(
+            "SELECT payload, scheduled_at FROM tasks WHERE status = 'pending'
+             ORDER BY priority DESC, scheduled_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let now = chrono::Utc::now();
+        rows.into_iter()
+            .enumerate()
+            .filter_map(|(position, row)| {
+                let payload: String = row.try_get("payload").ok()?;
+                let scheduled_at: String = row.try_get("scheduled_at").ok()?;
+                let task: Task = serde_json::from_str(&payload).ok()?;
+                if !filter.matches(&task) {
+                    return None;
+                }
+                let scheduled_at = chrono::DateTime::parse_from_rfc3339(&scheduled_at).ok()?.with_timezone(&chrono::Utc);
+                Some(QueuedTaskInfo {
+                    task_id: task.id,
+                    name: task.name.clone(),
+                    priority: task.priority,
+                    position,
+                    wait_time_ms: now
+                        .signed_duration_since(scheduled_at)
+                        .to_std()
+                        .unwrap_or(std::time::Duration::ZERO)
+                        .as_millis() as u64,
+                    tenant: task.tenant.clone(),
+                    provider: task.provider,
+                })
+            })
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+}
@@ -0,0 +1,179 @@
+//! Per-provider dispatch lanes.
+//!
+//! A single global semaphore means one slow or rate-limited provider can
+//! consume every permit, starving tasks bound for faster providers even
+//! though the global concurrency budget isn't actually exhausted.
+//! `ProviderLanes` hands out a separate concurrency permit per
+//! `LlmProvider`, each with its own `max_concurrent` and requests-per-minute
+//! cap, in addition to (not instead of) the orchestrator's global semaphore.
+
+use dashmap::DashMap;
+use meta_ai_common::types::LlmProvider;
+use meta_ai_core::orchestrator::ProviderUtilization;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Window over which a provider's request rate is measured.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// How long to back off before rechecking a provider's rate limit or a pause.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_millis(50);
+
+struct Lane {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    recent_requests: Arc<Mutex<VecDeque<Instant>>>,
+    max_requests_per_minute: u32,
+    paused: Arc<AtomicBool>,
+}
+
+/// Per-provider concurrency and rate limiting, independent of the
+/// orchestrator's global `task_semaphore`.
+pub struct ProviderLanes {
+    lanes: DashMap<LlmProvider, Lane>,
+    default_max_concurrent: usize,
+    max_concurrent_overrides: HashMap<LlmProvider, usize>,
+    default_max_requests_per_minute: u32,
+    rate_limit_overrides: HashMap<LlmProvider, u32>,
+}
+
+impl ProviderLanes {
+    pub fn new(
+        default_max_concurrent: usize,
+        max_concurrent_overrides: HashMap<LlmProvider, usize>,
+        default_max_requests_per_minute: u32,
+        rate_limit_overrides: HashMap<LlmProvider, u32>,
+    ) -> Self {
+        Self {
+            lanes: DashMap::new(),
+            default_max_concurrent,
+            max_concurrent_overrides,
+            default_max_requests_per_minute,
+            rate_limit_overrides,
+        }
+    }
+
+    fn max_concurrent_for(&self, provider: LlmProvider) -> usize {
+        self.max_concurrent_overrides.get(&provider).copied().unwrap_or(self.default_max_concurrent)
+    }
+
+    fn max_requests_per_minute_for(&self, provider: LlmProvider) -> u32 {
+        self.rate_limit_overrides.get(&provider).copied().unwrap_or(self.default_max_requests_per_minute)
+    }
+
+    fn prune_and_count(recent_requests: &Mutex<VecDeque<Instant>>) -> u32 {
+        let now = Instant::now();
+        let mut recent = recent_requests.lock();
+        while let Some(ts) = recent.front() {
+            if now.duration_since(*ts) > RATE_WINDOW {
+                recent.pop_front();
+            } else {
+                break;
+            }
+        }
+        recent.len() as u32
+    }
+
+    fn lane_entry(&self, provider: LlmProvider) -> dashmap::mapref::one::RefMut<'_, LlmProvider, Lane> {
+        self.lanes.entry(provider).or_insert_with(|| Lane {
+            semaphore: Arc::new(Semaphore::new(self.max_concurrent_for(provider))),
+            max_concurrent: self.max_concurrent_for(provider),
+            recent_requests: Arc::new(Mutex::new(VecDeque::new())),
+            max_requests_per_minute: self.max_requests_per_minute_for(provider),
+            paused: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Wait for `provider`'s rate limit to allow another request and for its
+    /// lane not to be paused, then acquire one of its concurrency permits and
+    /// record the request.
+    pub async fn acquire(&self, provider: LlmProvider) -> OwnedSemaphorePermit {
+        let (semaphore, recent_requests, max_requests_per_minute, paused) = {
+            let lane = self.lane_entry(provider);
+            (
+                Arc::clone(&lane.semaphore),
+                Arc::clone(&lane.recent_requests),
+                lane.max_requests_per_minute,
+                Arc::clone(&lane.paused),
+            )
+        };
+
+        loop {
+            if paused.load(Ordering::Relaxed) {
+                tokio::time::sleep(RATE_LIMIT_BACKOFF).await;
+                continue;
+            }
+            if Self::prune_and_count(&recent_requests) < max_requests_per_minute {
+                recent_requests.lock().push_back(Instant::now());
+                break;
+            }
+            tokio::time::sleep(RATE_LIMIT_BACKOFF).await;
+        }
+
+        semaphore.acquire_owned().await.expect("provider lane semaphore is never closed")
+    }
+
+    /// Stop handing out permits for `provider` via `acquire` until `resume`
+    /// is called. Permits already held are unaffected.
+    pub fn pause(&self, provider: LlmProvider) {
+        self.lane_entry(provider).paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resume handing out permits for `provider` after `pause`.
+    pub fn resume(&self, provider: LlmProvider) {
+        self.lane_entry(provider).paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether `provider`'s lane is currently paused.
+    pub fn is_paused(&self, provider: LlmProvider) -> bool {
+        self.lanes.get(&provider).is_some_and(|lane| lane.paused.load(Ordering::Relaxed))
+    }
+
+    /// Snapshot current in-flight/rate utilization for every provider lane
+    /// that has been used so far.
+    pub fn utilization(&self) -> HashMap<LlmProvider, ProviderUtilization> {
+        self.lanes
+            .iter()
+            .map(|entry| {
+                let lane = entry.value();
+                let in_flight = lane.max_concurrent.saturating_sub(lane.semaphore.available_permits());
+                (
+                    *entry.key(),
+                    ProviderUtilization {
+                        in_flight,
+                        max_concurrent: lane.max_concurrent,
+                        requests_per_minute: Self::prune_and_count(&lane.recent_requests),
+                        paused: lane.paused.load(Ordering::Relaxed),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn enforces_per_provider_concurrency_independently() {
+        let lanes = ProviderLanes::new(1, HashMap::new(), 1_000, HashMap::new());
+
+        let openai_permit = lanes.acquire(LlmProvider::OpenAI).await;
+        // Claude's lane is independent, so this must not block even though
+        // OpenAI's single permit is held.
+        let claude_permit = tokio::time::timeout(Duration::from_millis(200), lanes.acquire(LlmProvider::Claude)).await;
+        assert!(claude_permit.is_ok());
+
+        let utilization = lanes.utilization();
+        assert_eq!(utilization[&LlmProvider::OpenAI].in_flight, 1);
+        assert_eq!(utilization[&LlmProvider::Claude].in_flight, 1);
+
+        drop(openai_permit);
+        drop(claude_permit);
+    }
+}
@@ -0,0 +1,101 @@
+//! Evaluates `QualityGate`s against a task's observed metrics and decides
+//! what `GateAction` to apply. `QualityGate`/`ComparisonOperator`/`GateAction`
+//! are defined in `meta-ai-core` but nothing evaluated them before this.
+
+use meta_ai_common::types::LlmProvider;
+use meta_ai_core::evaluation::{ComparisonOperator, GateAction, QualityGate, QualityMetric};
+
+/// Observed metrics for a single completed task, matched against each
+/// gate's `QualityMetric`. Fields are optional since not every metric is
+/// available at every call site (e.g. a single task has no meaningful
+/// rolling accuracy without an `Evaluator` lookup).
+#[derive(Debug, Clone, Default)]
+pub struct GateMetrics {
+    pub accuracy: Option<f64>,
+    pub bug_rate: Option<f64>,
+    pub latency_ms: Option<f64>,
+    pub error_rate: Option<f64>,
+    pub token_usage: Option<f64>,
+    pub cost: Option<f64>,
+    /// SLO burn-rate for this task's `(provider, task type)`, read from
+    /// `TaskDispatcher::latency_burn_rate`. `None` if the caller has no
+    /// `OrchestratorConfig.latency_slo` configured or couldn't determine
+    /// the task's `TaskType`.
+    pub latency_burn_rate: Option<f64>,
+}
+
+impl GateMetrics {
+    fn value(&self, metric: QualityMetric) -> Option<f64> {
+        match metric {
+            QualityMetric::Accuracy => self.accuracy,
+            QualityMetric::BugRate => self.bug_rate,
+            QualityMetric::Latency => self.latency_ms,
+            QualityMetric::ErrorRate => self.error_rate,
+            QualityMetric::TokenUsage => self.token_usage,
+            QualityMetric::Cost => self.cost,
+            QualityMetric::LatencyBurnRate => self.latency_burn_rate,
+        }
+    }
+}
+
+/// What a triggered gate asks the caller to do. Evaluation only decides
+/// this; applying it (failing the task, requeuing it, switching provider)
+/// is the caller's responsibility, since only the caller has the task and
+/// scheduler in hand.
+#[derive(Debug, Clone)]
+pub enum GateOutcome {
+    /// No gate triggered.
+    Pass,
+    Warn { gate: String, description: String },
+    Block { gate: String, description: String },
+    Retry { gate: String, description: String },
+    /// `provider` is the next hop in the caller's fallback chain, if one was
+    /// configured for the task's current provider.
+    Fallback { gate: String, description: String, provider: Option<LlmProvider> },
+}
+
+fn compare(value: f64, operator: ComparisonOperator, threshold: f64) -> bool {
+    match operator {
+        ComparisonOperator::LessThan => value < threshold,
+        ComparisonOperator::LessThanOrEqual => value <= threshold,
+        ComparisonOperator::GreaterThan => value > threshold,
+        ComparisonOperator::GreaterThanOrEqual => value >= threshold,
+        ComparisonOperator::Equal => (value - threshold).abs() < f64::EPSILON,
+        ComparisonOperator::NotEqual => (value - threshold).abs() >= f64::EPSILON,
+    }
+}
+
+/// Evaluate `gates` in order against `metrics`, returning the first one
+/// whose condition triggers (so a `Block` gate listed before a `Warn` gate
+/// takes precedence). A gate whose metric isn't present in `metrics` is
+/// skipped rather than treated as triggered. `fallback_provider` resolves a
+/// triggered `GateAction::Fallback` gate to the next hop in the caller's
+/// fallback chain, if any.
+pub fn evaluate_gates(
+    gates: &[QualityGate],
+    metrics: &GateMetrics,
+    fallback_provider: Option<LlmProvider>,
+) -> GateOutcome {
+    for gate in gates {
+        let Some(value) = metrics.value(gate.metric) else { continue };
+        if !compare(value, gate.operator, gate.threshold) {
+            continue;
+        }
+
+        let description = format!(
+            "quality gate \"{}\" triggered: {:?} {:?} {} (observed {value})",
+            gate.name, gate.metric, gate.operator, gate.threshold
+        );
+
+        return match gate.action {
+            GateAction::Warn => GateOutcome::Warn { gate: gate.name.clone(), description },
+            GateAction::Block => GateOutcome::Block { gate: gate.name.clone(), description },
+            GateAction::Retry => GateOutcome::Retry { gate: gate.name.clone(), description },
+            GateAction::Fallback => {
+                GateOutcome::Fallback { gate: gate.name.clone(), description, provider: fallback_provider }
+            }
+        };
+    }
+
+    GateOutcome::Pass
+}
@@ -0,0 +1,129 @@
+//! Runtime enforcement of `ResourceConstraints` (tokens/minute, memory).
+//!
+//! Nothing in `meta-ai-core` reads `ResourceConstraints.max_tokens_per_minute`
+//! or `max_memory_mb` on its own; `ResourceGovernor` is the piece that does,
+//! tracked alongside each `MetaAIOrchestrator` and consulted by its dispatch
+//! loop before spawning a task.
+
+use meta_ai_core::orchestrator::ResourceConstraints;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Window over which token throughput is measured.
+const TOKEN_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rough per-task memory estimate used in the absence of real OS memory
+/// accounting. This is intentionally coarse; it exists so `max_memory_mb`
+/// has *some* enforceable meaning rather than none at all.
+const DEFAULT_TASK_MEMORY_MB: usize = 64;
+
+/// Tracks rolling token throughput and approximate in-flight memory usage
+/// against a set of `ResourceConstraints`, used to throttle dispatch.
+pub struct ResourceGovernor {
+    constraints: ResourceConstraints,
+    recent_tokens: Mutex<VecDeque<(Instant, u32)>>,
+    in_flight_memory_mb: AtomicUsize,
+}
+
+impl ResourceGovernor {
+    pub fn new(constraints: ResourceConstraints) -> Self {
+        Self {
+            constraints,
+            recent_tokens: Mutex::new(VecDeque::new()),
+            in_flight_memory_mb: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record tokens consumed by a completed request.
+    pub fn record_tokens(&self, tokens: u32) {
+        let now = Instant::now();
+        let mut recent = self.recent_tokens.lock();
+        recent.push_back((now, tokens));
+        while let Some((ts, _)) = recent.front() {
+            if now.duration_since(*ts) > TOKEN_WINDOW {
+                recent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Tokens consumed within the trailing `TOKEN_WINDOW`.
+    pub fn tokens_per_minute(&self) -> u32 {
+        let now = Instant::now();
+        let mut recent = self.recent_tokens.lock();
+        while let Some((ts, _)) = recent.front() {
+            if now.duration_since(*ts) > TOKEN_WINDOW {
+                recent.pop_front();
+            } else {
+                break;
+            }
+        }
+        recent.iter().map(|(_, tokens)| *tokens).sum()
+    }
+
+    /// Call when a task is about to be spawned, reserving its estimated
+    /// memory budget.
+    pub fn task_started(&self) {
+        self.in_flight_memory_mb.fetch_add(DEFAULT_TASK_MEMORY_MB, Ordering::Relaxed);
+    }
+
+    /// Call when a spawned task reaches a terminal state, releasing its
+    /// estimated memory budget.
+    pub fn task_finished(&self) {
+        self.in_flight_memory_mb.fetch_sub(DEFAULT_TASK_MEMORY_MB, Ordering::Relaxed);
+    }
+
+    /// Which constraint, if any, is currently exceeded.
+    pub fn throttle_reason(&self) -> Option<&'static str> {
+        if self.tokens_per_minute() >= self.constraints.max_tokens_per_minute {
+            return Some("tokens_per_minute");
+        }
+        if self.in_flight_memory_mb.load(Ordering::Relaxed) >= self.constraints.max_memory_mb {
+            return Some("memory");
+        }
+        None
+    }
+
+    /// Whether dispatch should currently be throttled.
+    pub fn is_throttled(&self) -> bool {
+        self.throttle_reason().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(max_tokens_per_minute: u32, max_memory_mb: usize) -> ResourceConstraints {
+        ResourceConstraints {
+            max_concurrent_tasks: 100,
+            max_memory_mb,
+            max_cpu_percent: 80.0,
+            max_tokens_per_minute,
+        }
+    }
+
+    #[test]
+    fn throttles_once_token_budget_exceeded() {
+        let governor = ResourceGovernor::new(constraints(100, 8192));
+        assert!(!governor.is_throttled());
+        governor.record_tokens(60);
+        assert!(!governor.is_throttled());
+        governor.record_tokens(60);
+        assert_eq!(governor.throttle_reason(), Some("tokens_per_minute"));
+    }
+
+    #[test]
+    fn throttles_once_memory_budget_exceeded() {
+        let governor = ResourceGovernor::new(constraints(1_000_000, 2 * DEFAULT_TASK_MEMORY_MB));
+        governor.task_started();
+        assert!(!governor.is_throttled());
+        governor.task_started();
+        assert_eq!(governor.throttle_reason(), Some("memory"));
+        governor.task_finished();
+        assert!(!governor.is_throttled());
+    }
+}
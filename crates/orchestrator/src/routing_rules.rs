@@ -0,0 +1,185 @@
+//! Config-driven routing rules.
+//!
+//! `SelectionStrategy::RuleBased` defers provider choice to a
+//! `RoutingRulesEngine`: an ordered (by `RoutingRule.priority`, highest
+//! first) set of rules matching a request's prompt, metadata, or task type
+//! to a preferred provider and a set of capabilities a candidate agent must
+//! have. Rules are seeded from `OrchestratorConfig.routing_rules` at
+//! startup and can be replaced at runtime via `reload`, so an operator can
+//! roll out a new routing policy without restarting the orchestrator.
+
+use meta_ai_common::types::{LlmProvider, LlmRequest};
+use meta_ai_core::agent::{AgentCapabilities, RoutingRule, TaskType};
+use parking_lot::Mutex;
+
+/// A rule's outcome once matched: the provider to prefer and the
+/// capabilities a candidate agent must have, both taken from the winning
+/// `RoutingRule`.
+#[derive(Debug, Clone)]
+pub struct RuleDecision {
+    pub preferred_provider: LlmProvider,
+    pub required_capabilities: Vec<String>,
+}
+
+/// Runtime-reloadable set of `RoutingRule`s, matched highest-`priority`-first.
+pub struct RoutingRulesEngine {
+    rules: Mutex<Vec<RoutingRule>>,
+}
+
+impl RoutingRulesEngine {
+    pub fn new(rules: Vec<RoutingRule>) -> Self {
+        Self { rules: Mutex::new(sorted_by_priority(rules)) }
+    }
+
+    /// Replace the whole rule set, re-sorted by priority. Takes effect for
+    /// the next call to `evaluate`.
+    pub fn reload(&self, rules: Vec<RoutingRule>) {
+        *self.rules.lock() = sorted_by_priority(rules);
+    }
+
+    /// Highest-priority rule whose `pattern` matches `request`/`task_type`,
+    /// or `None` if no rule matches.
+    pub fn evaluate(&self, request: &LlmRequest, task_type: Option<TaskType>) -> Option<RuleDecision> {
+        self.rules.lock().iter().find(|rule| rule_matches(&rule.pattern, request, task_type)).map(|rule| RuleDecision {
+            preferred_provider: rule.preferred_provider,
+            required_capabilities: rule.required_capabilities.clone(),
+        })
+    }
+}
+
+fn sorted_by_priority(mut rules: Vec<RoutingRule>) -> Vec<RoutingRule> {
+    rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+    rules
+}
+
+/// A rule's `pattern` matches one of three ways, checked in order:
+/// - `"task_type:<name>"` matches `task_type`, spelled the same way
+///   `dispatcher::task_type_from_metadata` reads it (e.g. `"code_generation"`).
+/// - `"meta:<key>=<value>"` matches `request.metadata[key] == value`.
+/// - anything else is a case-insensitive substring match against
+///   `request.prompt`.
+fn rule_matches(pattern: &str, request: &LlmRequest, task_type: Option<TaskType>) -> bool {
+    if let Some(wanted) = pattern.strip_prefix("task_type:") {
+        return task_type.is_some_and(|t| task_type_name(t) == wanted);
+    }
+    if let Some(rest) = pattern.strip_prefix("meta:") {
+        return match rest.split_once('=') {
+            Some((key, value)) => request.metadata.get(key).and_then(|v| v.as_str()).is_some_and(|v| v == value),
+            None => false,
+        };
+    }
+    request.prompt.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+fn task_type_name(task_type: TaskType) -> &'static str {
+    match task_type {
+        TaskType::Reasoning => "reasoning",
+        TaskType::CodeGeneration => "code_generation",
+        TaskType::Documentation => "documentation",
+        TaskType::Analysis => "analysis",
+        TaskType::Creative => "creative",
+        TaskType::Translation => "translation",
+        TaskType::Summarization => "summarization",
+        TaskType::QA => "qa",
+    }
+}
+
+/// Whether `capabilities` satisfies a routing rule's named required
+/// capability. Unrecognized names are treated as unsatisfied, so a typo in
+/// config fails closed instead of silently matching every agent.
+pub fn capability_satisfied(capabilities: &AgentCapabilities, name: &str) -> bool {
+    match name {
+        "streaming" => capabilities.supports_streaming,
+        "function_calling" => capabilities.supports_function_calling,
+        "vision" => capabilities.supports_vision,
+        "code_execution" => capabilities.supports_code_execution,
+        "web_search" => capabilities.supports_web_search,
+        "embeddings" => capabilities.supports_embeddings,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_common::types::{LlmParameters, Metadata};
+    use uuid::Uuid;
+
+    fn request(prompt: &str, metadata: Metadata) -> LlmRequest {
+        LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider: LlmProvider::Claude,
+            prompt: prompt.to_string(),
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: vec![],
+            metadata,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_evaluates_highest_priority_match_first() {
+        let engine = RoutingRulesEngine::new(vec![
+            RoutingRule {
+                pattern: "code".to_string(),
+                preferred_provider: LlmProvider::Copilot,
+                required_capabilities: vec![],
+                priority: 1,
+            },
+            RoutingRule {
+                pattern: "code".to_string(),
+                preferred_provider: LlmProvider::Cursor,
+                required_capabilities: vec![],
+                priority: 5,
+            },
+        ]);
+
+        let decision = engine.evaluate(&request("write some code please", Metadata::new()), None).unwrap();
+        assert_eq!(decision.preferred_provider, LlmProvider::Cursor);
+    }
+
+    #[test]
+    fn test_matches_task_type_pattern() {
+        let engine = RoutingRulesEngine::new(vec![RoutingRule {
+            pattern: "task_type:code_generation".to_string(),
+            preferred_provider: LlmProvider::OpenAI,
+            required_capabilities: vec![],
+            priority: 0,
+        }]);
+
+        let decision = engine.evaluate(&request("hello", Metadata::new()), Some(TaskType::CodeGeneration)).unwrap();
+        assert_eq!(decision.preferred_provider, LlmProvider::OpenAI);
+        assert!(engine.evaluate(&request("hello", Metadata::new()), Some(TaskType::Analysis)).is_none());
+    }
+
+    #[test]
+    fn test_matches_metadata_pattern() {
+        let engine = RoutingRulesEngine::new(vec![RoutingRule {
+            pattern: "meta:tenant=acme".to_string(),
+            preferred_provider: LlmProvider::Local,
+            required_capabilities: vec![],
+            priority: 0,
+        }]);
+
+        let mut metadata = Metadata::new();
+        metadata.insert("tenant".to_string(), serde_json::json!("acme"));
+        assert!(engine.evaluate(&request("hello", metadata), None).is_some());
+        assert!(engine.evaluate(&request("hello", Metadata::new()), None).is_none());
+    }
+
+    #[test]
+    fn test_reload_replaces_rules() {
+        let engine = RoutingRulesEngine::new(vec![RoutingRule {
+            pattern: "code".to_string(),
+            preferred_provider: LlmProvider::Copilot,
+            required_capabilities: vec![],
+            priority: 0,
+        }]);
+        assert!(engine.evaluate(&request("some code", Metadata::new()), None).is_some());
+
+        engine.reload(vec![]);
+        assert!(engine.evaluate(&request("some code", Metadata::new()), None).is_none());
+    }
+}
@@ -1,26 +1,73 @@
 //! Task scheduling implementation
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use meta_ai_common::{
     error::{Error, Result},
+    metrics::{QUEUE_DEPTH, SCHEDULING_LATENCY, REQUEUE_COUNTER},
     types::{Task, TaskId, Priority},
 };
-use meta_ai_core::orchestrator::{TaskScheduler, QueueStats};
+use meta_ai_core::orchestrator::{TaskScheduler, QueueStats, QueuedTaskFilter, QueuedTaskInfo};
 use priority_queue::PriorityQueue;
 use std::{
-    collections::HashMap,
-    sync::Arc,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc},
     time::{Duration, Instant},
 };
 use parking_lot::Mutex;
 use tracing::{debug, instrument};
 
+/// Holds tasks whose `Task.not_before` hasn't arrived yet, out of a
+/// scheduler's main queue, so they aren't dispatched early. Used for "run in
+/// an hour" workflows and for retry backoff at the scheduling layer.
+#[derive(Default)]
+struct DelayedQueue {
+    heap: BinaryHeap<Reverse<(DateTime<Utc>, TaskId)>>,
+    tasks: HashMap<TaskId, Task>,
+}
+
+impl DelayedQueue {
+    fn push(&mut self, not_before: DateTime<Utc>, task: Task) {
+        self.heap.push(Reverse((not_before, task.id)));
+        self.tasks.insert(task.id, task);
+    }
+
+    /// Remove and return every task whose `not_before` is now due.
+    fn drain_due(&mut self, now: DateTime<Utc>) -> Vec<Task> {
+        let mut due = Vec::new();
+        while let Some(&Reverse((not_before, task_id))) = self.heap.peek() {
+            if not_before > now {
+                break;
+            }
+            self.heap.pop();
+            if let Some(task) = self.tasks.remove(&task_id) {
+                due.push(task);
+            }
+        }
+        due
+    }
+
+    fn len(&self) -> usize {
+        self.tasks.len()
+    }
+}
+
 /// Priority-based task scheduler
 pub struct PriorityScheduler {
     queue: Arc<Mutex<PriorityQueue<TaskId, PriorityWrapper>>>,
     tasks: Arc<Mutex<HashMap<TaskId, ScheduledTask>>>,
+    delayed: Arc<Mutex<DelayedQueue>>,
     max_queue_size: usize,
     stats: Arc<Mutex<SchedulerStats>>,
+    paused: Arc<AtomicBool>,
+    /// Live count of `queue`'s contents broken down by `Priority`, kept in
+    /// sync with every push/pop so `QUEUE_DEPTH` can be set in O(1) instead
+    /// of scanning `tasks` on every call. Note that `ShardedScheduler` runs
+    /// several independent `PriorityScheduler`s under one set of priority
+    /// labels, so `QUEUE_DEPTH` reflects whichever shard last updated a given
+    /// priority rather than a cross-shard sum.
+    priority_counts: Arc<Mutex<HashMap<Priority, i64>>>,
 }
 
 /// Wrapper for priority to implement Ord
@@ -54,8 +101,12 @@ struct ScheduledTask {
     attempt_count: u32,
 }
 
+/// How many recent wait-time samples to retain per scheduler (or per tenant,
+/// for `FairShareScheduler`) for percentile calculation.
+const MAX_WAIT_SAMPLES: usize = 1000;
+
 /// Scheduler statistics
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 struct SchedulerStats {
     total_scheduled: u64,
     total_completed: u64,
@@ -63,6 +114,41 @@ struct SchedulerStats {
     total_requeued: u64,
     cumulative_wait_time: Duration,
     cumulative_execution_time: Duration,
+    wait_time_samples: VecDeque<Duration>,
+}
+
+impl SchedulerStats {
+    /// Record a task's queue wait time, both for the running average and for
+    /// percentile calculation (bounded to the last `MAX_WAIT_SAMPLES`).
+    fn record_wait(&mut self, wait: Duration) {
+        self.cumulative_wait_time += wait;
+        self.wait_time_samples.push_back(wait);
+        if self.wait_time_samples.len() > MAX_WAIT_SAMPLES {
+            self.wait_time_samples.pop_front();
+        }
+    }
+}
+
+/// Prometheus label for `Priority`, matching the lowercase style other
+/// enum-keyed labels (e.g. `resource="tokens_per_minute"`) use in this crate.
+fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Medium => "medium",
+        Priority::High => "high",
+        Priority::Critical => "critical",
+    }
+}
+
+/// `percentile` in `[0.0, 1.0]`, e.g. `0.5` for P50, `0.95` for P95.
+fn wait_time_percentile_ms(samples: impl Iterator<Item = Duration>, percentile: f64) -> f64 {
+    let mut sorted: Vec<Duration> = samples.collect();
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort();
+    let rank = (((sorted.len() - 1) as f64) * percentile).round() as usize;
+    sorted[rank].as_millis() as f64
 }
 
 impl PriorityScheduler {
@@ -70,11 +156,41 @@ impl PriorityScheduler {
         Self {
             queue: Arc::new(Mutex::new(PriorityQueue::new())),
             tasks: Arc::new(Mutex::new(HashMap::new())),
+            delayed: Arc::new(Mutex::new(DelayedQueue::default())),
             max_queue_size,
             stats: Arc::new(Mutex::new(SchedulerStats::default())),
+            paused: Arc::new(AtomicBool::new(false)),
+            priority_counts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Adjust the live count for `priority` by `delta` (`1` on enqueue, `-1`
+    /// on dequeue) and publish it to `QUEUE_DEPTH`.
+    fn record_queue_depth(&self, priority: Priority, delta: i64) {
+        let mut counts = self.priority_counts.lock();
+        let count = counts.entry(priority).or_insert(0);
+        *count += delta;
+        QUEUE_DEPTH.with_label_values(&[priority_label(priority)]).set((*count).max(0) as f64);
+    }
+
+    /// Move any tasks whose `not_before` has arrived from `delayed` into the
+    /// main queue.
+    fn promote_due(&self) {
+        let due = self.delayed.lock().drain_due(Utc::now());
+        if due.is_empty() {
+            return;
+        }
+        let mut queue = self.queue.lock();
+        let mut tasks = self.tasks.lock();
+        for task in due {
+            let task_id = task.id;
+            let priority = task.priority;
+            tasks.insert(task_id, ScheduledTask { task, scheduled_at: Instant::now(), attempt_count: 0 });
+            queue.push(task_id, Self::create_priority_wrapper(priority));
+            self.record_queue_depth(priority, 1);
         }
     }
-    
+
     fn create_priority_wrapper(priority: Priority) -> PriorityWrapper {
         PriorityWrapper {
             priority,
@@ -89,71 +205,102 @@ impl PriorityScheduler {
 #[async_trait]
 impl TaskScheduler for PriorityScheduler {
     #[instrument(skip(self, task))]
-    async fn schedule_task(&mut self, task: Task) -> Result<()> {
+    async fn schedule_task(&self, task: Task) -> Result<()> {
         let task_id = task.id;
         let priority = task.priority;
-        
+
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_scheduled += 1;
+                debug!("Delayed task {} until {}", task_id, not_before);
+                return Ok(());
+            }
+        }
+
         // Check queue size
         {
             let queue = self.queue.lock();
             if queue.len() >= self.max_queue_size {
-                return Err(Error::Internal("Task queue is full".to_string()));
+                return Err(Error::QueueFull { depth: queue.len(), capacity: self.max_queue_size });
             }
         }
-        
+
         // Add to queue
         {
             let mut queue = self.queue.lock();
             let mut tasks = self.tasks.lock();
-            
+
             let scheduled_task = ScheduledTask {
                 task,
                 scheduled_at: Instant::now(),
                 attempt_count: 0,
             };
-            
+
             tasks.insert(task_id, scheduled_task);
             queue.push(task_id, Self::create_priority_wrapper(priority));
-            
+
             let mut stats = self.stats.lock();
             stats.total_scheduled += 1;
         }
-        
+        self.record_queue_depth(priority, 1);
+
         debug!("Scheduled task {} with priority {:?}", task_id, priority);
         Ok(())
     }
-    
+
     #[instrument(skip(self))]
-    async fn next_task(&mut self) -> Result<Option<Task>> {
+    async fn next_task(&self) -> Result<Option<Task>> {
+        if self.is_paused() {
+            return Ok(None);
+        }
+
+        self.promote_due();
+
         let mut queue = self.queue.lock();
         let mut tasks = self.tasks.lock();
-        
+
         if let Some((task_id, _)) = queue.pop() {
             if let Some(mut scheduled_task) = tasks.remove(&task_id) {
                 scheduled_task.attempt_count += 1;
-                
+
                 // Update stats
                 let wait_time = scheduled_task.scheduled_at.elapsed();
                 let mut stats = self.stats.lock();
-                stats.cumulative_wait_time += wait_time;
-                
+                stats.record_wait(wait_time);
+                drop(stats);
+                self.record_queue_depth(scheduled_task.task.priority, -1);
+                SCHEDULING_LATENCY
+                    .with_label_values(&[priority_label(scheduled_task.task.priority)])
+                    .observe(wait_time.as_secs_f64());
+
                 debug!("Dequeued task {} after {:?} wait", task_id, wait_time);
                 return Ok(Some(scheduled_task.task));
             }
         }
-        
+
         Ok(None)
     }
-    
+
     #[instrument(skip(self, task))]
-    async fn requeue_task(&mut self, task: Task) -> Result<()> {
+    async fn requeue_task(&self, task: Task) -> Result<()> {
         let task_id = task.id;
         let priority = task.priority;
-        
+
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_requeued += 1;
+                REQUEUE_COUNTER.with_label_values(&[priority_label(priority)]).inc();
+                debug!("Requeued task {} with backoff until {}", task_id, not_before);
+                return Ok(());
+            }
+        }
+
         {
             let mut queue = self.queue.lock();
             let mut tasks = self.tasks.lock();
-            
+
             // Get existing scheduled task or create new one
             let scheduled_task = tasks.entry(task_id).or_insert_with(|| {
                 ScheduledTask {
@@ -162,27 +309,29 @@ impl TaskScheduler for PriorityScheduler {
                     attempt_count: 0,
                 }
             });
-            
+
             scheduled_task.task = task;
             scheduled_task.attempt_count += 1;
-            
+
             // Re-add to queue with slightly lower priority for fairness
             queue.push(task_id, Self::create_priority_wrapper(priority));
-            
+
             let mut stats = self.stats.lock();
             stats.total_requeued += 1;
         }
-        
+        self.record_queue_depth(priority, 1);
+        REQUEUE_COUNTER.with_label_values(&[priority_label(priority)]).inc();
+
         debug!("Requeued task {} with priority {:?}", task_id, priority);
         Ok(())
     }
-    
+
     async fn queue_stats(&self) -> QueueStats {
         let queue = self.queue.lock();
         let tasks = self.tasks.lock();
         let stats = self.stats.lock();
-        
-        let pending_tasks = queue.len();
+
+        let pending_tasks = queue.len() + self.delayed.lock().len();
         let running_tasks = 0; // Would need to track this separately
         
         let average_wait_time_ms = if stats.total_scheduled > 0 {
@@ -196,7 +345,7 @@ impl TaskScheduler for PriorityScheduler {
         } else {
             0.0
         };
-        
+
         QueueStats {
             pending_tasks,
             running_tasks,
@@ -204,71 +353,840 @@ impl TaskScheduler for PriorityScheduler {
             failed_tasks: stats.total_failed as usize,
             average_wait_time_ms,
             average_execution_time_ms,
+            p50_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.5),
+            p95_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.95),
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
         }
     }
+
+    fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let queue = self.queue.lock();
+        let tasks = self.tasks.lock();
+        let now = Instant::now();
+
+        queue
+            .clone()
+            .into_sorted_vec()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(position, task_id)| {
+                let scheduled = tasks.get(&task_id)?;
+                filter.matches(&scheduled.task).then(|| QueuedTaskInfo {
+                    task_id,
+                    name: scheduled.task.name.clone(),
+                    priority: scheduled.task.priority,
+                    position,
+                    wait_time_ms: now.duration_since(scheduled.scheduled_at).as_millis() as u64,
+                    tenant: scheduled.task.tenant.clone(),
+                    provider: scheduled.task.provider,
+                })
+            })
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
 }
 
 /// Round-robin scheduler for comparison
 pub struct RoundRobinScheduler {
     queue: Arc<Mutex<VecDeque<Task>>>,
+    delayed: Arc<Mutex<DelayedQueue>>,
     max_queue_size: usize,
     stats: Arc<Mutex<SchedulerStats>>,
+    paused: Arc<AtomicBool>,
 }
 
 impl RoundRobinScheduler {
     pub fn new(max_queue_size: usize) -> Self {
         Self {
             queue: Arc::new(Mutex::new(VecDeque::new())),
+            delayed: Arc::new(Mutex::new(DelayedQueue::default())),
             max_queue_size,
             stats: Arc::new(Mutex::new(SchedulerStats::default())),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Move any tasks whose `not_before` has arrived from `delayed` into the
+    /// main queue.
+    fn promote_due(&self) {
+        let due = self.delayed.lock().drain_due(Utc::now());
+        self.queue.lock().extend(due);
+    }
 }
 
 #[async_trait]
 impl TaskScheduler for RoundRobinScheduler {
-    async fn schedule_task(&mut self, task: Task) -> Result<()> {
+    async fn schedule_task(&self, task: Task) -> Result<()> {
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_scheduled += 1;
+                return Ok(());
+            }
+        }
+
         let mut queue = self.queue.lock();
-        
+
         if queue.len() >= self.max_queue_size {
-            return Err(Error::Internal("Task queue is full".to_string()));
+            return Err(Error::QueueFull { depth: queue.len(), capacity: self.max_queue_size });
         }
-        
+
         queue.push_back(task);
-        
+
         let mut stats = self.stats.lock();
         stats.total_scheduled += 1;
-        
+
         Ok(())
     }
-    
-    async fn next_task(&mut self) -> Result<Option<Task>> {
-        let mut queue = self.queue.lock();
-        Ok(queue.pop_front())
+
+    async fn next_task(&self) -> Result<Option<Task>> {
+        if self.is_paused() {
+            return Ok(None);
+        }
+
+        self.promote_due();
+
+        let task = self.queue.lock().pop_front();
+        if let Some(task) = &task {
+            let wait = Utc::now().signed_duration_since(task.created_at).to_std().unwrap_or(Duration::ZERO);
+            self.stats.lock().record_wait(wait);
+        }
+        Ok(task)
     }
-    
-    async fn requeue_task(&mut self, task: Task) -> Result<()> {
+
+    async fn requeue_task(&self, task: Task) -> Result<()> {
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_requeued += 1;
+                return Ok(());
+            }
+        }
+
         let mut queue = self.queue.lock();
         queue.push_back(task);
-        
+
         let mut stats = self.stats.lock();
         stats.total_requeued += 1;
-        
+
         Ok(())
     }
-    
+
     async fn queue_stats(&self) -> QueueStats {
         let queue = self.queue.lock();
         let stats = self.stats.lock();
-        
+
+        let average_wait_time_ms = if stats.total_scheduled > 0 {
+            stats.cumulative_wait_time.as_millis() as f64 / stats.total_scheduled as f64
+        } else {
+            0.0
+        };
+
+        QueueStats {
+            pending_tasks: queue.len() + self.delayed.lock().len(),
+            running_tasks: 0,
+            completed_tasks: stats.total_completed as usize,
+            failed_tasks: stats.total_failed as usize,
+            average_wait_time_ms,
+            average_execution_time_ms: 0.0,
+            p50_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.5),
+            p95_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.95),
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
+        }
+    }
+
+    fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let queue = self.queue.lock();
+        let now = Utc::now();
+
+        queue
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| filter.matches(task))
+            .skip(offset)
+            .take(limit)
+            .map(|(position, task)| QueuedTaskInfo {
+                task_id: task.id,
+                name: task.name.clone(),
+                priority: task.priority,
+                position,
+                wait_time_ms: now
+                    .signed_duration_since(task.created_at)
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+                    .as_millis() as u64,
+                tenant: task.tenant.clone(),
+                provider: task.provider,
+            })
+            .collect()
+    }
+}
+
+/// Sort key for `EdfScheduler`: orders by earliest absolute deadline first,
+/// falling back to `Priority` (then insertion order) for tasks with no
+/// deadline (`Task.timeout_ms` unset).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DeadlineKey {
+    deadline: Option<DateTime<Utc>>,
+    priority: Priority,
+    timestamp: u64,
+}
+
+impl Ord for DeadlineKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // `PriorityQueue` is a max-heap, so "comes first" must compare as
+        // greater: an earlier deadline, or (absent one) higher priority.
+        match (self.deadline, other.deadline) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (None, None) => self
+                .priority
+                .cmp(&other.priority)
+                .then_with(|| other.timestamp.cmp(&self.timestamp)),
+        }
+    }
+}
+
+impl PartialOrd for DeadlineKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Earliest-deadline-first scheduler. Orders tasks by `created_at +
+/// timeout_ms` (their absolute deadline) so latency-SLO workloads miss fewer
+/// deadlines than a plain priority queue would; tasks without a deadline fall
+/// back to `Priority` ordering, same as `PriorityScheduler`.
+pub struct EdfScheduler {
+    queue: Arc<Mutex<PriorityQueue<TaskId, DeadlineKey>>>,
+    tasks: Arc<Mutex<HashMap<TaskId, ScheduledTask>>>,
+    delayed: Arc<Mutex<DelayedQueue>>,
+    max_queue_size: usize,
+    stats: Arc<Mutex<SchedulerStats>>,
+    paused: Arc<AtomicBool>,
+}
+
+impl EdfScheduler {
+    pub fn new(max_queue_size: usize) -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(PriorityQueue::new())),
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            delayed: Arc::new(Mutex::new(DelayedQueue::default())),
+            max_queue_size,
+            stats: Arc::new(Mutex::new(SchedulerStats::default())),
+            paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Move any tasks whose `not_before` has arrived from `delayed` into the
+    /// main queue.
+    fn promote_due(&self) {
+        let due = self.delayed.lock().drain_due(Utc::now());
+        if due.is_empty() {
+            return;
+        }
+        let mut queue = self.queue.lock();
+        let mut tasks = self.tasks.lock();
+        for task in due {
+            let task_id = task.id;
+            let key = Self::create_deadline_key(&task);
+            tasks.insert(task_id, ScheduledTask { task, scheduled_at: Instant::now(), attempt_count: 0 });
+            queue.push(task_id, key);
+        }
+    }
+
+    fn create_deadline_key(task: &Task) -> DeadlineKey {
+        DeadlineKey {
+            deadline: task
+                .timeout_ms
+                .map(|ms| task.created_at + chrono::Duration::milliseconds(ms as i64)),
+            priority: task.priority,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskScheduler for EdfScheduler {
+    #[instrument(skip(self, task))]
+    async fn schedule_task(&self, task: Task) -> Result<()> {
+        let task_id = task.id;
+
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_scheduled += 1;
+                debug!("Delayed task {} until {}", task_id, not_before);
+                return Ok(());
+            }
+        }
+
+        let key = Self::create_deadline_key(&task);
+
+        let mut queue = self.queue.lock();
+        if queue.len() >= self.max_queue_size {
+            return Err(Error::QueueFull { depth: queue.len(), capacity: self.max_queue_size });
+        }
+
+        let mut tasks = self.tasks.lock();
+        tasks.insert(task_id, ScheduledTask { task, scheduled_at: Instant::now(), attempt_count: 0 });
+        queue.push(task_id, key);
+
+        let mut stats = self.stats.lock();
+        stats.total_scheduled += 1;
+        debug!("Scheduled task {} with deadline {:?}", task_id, key.deadline);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn next_task(&self) -> Result<Option<Task>> {
+        if self.is_paused() {
+            return Ok(None);
+        }
+        self.promote_due();
+
+        let mut queue = self.queue.lock();
+        let mut tasks = self.tasks.lock();
+
+        if let Some((task_id, _)) = queue.pop() {
+            if let Some(mut scheduled_task) = tasks.remove(&task_id) {
+                scheduled_task.attempt_count += 1;
+                let wait_time = scheduled_task.scheduled_at.elapsed();
+                let mut stats = self.stats.lock();
+                stats.record_wait(wait_time);
+                debug!("Dequeued task {} after {:?} wait", task_id, wait_time);
+                return Ok(Some(scheduled_task.task));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[instrument(skip(self, task))]
+    async fn requeue_task(&self, task: Task) -> Result<()> {
+        let task_id = task.id;
+
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task);
+                self.stats.lock().total_requeued += 1;
+                debug!("Requeued task {} with backoff until {}", task_id, not_before);
+                return Ok(());
+            }
+        }
+
+        let key = Self::create_deadline_key(&task);
+
+        let mut queue = self.queue.lock();
+        let mut tasks = self.tasks.lock();
+        let scheduled_task = tasks.entry(task_id).or_insert_with(|| {
+            ScheduledTask { task: task.clone(), scheduled_at: Instant::now(), attempt_count: 0 }
+        });
+        scheduled_task.task = task;
+        scheduled_task.attempt_count += 1;
+        queue.push(task_id, key);
+
+        let mut stats = self.stats.lock();
+        stats.total_requeued += 1;
+        Ok(())
+    }
+
+    async fn queue_stats(&self) -> QueueStats {
+        let queue = self.queue.lock();
+        let stats = self.stats.lock();
+
+        let average_wait_time_ms = if stats.total_scheduled > 0 {
+            stats.cumulative_wait_time.as_millis() as f64 / stats.total_scheduled as f64
+        } else {
+            0.0
+        };
+
+        let average_execution_time_ms = if stats.total_completed > 0 {
+            stats.cumulative_execution_time.as_millis() as f64 / stats.total_completed as f64
+        } else {
+            0.0
+        };
+
         QueueStats {
-            pending_tasks: queue.len(),
+            pending_tasks: queue.len() + self.delayed.lock().len(),
             running_tasks: 0,
             completed_tasks: stats.total_completed as usize,
             failed_tasks: stats.total_failed as usize,
+            average_wait_time_ms,
+            average_execution_time_ms,
+            p50_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.5),
+            p95_wait_time_ms: wait_time_percentile_ms(stats.wait_time_samples.iter().copied(), 0.95),
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
+        }
+    }
+
+    fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let queue = self.queue.lock();
+        let tasks = self.tasks.lock();
+        let now = Instant::now();
+
+        queue
+            .clone()
+            .into_sorted_vec()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(position, task_id)| {
+                let scheduled = tasks.get(&task_id)?;
+                filter.matches(&scheduled.task).then(|| QueuedTaskInfo {
+                    task_id,
+                    name: scheduled.task.name.clone(),
+                    priority: scheduled.task.priority,
+                    position,
+                    wait_time_ms: now.duration_since(scheduled.scheduled_at).as_millis() as u64,
+                    tenant: scheduled.task.tenant.clone(),
+                    provider: scheduled.task.provider,
+                })
+            })
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Weighted fair-share scheduler that interleaves tasks across tenants
+/// (`Task.tenant`) so one noisy tenant can't monopolize the queue. Tasks
+/// without a tenant are grouped under the `None` key, weighted like any
+/// other tenant.
+///
+/// Uses a simple credit-based weighted round robin: each tenant with a
+/// non-empty queue holds a credit balance starting at its configured weight;
+/// `next_task` serves whichever eligible tenant has the most credit
+/// remaining and debits it by one, resetting every tenant's credits once
+/// they've all been exhausted.
+pub struct FairShareScheduler {
+    queues: Arc<Mutex<HashMap<Option<String>, VecDeque<Task>>>>,
+    weights: Arc<Mutex<HashMap<Option<String>, u32>>>,
+    credits: Arc<Mutex<HashMap<Option<String>, u32>>>,
+    stats: Arc<Mutex<HashMap<Option<String>, SchedulerStats>>>,
+    delayed: Arc<Mutex<DelayedQueue>>,
+    default_weight: u32,
+    max_queue_size: usize,
+    paused: Arc<AtomicBool>,
+}
+
+impl FairShareScheduler {
+    pub fn new(max_queue_size: usize, default_weight: u32, tenant_weights: HashMap<String, u32>) -> Self {
+        let weights = tenant_weights.into_iter().map(|(tenant, weight)| (Some(tenant), weight)).collect();
+        Self {
+            queues: Arc::new(Mutex::new(HashMap::new())),
+            weights: Arc::new(Mutex::new(weights)),
+            credits: Arc::new(Mutex::new(HashMap::new())),
+            stats: Arc::new(Mutex::new(HashMap::new())),
+            delayed: Arc::new(Mutex::new(DelayedQueue::default())),
+            default_weight: default_weight.max(1),
+            max_queue_size,
+            paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Move any tasks whose `not_before` has arrived from `delayed` into
+    /// their tenant's main queue.
+    fn promote_due(&self) {
+        let due = self.delayed.lock().drain_due(Utc::now());
+        if due.is_empty() {
+            return;
+        }
+        let mut queues = self.queues.lock();
+        let mut credits = self.credits.lock();
+        for task in due {
+            let tenant = task.tenant.clone();
+            queues.entry(tenant.clone()).or_default().push_back(task);
+            credits.entry(tenant.clone()).or_insert_with(|| self.weight_for(&tenant));
+        }
+    }
+
+    /// Set (or override) a tenant's fair-share weight. Takes effect on the
+    /// next credit reset.
+    pub fn set_tenant_weight(&self, tenant: Option<String>, weight: u32) {
+        self.weights.lock().insert(tenant, weight.max(1));
+    }
+
+    /// Per-tenant queue statistics, keyed the same way `Task.tenant` is
+    /// (`None` for tasks submitted without one).
+    pub fn per_tenant_stats(&self) -> HashMap<Option<String>, QueueStats> {
+        let queues = self.queues.lock();
+        let stats = self.stats.lock();
+
+        queues
+            .iter()
+            .map(|(tenant, queue)| {
+                let tenant_stats = stats.get(tenant).cloned().unwrap_or_default();
+                let average_wait_time_ms = if tenant_stats.total_scheduled > 0 {
+                    tenant_stats.cumulative_wait_time.as_millis() as f64 / tenant_stats.total_scheduled as f64
+                } else {
+                    0.0
+                };
+                (
+                    tenant.clone(),
+                    QueueStats {
+                        pending_tasks: queue.len(),
+                        running_tasks: 0,
+                        completed_tasks: tenant_stats.total_completed as usize,
+                        failed_tasks: tenant_stats.total_failed as usize,
+                        average_wait_time_ms,
+                        average_execution_time_ms: 0.0,
+                        p50_wait_time_ms: wait_time_percentile_ms(tenant_stats.wait_time_samples.iter().copied(), 0.5),
+                        p95_wait_time_ms: wait_time_percentile_ms(tenant_stats.wait_time_samples.iter().copied(), 0.95),
+                        throttled: false,
+                        provider_utilization: HashMap::new(),
+                        paused: self.is_paused(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn weight_for(&self, tenant: &Option<String>) -> u32 {
+        self.weights.lock().get(tenant).copied().unwrap_or(self.default_weight)
+    }
+}
+
+#[async_trait]
+impl TaskScheduler for FairShareScheduler {
+    #[instrument(skip(self, task))]
+    async fn schedule_task(&self, task: Task) -> Result<()> {
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.delayed.lock().push(not_before, task.clone());
+                self.stats.lock().entry(task.tenant.clone()).or_default().total_scheduled += 1;
+                return Ok(());
+            }
+        }
+
+        let tenant = task.tenant.clone();
+
+        let mut queues = self.queues.lock();
+        let total: usize = queues.values().map(VecDeque::len).sum();
+        if total >= self.max_queue_size {
+            return Err(Error::QueueFull { depth: total, capacity: self.max_queue_size });
+        }
+        queues.entry(tenant.clone()).or_default().push_back(task);
+        drop(queues);
+
+        self.credits.lock().entry(tenant.clone()).or_insert_with(|| self.weight_for(&tenant));
+        self.stats.lock().entry(tenant).or_default().total_scheduled += 1;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn next_task(&self) -> Result<Option<Task>> {
+        if self.is_paused() {
+            return Ok(None);
+        }
+
+        self.promote_due();
+
+        let mut queues = self.queues.lock();
+        let eligible: Vec<Option<String>> =
+            queues.iter().filter(|(_, q)| !q.is_empty()).map(|(t, _)| t.clone()).collect();
+        if eligible.is_empty() {
+            return Ok(None);
+        }
+
+        let mut credits = self.credits.lock();
+        if eligible.iter().all(|tenant| credits.get(tenant).copied().unwrap_or(0) == 0) {
+            for tenant in &eligible {
+                let weight = self.weight_for(tenant);
+                credits.insert(tenant.clone(), weight);
+            }
+        }
+
+        let chosen = eligible
+            .iter()
+            .max_by_key(|tenant| credits.get(*tenant).copied().unwrap_or(0))
+            .cloned()
+            .expect("eligible is non-empty");
+
+        if let Some(credit) = credits.get_mut(&chosen) {
+            *credit = credit.saturating_sub(1);
+        }
+        drop(credits);
+
+        let task = queues.get_mut(&chosen).and_then(VecDeque::pop_front);
+        if let Some(task) = &task {
+            let wait = Utc::now().signed_duration_since(task.created_at).to_std().unwrap_or(Duration::ZERO);
+            self.stats.lock().entry(chosen).or_default().record_wait(wait);
+        }
+        Ok(task)
+    }
+
+    #[instrument(skip(self, task))]
+    async fn requeue_task(&self, task: Task) -> Result<()> {
+        if let Some(not_before) = task.not_before {
+            if not_before > Utc::now() {
+                self.stats.lock().entry(task.tenant.clone()).or_default().total_requeued += 1;
+                self.delayed.lock().push(not_before, task);
+                return Ok(());
+            }
+        }
+
+        let tenant = task.tenant.clone();
+        self.queues.lock().entry(tenant.clone()).or_default().push_back(task);
+        self.stats.lock().entry(tenant).or_default().total_requeued += 1;
+        Ok(())
+    }
+
+    async fn queue_stats(&self) -> QueueStats {
+        let queues = self.queues.lock();
+        let stats = self.stats.lock();
+
+        let pending_tasks: usize = queues.values().map(VecDeque::len).sum::<usize>() + self.delayed.lock().len();
+        let (total_scheduled, total_completed, total_failed, cumulative_wait_time): (u64, u64, u64, Duration) = stats
+            .values()
+            .fold((0, 0, 0, Duration::ZERO), |acc, s| {
+                (acc.0 + s.total_scheduled, acc.1 + s.total_completed, acc.2 + s.total_failed, acc.3 + s.cumulative_wait_time)
+            });
+
+        let average_wait_time_ms = if total_scheduled > 0 {
+            cumulative_wait_time.as_millis() as f64 / total_scheduled as f64
+        } else {
+            0.0
+        };
+
+        let all_samples: Vec<Duration> = stats.values().flat_map(|s| s.wait_time_samples.iter().copied()).collect();
+
+        QueueStats {
+            pending_tasks,
+            running_tasks: 0,
+            completed_tasks: total_completed as usize,
+            failed_tasks: total_failed as usize,
+            average_wait_time_ms,
+            average_execution_time_ms: 0.0,
+            p50_wait_time_ms: wait_time_percentile_ms(all_samples.iter().copied(), 0.5),
+            p95_wait_time_ms: wait_time_percentile_ms(all_samples.iter().copied(), 0.95),
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
+        }
+    }
+
+    fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// `position` is approximate: actual dispatch order depends on each
+    /// tenant's credit balance at call time, which shifts between calls, so
+    /// this only reflects a flattened snapshot across tenant queues rather
+    /// than the true interleaved order `next_task` would produce.
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let queues = self.queues.lock();
+        let now = Utc::now();
+
+        queues
+            .values()
+            .flatten()
+            .enumerate()
+            .filter(|(_, task)| filter.matches(task))
+            .skip(offset)
+            .take(limit)
+            .map(|(position, task)| QueuedTaskInfo {
+                task_id: task.id,
+                name: task.name.clone(),
+                priority: task.priority,
+                position,
+                wait_time_ms: now
+                    .signed_duration_since(task.created_at)
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+                    .as_millis() as u64,
+                tenant: task.tenant.clone(),
+                provider: task.provider,
+            })
+            .collect()
+    }
+}
+
+/// Sharded, work-stealing scheduler for high submission rates.
+///
+/// A single `PriorityScheduler` serializes every `schedule_task`/`next_task`
+/// call behind one mutex, which becomes the bottleneck well before a single
+/// machine's CPU or network capacity is exhausted. `ShardedScheduler` spreads
+/// tasks across `N` independent `PriorityScheduler` shards (each with its own
+/// mutex), round-robining `schedule_task` across them. A worker's `next_task`
+/// first checks its "home" shard (rotated the same way as scheduling, so
+/// shards drain roughly in submission order) and, if that shard is empty,
+/// steals from the next shard round, and so on, so idle shards don't leave
+/// work stranded on a busy one.
+pub struct ShardedScheduler {
+    shards: Vec<PriorityScheduler>,
+    next_schedule_shard: AtomicUsize,
+    next_steal_shard: AtomicUsize,
+}
+
+impl ShardedScheduler {
+    /// `shard_count` shards, each with its own `max_queue_size` (so total
+    /// queue capacity is `shard_count * max_queue_size`).
+    pub fn new(shard_count: usize, max_queue_size: usize) -> Self {
+        assert!(shard_count > 0, "ShardedScheduler requires at least one shard");
+        Self {
+            shards: (0..shard_count).map(|_| PriorityScheduler::new(max_queue_size)).collect(),
+            next_schedule_shard: AtomicUsize::new(0),
+            next_steal_shard: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+}
+
+#[async_trait]
+impl TaskScheduler for ShardedScheduler {
+    #[instrument(skip(self, task))]
+    async fn schedule_task(&self, task: Task) -> Result<()> {
+        let shard = self.next_schedule_shard.fetch_add(1, Ordering::Relaxed) % self.shard_count();
+        self.shards[shard].schedule_task(task).await
+    }
+
+    #[instrument(skip(self))]
+    async fn next_task(&self) -> Result<Option<Task>> {
+        let start = self.next_steal_shard.fetch_add(1, Ordering::Relaxed) % self.shard_count();
+
+        for offset in 0..self.shard_count() {
+            let shard = (start + offset) % self.shard_count();
+            if let Some(task) = self.shards[shard].next_task().await? {
+                if offset > 0 {
+                    debug!("Shard {} stole a task from shard {}", start, shard);
+                }
+                return Ok(Some(task));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[instrument(skip(self, task))]
+    async fn requeue_task(&self, task: Task) -> Result<()> {
+        let shard = self.next_schedule_shard.fetch_add(1, Ordering::Relaxed) % self.shard_count();
+        self.shards[shard].requeue_task(task).await
+    }
+
+    async fn queue_stats(&self) -> QueueStats {
+        let mut combined = QueueStats {
+            pending_tasks: 0,
+            running_tasks: 0,
+            completed_tasks: 0,
+            failed_tasks: 0,
             average_wait_time_ms: 0.0,
             average_execution_time_ms: 0.0,
+            p50_wait_time_ms: 0.0,
+            p95_wait_time_ms: 0.0,
+            throttled: false,
+            provider_utilization: HashMap::new(),
+            paused: self.is_paused(),
+        };
+
+        let mut wait_time_total = 0.0;
+        for shard in &self.shards {
+            let stats = shard.queue_stats().await;
+            combined.pending_tasks += stats.pending_tasks;
+            combined.completed_tasks += stats.completed_tasks;
+            combined.failed_tasks += stats.failed_tasks;
+            wait_time_total += stats.average_wait_time_ms;
+            // Each shard only reports its own percentile, not the raw
+            // samples behind it, so there's no way to recompute a true
+            // percentile across shards here; take the max as the
+            // conservative (worst-case) combined figure instead of
+            // silently reporting 0.0.
+            combined.p50_wait_time_ms = combined.p50_wait_time_ms.max(stats.p50_wait_time_ms);
+            combined.p95_wait_time_ms = combined.p95_wait_time_ms.max(stats.p95_wait_time_ms);
         }
+        combined.average_wait_time_ms = wait_time_total / self.shard_count() as f64;
+
+        combined
+    }
+
+    fn pause(&self) {
+        for shard in &self.shards {
+            shard.pause();
+        }
+    }
+
+    fn resume(&self) {
+        for shard in &self.shards {
+            shard.resume();
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.shards.iter().all(|shard| shard.is_paused())
+    }
+
+    /// `position` is approximate: it's the flattened position across shards
+    /// in shard order, not the true round-robin/steal order `next_task`
+    /// would dispatch in.
+    async fn list_queued_tasks(&self, filter: &QueuedTaskFilter, limit: usize, offset: usize) -> Vec<QueuedTaskInfo> {
+        let mut combined = Vec::new();
+        let mut base_position = 0;
+        for shard in &self.shards {
+            let mut shard_tasks = shard.list_queued_tasks(filter, usize::MAX, 0).await;
+            for info in &mut shard_tasks {
+                info.position += base_position;
+            }
+            base_position += shard.queue_stats().await.pending_tasks;
+            combined.extend(shard_tasks);
+        }
+        combined.into_iter().skip(offset).take(limit).collect()
     }
 }
 
@@ -276,7 +1194,7 @@ impl TaskScheduler for RoundRobinScheduler {
 mod tests {
     use super::*;
     use uuid::Uuid;
-    
+
     #[tokio::test]
     async fn test_priority_scheduling() {
         let mut scheduler = PriorityScheduler::new(100);
@@ -307,6 +1225,308 @@ mod tests {
         let next = scheduler.next_task().await.unwrap().unwrap();
         assert_eq!(next.id, low_priority_task.id);
     }
+
+    #[tokio::test]
+    async fn test_edf_scheduling_orders_by_deadline() {
+        let mut scheduler = EdfScheduler::new(100);
+
+        let urgent_task = Task {
+            id: Uuid::new_v4(),
+            name: "Due soon".to_string(),
+            priority: Priority::Low,
+            timeout_ms: Some(100),
+            ..Default::default()
+        };
+
+        let relaxed_task = Task {
+            id: Uuid::new_v4(),
+            name: "Due later".to_string(),
+            priority: Priority::Critical,
+            timeout_ms: Some(60_000),
+            ..Default::default()
+        };
+
+        // Scheduled in reverse order; the earlier deadline must still win
+        // even though it has the lower priority.
+        scheduler.schedule_task(relaxed_task.clone()).await.unwrap();
+        scheduler.schedule_task(urgent_task.clone()).await.unwrap();
+
+        let next = scheduler.next_task().await.unwrap().unwrap();
+        assert_eq!(next.id, urgent_task.id);
+
+        let next = scheduler.next_task().await.unwrap().unwrap();
+        assert_eq!(next.id, relaxed_task.id);
+    }
+
+    #[tokio::test]
+    async fn test_edf_scheduling_falls_back_to_priority() {
+        let mut scheduler = EdfScheduler::new(100);
+
+        let high_priority_task = Task {
+            id: Uuid::new_v4(),
+            name: "High priority, no deadline".to_string(),
+            priority: Priority::High,
+            ..Default::default()
+        };
+
+        let low_priority_task = Task {
+            id: Uuid::new_v4(),
+            name: "Low priority, no deadline".to_string(),
+            priority: Priority::Low,
+            ..Default::default()
+        };
+
+        scheduler.schedule_task(low_priority_task.clone()).await.unwrap();
+        scheduler.schedule_task(high_priority_task.clone()).await.unwrap();
+
+        let next = scheduler.next_task().await.unwrap().unwrap();
+        assert_eq!(next.id, high_priority_task.id);
+    }
+
+    #[tokio::test]
+    async fn test_fair_share_interleaves_by_weight() {
+        let mut weights = HashMap::new();
+        weights.insert("gold".to_string(), 2);
+        weights.insert("noisy".to_string(), 1);
+        let mut scheduler = FairShareScheduler::new(100, 1, weights);
+
+        for _ in 0..4 {
+            scheduler
+                .schedule_task(Task { tenant: Some("noisy".to_string()), ..Default::default() })
+                .await
+                .unwrap();
+        }
+        for _ in 0..4 {
+            scheduler
+                .schedule_task(Task { tenant: Some("gold".to_string()), ..Default::default() })
+                .await
+                .unwrap();
+        }
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            let task = scheduler.next_task().await.unwrap().unwrap();
+            order.push(task.tenant.unwrap());
+        }
+
+        // "gold" has twice the weight of "noisy", so it should win 2 of
+        // every 3 picks once both queues are non-empty, rather than "noisy"
+        // (scheduled first) monopolizing the front of a single FIFO queue.
+        assert!(order.contains(&"gold".to_string()));
+        assert!(order.contains(&"noisy".to_string()));
+        assert_ne!(order, vec!["noisy", "noisy", "noisy", "noisy"]);
+    }
+
+    #[tokio::test]
+    async fn test_fair_share_per_tenant_stats() {
+        let mut scheduler = FairShareScheduler::new(100, 1, HashMap::new());
+        scheduler
+            .schedule_task(Task { tenant: Some("a".to_string()), ..Default::default() })
+            .await
+            .unwrap();
+        scheduler
+            .schedule_task(Task { tenant: Some("b".to_string()), ..Default::default() })
+            .await
+            .unwrap();
+        scheduler
+            .schedule_task(Task { tenant: Some("b".to_string()), ..Default::default() })
+            .await
+            .unwrap();
+
+        let stats = scheduler.per_tenant_stats();
+        assert_eq!(stats[&Some("a".to_string())].pending_tasks, 1);
+        assert_eq!(stats[&Some("b".to_string())].pending_tasks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sharded_scheduler_steals_from_other_shards() {
+        let scheduler = ShardedScheduler::new(4, 100);
+
+        // All 4 tasks land on different shards (round-robin scheduling), so
+        // dequeuing all of them requires work-stealing across shards rather
+        // than draining a single one.
+        for _ in 0..4 {
+            scheduler.schedule_task(Task::default()).await.unwrap();
+        }
+
+        let mut drained = 0;
+        while scheduler.next_task().await.unwrap().is_some() {
+            drained += 1;
+        }
+        assert_eq!(drained, 4);
+    }
+
+    #[tokio::test]
+    async fn test_sharded_scheduler_aggregates_stats_and_pause() {
+        let scheduler = ShardedScheduler::new(3, 100);
+        scheduler.schedule_task(Task::default()).await.unwrap();
+        scheduler.schedule_task(Task::default()).await.unwrap();
+
+        let stats = scheduler.queue_stats().await;
+        assert_eq!(stats.pending_tasks, 2);
+        assert!(!stats.paused);
+
+        scheduler.pause();
+        assert!(scheduler.is_paused());
+        assert!(scheduler.next_task().await.unwrap().is_none());
+
+        scheduler.resume();
+        assert!(!scheduler.is_paused());
+        assert!(scheduler.next_task().await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_list_queued_tasks_filters_and_paginates() {
+        let scheduler = PriorityScheduler::new(100);
+
+        let high_priority_task = Task {
+            id: Uuid::new_v4(),
+            name: "High priority".to_string(),
+            priority: Priority::High,
+            ..Default::default()
+        };
+        let low_priority_task = Task {
+            id: Uuid::new_v4(),
+            name: "Low priority".to_string(),
+            priority: Priority::Low,
+            ..Default::default()
+        };
+        scheduler.schedule_task(low_priority_task.clone()).await.unwrap();
+        scheduler.schedule_task(high_priority_task.clone()).await.unwrap();
+
+        let all = scheduler.list_queued_tasks(&QueuedTaskFilter::default(), 100, 0).await;
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].task_id, high_priority_task.id);
+        assert_eq!(all[0].position, 0);
+        assert_eq!(all[1].task_id, low_priority_task.id);
+        assert_eq!(all[1].position, 1);
+
+        let filtered = scheduler
+            .list_queued_tasks(&QueuedTaskFilter { priority: Some(Priority::Low), ..Default::default() }, 100, 0)
+            .await;
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].task_id, low_priority_task.id);
+
+        let page = scheduler.list_queued_tasks(&QueuedTaskFilter::default(), 1, 1).await;
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].task_id, low_priority_task.id);
+    }
+
+    fn priority_strategy() -> impl proptest::strategy::Strategy<Value = Priority> {
+        use proptest::prelude::*;
+        prop_oneof![
+            Just(Priority::Low),
+            Just(Priority::Medium),
+            Just(Priority::High),
+            Just(Priority::Critical),
+        ]
+    }
+
+    fn task_with_priority(priority: Priority) -> Task {
+        Task { id: Uuid::new_v4(), priority, ..Default::default() }
+    }
+
+    proptest::proptest! {
+        /// Every task scheduled onto a `PriorityScheduler` comes back out of
+        /// `next_task` exactly once, however many tasks or priorities are
+        /// thrown at it.
+        #[test]
+        fn priority_scheduler_loses_no_tasks(priorities in proptest::collection::vec(priority_strategy(), 0..50)) {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let result: Result<(), proptest::test_runner::TestCaseError> = rt.block_on(async {
+                let scheduler = PriorityScheduler::new(usize::MAX);
+                let mut scheduled: Vec<TaskId> = Vec::new();
+                for priority in priorities {
+                    let task = task_with_priority(priority);
+                    scheduled.push(task.id);
+                    scheduler.schedule_task(task).await.unwrap();
+                }
+
+                let mut drained = Vec::new();
+                while let Some(task) = scheduler.next_task().await.unwrap() {
+                    drained.push(task.id);
+                }
+
+                scheduled.sort();
+                drained.sort();
+                proptest::prop_assert_eq!(scheduled, drained);
+                Ok(())
+            });
+            result?;
+        }
+
+        /// `next_task` never dequeues a lower-priority task while a
+        /// higher-priority one is still queued: the sequence of dequeued
+        /// priorities is non-increasing.
+        #[test]
+        fn priority_scheduler_respects_priority_ordering(priorities in proptest::collection::vec(priority_strategy(), 1..50)) {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let result: Result<(), proptest::test_runner::TestCaseError> = rt.block_on(async {
+                let scheduler = PriorityScheduler::new(usize::MAX);
+                for priority in priorities {
+                    scheduler.schedule_task(task_with_priority(priority)).await.unwrap();
+                }
+
+                let mut dequeued = Vec::new();
+                while let Some(task) = scheduler.next_task().await.unwrap() {
+                    dequeued.push(task.priority);
+                }
+
+                for pair in dequeued.windows(2) {
+                    proptest::prop_assert!(pair[0] >= pair[1]);
+                }
+                Ok(())
+            });
+            result?;
+        }
+
+        /// `FairShareScheduler` never starves a lower-weighted tenant behind
+        /// a higher-weighted one: both tenants are served at least once
+        /// within one credit-reset round (`max(weight_a, weight_b) + 2`
+        /// draws), rather than the higher-weighted tenant monopolizing the
+        /// queue indefinitely.
+        #[test]
+        fn fair_share_scheduler_avoids_starvation(
+            a_count in 1..20usize,
+            b_count in 1..20usize,
+            a_weight in 1..5u32,
+            b_weight in 1..5u32,
+        ) {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let result: Result<(), proptest::test_runner::TestCaseError> = rt.block_on(async {
+                let mut weights = HashMap::new();
+                weights.insert("a".to_string(), a_weight);
+                weights.insert("b".to_string(), b_weight);
+                let scheduler = FairShareScheduler::new(usize::MAX, 1, weights);
+
+                for _ in 0..a_count {
+                    scheduler
+                        .schedule_task(Task { tenant: Some("a".to_string()), ..Default::default() })
+                        .await
+                        .unwrap();
+                }
+                for _ in 0..b_count {
+                    scheduler
+                        .schedule_task(Task { tenant: Some("b".to_string()), ..Default::default() })
+                        .await
+                        .unwrap();
+                }
+
+                let round = a_weight.max(b_weight) as usize + 2;
+                let mut served = std::collections::HashSet::new();
+                for _ in 0..round {
+                    if let Some(task) = scheduler.next_task().await.unwrap() {
+                        served.insert(task.tenant);
+                    }
+                }
+
+                proptest::prop_assert!(served.contains(&Some("a".to_string())));
+                proptest::prop_assert!(served.contains(&Some("b".to_string())));
+                Ok(())
+            });
+            result?;
+        }
+    }
 }
 
 use std::collections::VecDeque;
\ No newline at end of file
@@ -0,0 +1,114 @@
+//! Latency/memory trade-off of `EmbeddingCache`'s optional `f16` compression
+//! (see `QdrantRagEngine::with_embedding_model` /
+//! `RagConfig.embedding_cache_f16`). Run with `cargo bench -p meta-ai-rag`.
+//!
+//! `f16` storage halves the cache's memory footprint per cached embedding,
+//! at the cost of a conversion on every hit/insert and a small precision
+//! loss. This prints the average cosine-similarity error that loss
+//! introduces once, then benchmarks insert/get throughput for both modes so
+//! the conversion overhead is visible alongside the memory savings.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use meta_ai_core::rag::EmbeddingCache;
+use rand::Rng;
+
+const DIMENSION: usize = 768;
+const CACHE_SIZE: usize = 10_000;
+
+fn random_embedding(rng: &mut impl Rng) -> Vec<f32> {
+    (0..DIMENSION).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    dot / (norm_a * norm_b)
+}
+
+/// Print the average cosine-similarity drift `f16` rounding introduces
+/// across a sample of random embeddings, so the recall/latency trade-off in
+/// this file's name is visible without needing a live Qdrant collection to
+/// measure recall against.
+fn report_precision_loss() {
+    let mut rng = rand::thread_rng();
+    let samples = 200;
+
+    let f32_cache = EmbeddingCache::with_options(samples, None, false);
+    let f16_cache = EmbeddingCache::with_options(samples, None, true);
+
+    let mut total_similarity = 0.0;
+    for i in 0..samples {
+        let embedding = random_embedding(&mut rng);
+        let key = i.to_string();
+        f32_cache.insert(key.clone(), embedding.clone());
+        f16_cache.insert(key.clone(), embedding.clone());
+
+        let roundtripped = f16_cache.get(&key).expect("just inserted");
+        total_similarity += cosine_similarity(&embedding, &roundtripped);
+    }
+
+    println!(
+        "f16 round-trip average cosine similarity over {samples} random {DIMENSION}-dim \
+         embeddings: {:.6} (1.0 = no loss)",
+        total_similarity / samples as f32
+    );
+}
+
+fn bench_insert(c: &mut Criterion) {
+    report_precision_loss();
+
+    let mut rng = rand::thread_rng();
+    let embeddings: Vec<Vec<f32>> = (0..CACHE_SIZE).map(|_| random_embedding(&mut rng)).collect();
+
+    let mut group = c.benchmark_group("embedding_cache_insert");
+    group.bench_function("f32", |b| {
+        b.iter(|| {
+            let cache = EmbeddingCache::with_options(CACHE_SIZE, None, false);
+            for (i, embedding) in embeddings.iter().enumerate() {
+                cache.insert(i.to_string(), embedding.clone());
+            }
+        });
+    });
+    group.bench_function("f16", |b| {
+        b.iter(|| {
+            let cache = EmbeddingCache::with_options(CACHE_SIZE, None, true);
+            for (i, embedding) in embeddings.iter().enumerate() {
+                cache.insert(i.to_string(), embedding.clone());
+            }
+        });
+    });
+    group.finish();
+}
+
+fn bench_get(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let embeddings: Vec<Vec<f32>> = (0..CACHE_SIZE).map(|_| random_embedding(&mut rng)).collect();
+
+    let f32_cache = EmbeddingCache::with_options(CACHE_SIZE, None, false);
+    let f16_cache = EmbeddingCache::with_options(CACHE_SIZE, None, true);
+    for (i, embedding) in embeddings.iter().enumerate() {
+        f32_cache.insert(i.to_string(), embedding.clone());
+        f16_cache.insert(i.to_string(), embedding.clone());
+    }
+
+    let mut group = c.benchmark_group("embedding_cache_get");
+    group.bench_function("f32", |b| {
+        b.iter(|| {
+            for i in 0..CACHE_SIZE {
+                criterion::black_box(f32_cache.get(&i.to_string()));
+            }
+        });
+    });
+    group.bench_function("f16", |b| {
+        b.iter(|| {
+            for i in 0..CACHE_SIZE {
+                criterion::black_box(f16_cache.get(&i.to_string()));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_get);
+criterion_main!(benches);
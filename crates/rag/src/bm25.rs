@@ -0,0 +1,324 @@
+//! In-process Okapi BM25 keyword index, run alongside the vector store so
+//! hybrid search (see `engine::QdrantRagEngine::search`) doesn't lose exact
+//! matches (error codes, identifiers, literals) that embeddings tend to blur.
+
+use meta_ai_common::types::Metadata;
+use meta_ai_core::rag::MetadataFilter;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// BM25's term-frequency saturation constant. 1.2 is the standard default.
+const DEFAULT_K1: f32 = 1.2;
+/// BM25's document-length normalization constant. 0.75 is the standard default.
+const DEFAULT_B: f32 = 0.75;
+
+static TOKEN_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9_]+").expect("static regex"));
+
+fn tokenize(text: &str) -> Vec<String> {
+    TOKEN_PATTERN
+        .find_iter(text)
+        .map(|m| m.as_str().to_lowercase())
+        .collect()
+}
+
+#[derive(Default, Clone)]
+struct DocumentStats {
+    term_frequencies: HashMap<String, u32>,
+    length: usize,
+    metadata: Metadata,
+}
+
+/// Okapi BM25 index over a set of documents, keyed by the same document ID
+/// `RagEngine` uses, so a BM25 hit can be joined back against a vector hit.
+pub struct Bm25Index {
+    k1: f32,
+    b: f32,
+    documents: HashMap<String, DocumentStats>,
+    document_frequency: HashMap<String, usize>,
+    total_length: u64,
+}
+
+impl Bm25Index {
+    /// Build an index using the standard `k1 = 1.2`, `b = 0.75` defaults.
+    pub fn new() -> Self {
+        Self {
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+            documents: HashMap::new(),
+            document_frequency: HashMap::new(),
+            total_length: 0,
+        }
+    }
+
+    /// Index (or re-index) `content` under `id`, replacing any previous entry
+    /// for the same ID. `metadata` is kept alongside the term statistics so
+    /// `search` can apply a `MetadataFilter` without a separate lookup.
+    pub fn index_document(&mut self, id: &str, content: &str, metadata: Metadata) {
+        self.remove_document(id);
+
+        let tokens = tokenize(content);
+        let mut term_frequencies = HashMap::new();
+        for token in &tokens {
+            *term_frequencies.entry(token.clone()).or_insert(0u32) += 1;
+        }
+        for term in term_frequencies.keys() {
+            *self.document_frequency.entry(term.clone()).or_insert(0) += 1;
+        }
+
+        self.total_length += tokens.len() as u64;
+        self.documents.insert(
+            id.to_string(),
+            DocumentStats {
+                term_frequencies,
+                length: tokens.len(),
+                metadata,
+            },
+        );
+    }
+
+    /// Remove a document from the index, e.g. on `RagEngine::delete_document`.
+    pub fn remove_document(&mut self, id: &str) {
+        let Some(stats) = self.documents.remove(id) else {
+            return;
+        };
+        self.total_length = self.total_length.saturating_sub(stats.length as u64);
+        for term in stats.term_frequencies.keys() {
+            if let Some(count) = self.document_frequency.get_mut(term) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    self.document_frequency.remove(term);
+                }
+            }
+        }
+    }
+
+    /// Drop every indexed document, e.g. on `RagEngine::clear_collection`.
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.document_frequency.clear();
+        self.total_length = 0;
+    }
+
+    /// Score every indexed document against `query`, skipping any whose
+    /// metadata doesn't match `filter`, and return the `top_k` highest-scoring
+    /// document IDs with their BM25 score, descending.
+    pub fn search(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+    ) -> Vec<(String, f32)> {
+        let query_terms = tokenize(query);
+        if self.documents.is_empty() || query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        let doc_count = self.documents.len() as f32;
+        let avg_length = self.total_length as f32 / doc_count;
+
+        let idf: HashMap<&str, f32> = query_terms
+            .iter()
+            .map(|term| {
+                let df = self
+                    .document_frequency
+                    .get(term.as_str())
+                    .copied()
+                    .unwrap_or(0) as f32;
+                (term.as_str(), ((doc_count - df + 0.5) / (df + 0.5) + 1.0).ln())
+            })
+            .collect();
+
+        let mut scores: Vec<(String, f32)> = self
+            .documents
+            .iter()
+            .filter(|(_, stats)| filter.map_or(true, |filter| filter.matches(&stats.metadata)))
+            .filter_map(|(id, stats)| {
+                let score: f32 = query_terms
+                    .iter()
+                    .map(|term| {
+                        let tf = stats.term_frequencies.get(term).copied().unwrap_or(0) as f32;
+                        if tf == 0.0 {
+                            return 0.0;
+                        }
+                        let idf = idf.get(term.as_str()).copied().unwrap_or(0.0);
+                        let norm = 1.0 - self.b + self.b * (stats.length as f32 / avg_length);
+                        idf * (tf * (self.k1 + 1.0)) / (tf + self.k1 * norm)
+                    })
+                    .sum();
+                (score > 0.0).then(|| (id.clone(), score))
+            })
+            .collect();
+
+        scores.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        scores.truncate(top_k);
+        scores
+    }
+}
+
+/// Combine a BM25 ranking and a vector-similarity ranking (both `(document_id,
+/// score)`, highest score first) into a single fused ranking, per
+/// `FusionStrategy`.
+pub fn fuse(
+    bm25_results: &[(String, f32)],
+    vector_results: &[(String, f32)],
+    strategy: &meta_ai_common::config::FusionStrategy,
+) -> Vec<(String, f32)> {
+    use meta_ai_common::config::FusionStrategy;
+
+    match *strategy {
+        FusionStrategy::ReciprocalRank { k } => {
+            let mut fused: HashMap<String, f32> = HashMap::new();
+            for (rank, (id, _)) in bm25_results.iter().enumerate() {
+                *fused.entry(id.clone()).or_insert(0.0) += 1.0 / (k + rank as f32 + 1.0);
+            }
+            for (rank, (id, _)) in vector_results.iter().enumerate() {
+                *fused.entry(id.clone()).or_insert(0.0) += 1.0 / (k + rank as f32 + 1.0);
+            }
+            let mut fused: Vec<(String, f32)> = fused.into_iter().collect();
+            fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            fused
+        }
+        FusionStrategy::Weighted { bm25_weight, vector_weight } => {
+            let mut fused: HashMap<String, f32> = HashMap::new();
+            for (id, score) in normalize(bm25_results) {
+                *fused.entry(id).or_insert(0.0) += score * bm25_weight;
+            }
+            for (id, score) in normalize(vector_results) {
+                *fused.entry(id).or_insert(0.0) += score * vector_weight;
+            }
+            let mut fused: Vec<(String, f32)> = fused.into_iter().collect();
+            fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            fused
+        }
+    }
+}
+
+/// Min-max normalize a ranked result list's scores into `[0, 1]` so BM25 and
+/// cosine-similarity scores (on entirely different scales) can be weighted
+/// together. A list with no score spread normalizes to `1.0` for every entry.
+fn normalize(results: &[(String, f32)]) -> Vec<(String, f32)> {
+    let Some(min) = results.iter().map(|(_, s)| *s).fold(None, |acc, s| {
+        Some(acc.map_or(s, |m: f32| m.min(s)))
+    }) else {
+        return Vec::new();
+    };
+    let max = results.iter().map(|(_, s)| *s).fold(min, f32::max);
+    let range = max - min;
+
+    results
+        .iter()
+        .map(|(id, score)| {
+            let normalized = if range > 0.0 { (score - min) / range } else { 1.0 };
+            (id.clone(), normalized)
+        })
+        .collect()
+}
+
+impl Default for Bm25Index {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_core::rag::FilterValue;
+
+    #[test]
+    fn search_ranks_documents_with_more_term_occurrences_higher() {
+        let mut index = Bm25Index::new();
+        index.index_document("a", "the quick brown fox jumps over the lazy dog", Metadata::new());
+        index.index_document("b", "the quick fox", Metadata::new());
+
+        let results = index.search("quick fox", 10, None);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "b");
+    }
+
+    #[test]
+    fn search_with_no_matching_terms_returns_empty() {
+        let mut index = Bm25Index::new();
+        index.index_document("a", "the quick brown fox", Metadata::new());
+
+        assert!(index.search("nonexistent", 10, None).is_empty());
+    }
+
+    #[test]
+    fn search_respects_metadata_filter() {
+        let mut index = Bm25Index::new();
+        let mut meta_a = Metadata::new();
+        meta_a.insert("lang".to_string(), serde_json::json!("en"));
+        let mut meta_b = Metadata::new();
+        meta_b.insert("lang".to_string(), serde_json::json!("fr"));
+
+        index.index_document("a", "quick fox", meta_a);
+        index.index_document("b", "quick fox", meta_b);
+
+        let filter = MetadataFilter::Eq("lang".to_string(), FilterValue::String("en".to_string()));
+        let results = index.search("quick fox", 10, Some(&filter));
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "a");
+    }
+
+    #[test]
+    fn remove_document_excludes_it_from_future_searches() {
+        let mut index = Bm25Index::new();
+        index.index_document("a", "quick fox", Metadata::new());
+        index.remove_document("a");
+
+        assert!(index.search("quick fox", 10, None).is_empty());
+    }
+
+    #[test]
+    fn clear_removes_every_document() {
+        let mut index = Bm25Index::new();
+        index.index_document("a", "quick fox", Metadata::new());
+        index.index_document("b", "lazy dog", Metadata::new());
+        index.clear();
+
+        assert!(index.search("quick", 10, None).is_empty());
+        assert!(index.search("lazy", 10, None).is_empty());
+    }
+
+    #[test]
+    fn fuse_reciprocal_rank_favors_documents_ranked_highly_in_both_lists() {
+        let bm25 = vec![("a".to_string(), 5.0), ("b".to_string(), 3.0)];
+        let vector = vec![("b".to_string(), 0.9), ("a".to_string(), 0.1)];
+
+        let fused = fuse(&bm25, &vector, &meta_ai_common::config::FusionStrategy::ReciprocalRank { k: 60.0 });
+
+        assert_eq!(fused.len(), 2);
+        assert!((fused[0].1 - fused[1].1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fuse_weighted_combines_normalized_scores() {
+        let bm25 = vec![("a".to_string(), 10.0), ("b".to_string(), 0.0)];
+        let vector = vec![("a".to_string(), 0.0), ("b".to_string(), 1.0)];
+
+        let fused = fuse(
+            &bm25,
+            &vector,
+            &meta_ai_common::config::FusionStrategy::Weighted { bm25_weight: 0.5, vector_weight: 0.5 },
+        );
+        let scores: HashMap<String, f32> = fused.into_iter().collect();
+
+        assert!((scores["a"] - 0.5).abs() < 1e-6);
+        assert!((scores["b"] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normalize_with_no_score_spread_yields_all_ones() {
+        let results = vec![("a".to_string(), 2.0), ("b".to_string(), 2.0)];
+        let normalized = normalize(&results);
+        assert!(normalized.iter().all(|(_, s)| *s == 1.0));
+    }
+}
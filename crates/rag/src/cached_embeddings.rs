@@ -0,0 +1,164 @@
+//! Caching decorator for `EmbeddingModel`: wraps another model and skips
+//! re-embedding any text it's already seen recently (see `EmbeddingCache`).
+
+use async_trait::async_trait;
+use meta_ai_common::error::Result;
+use meta_ai_common::types::Embedding;
+use meta_ai_core::rag::{EmbeddingCache, EmbeddingCacheMetrics, EmbeddingModel};
+use std::sync::Arc;
+
+/// `EmbeddingModel` that serves repeated texts out of an `EmbeddingCache`
+/// instead of re-running `inner` on them.
+pub struct CachedEmbeddingModel {
+    inner: Arc<dyn EmbeddingModel>,
+    cache: EmbeddingCache,
+}
+
+impl CachedEmbeddingModel {
+    pub fn new(
+        inner: Arc<dyn EmbeddingModel>,
+        max_size: usize,
+        ttl: Option<std::time::Duration>,
+        f16: bool,
+    ) -> Self {
+        Self { inner, cache: EmbeddingCache::with_options(max_size, ttl, f16) }
+    }
+
+    /// Cache hit/miss counts accumulated so far.
+    pub fn cache_metrics(&self) -> EmbeddingCacheMetrics {
+        self.cache.metrics()
+    }
+}
+
+#[async_trait]
+impl EmbeddingModel for CachedEmbeddingModel {
+    async fn embed(&self, text: &str) -> Result<Embedding> {
+        if let Some(embedding) = self.cache.get(text) {
+            return Ok(embedding);
+        }
+        let embedding = self.inner.embed(text).await?;
+        self.cache.insert(text.to_string(), embedding.clone());
+        Ok(embedding)
+    }
+
+    async fn embed_batch(&self, texts: Vec<&str>) -> Result<Vec<Embedding>> {
+        let mut embeddings = vec![None; texts.len()];
+        let mut misses = Vec::new();
+
+        for (index, text) in texts.iter().enumerate() {
+            if let Some(embedding) = self.cache.get(text) {
+                embeddings[index] = Some(embedding);
+            } else {
+                misses.push((index, *text));
+            }
+        }
+
+        if !misses.is_empty() {
+            let miss_texts: Vec<&str> = misses.iter().map(|(_, text)| *text).collect();
+            let fresh = self.inner.embed_batch(miss_texts).await?;
+            for ((index, text), embedding) in misses.into_iter().zip(fresh) {
+                self.cache.insert(text.to_string(), embedding.clone());
+                embeddings[index] = Some(embedding);
+            }
+        }
+
+        Ok(embeddings
+            .into_iter()
+            .map(|embedding| embedding.expect("every index is filled by a cache hit or a fresh embedding"))
+            .collect())
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// `EmbeddingModel` that returns a deterministic embedding derived from
+    /// the text's length and counts how many times it was actually called,
+    /// so tests can assert on cache hits/misses.
+    struct CountingModel {
+        calls: AtomicUsize,
+    }
+
+    impl CountingModel {
+        fn new() -> Self {
+            Self { calls: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for CountingModel {
+        async fn embed(&self, text: &str) -> Result<Embedding> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![text.len() as f32])
+        }
+
+        async fn embed_batch(&self, texts: Vec<&str>) -> Result<Vec<Embedding>> {
+            self.calls.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts.iter().map(|text| vec![text.len() as f32]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            1
+        }
+
+        fn model_name(&self) -> &str {
+            "counting-model"
+        }
+    }
+
+    #[tokio::test]
+    async fn embed_reuses_cached_result_for_a_repeated_text() {
+        let inner = Arc::new(CountingModel::new());
+        let model = CachedEmbeddingModel::new(inner.clone(), 10, None, false);
+
+        let first = model.embed("hello").await.unwrap();
+        let second = model.embed("hello").await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(model.cache_metrics().hits, 1);
+    }
+
+    #[tokio::test]
+    async fn embed_misses_the_cache_for_distinct_texts() {
+        let inner = Arc::new(CountingModel::new());
+        let model = CachedEmbeddingModel::new(inner.clone(), 10, None, false);
+
+        model.embed("hello").await.unwrap();
+        model.embed("goodbye").await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(model.cache_metrics().misses, 2);
+    }
+
+    #[tokio::test]
+    async fn embed_batch_only_calls_inner_for_uncached_texts() {
+        let inner = Arc::new(CountingModel::new());
+        let model = CachedEmbeddingModel::new(inner.clone(), 10, None, false);
+
+        model.embed("hello").await.unwrap();
+        let results = model.embed_batch(vec!["hello", "world"]).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn dimension_and_model_name_delegate_to_inner() {
+        let inner = Arc::new(CountingModel::new());
+        let model = CachedEmbeddingModel::new(inner, 10, None, false);
+
+        assert_eq!(model.dimension(), 1);
+        assert_eq!(model.model_name(), "counting-model");
+    }
+}
@@ -0,0 +1,132 @@
+//! Default `DocumentProcessor`: splits a document's content into fixed-size,
+//! overlapping chunks, the way `RagConfig.chunk_size`/`chunk_overlap` were
+//! always meant to be used (see `engine::QdrantRagEngine::index_documents`).
+
+use async_trait::async_trait;
+use meta_ai_common::error::Result;
+use meta_ai_core::rag::{ChunkingStrategy, DocumentChunk, DocumentProcessor};
+
+/// Splits content into chunks of `size` characters, each overlapping the
+/// previous one by `overlap` characters so a fact that happens to fall on a
+/// chunk boundary still appears whole in at least one chunk.
+pub struct FixedSizeChunker {
+    strategy: ChunkingStrategy,
+}
+
+impl FixedSizeChunker {
+    pub fn new(size: usize, overlap: usize) -> Self {
+        Self {
+            strategy: ChunkingStrategy::FixedSize { size, overlap: overlap.min(size.saturating_sub(1)) },
+        }
+    }
+}
+
+#[async_trait]
+impl DocumentProcessor for FixedSizeChunker {
+    async fn process_document(&self, content: &str) -> Result<Vec<DocumentChunk>> {
+        let ChunkingStrategy::FixedSize { size, overlap } = self.strategy.clone() else {
+            unreachable!("FixedSizeChunker is always constructed with ChunkingStrategy::FixedSize");
+        };
+        let content = self.preprocess_text(content);
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let chars: Vec<char> = content.chars().collect();
+        let stride = size.saturating_sub(overlap).max(1);
+        let mut chunks = Vec::new();
+        let mut start = 0;
+
+        while start < chars.len() {
+            let end = (start + size).min(chars.len());
+            chunks.push(DocumentChunk {
+                content: chars[start..end].iter().collect(),
+                start_offset: start,
+                end_offset: end,
+                metadata: meta_ai_common::types::Metadata::new(),
+            });
+            if end == chars.len() {
+                break;
+            }
+            start += stride;
+        }
+
+        Ok(chunks)
+    }
+
+    fn chunking_strategy(&self) -> &ChunkingStrategy {
+        &self.strategy
+    }
+
+    fn preprocess_text(&self, text: &str) -> String {
+        text.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn empty_content_produces_no_chunks() {
+        let chunker = FixedSizeChunker::new(10, 2);
+        let chunks = chunker.process_document("   ").await.unwrap();
+        assert!(chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn content_shorter_than_chunk_size_produces_one_chunk() {
+        let chunker = FixedSizeChunker::new(100, 10);
+        let chunks = chunker.process_document("hello world").await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "hello world");
+        assert_eq!(chunks[0].start_offset, 0);
+        assert_eq!(chunks[0].end_offset, 11);
+    }
+
+    #[tokio::test]
+    async fn content_is_split_into_overlapping_chunks() {
+        let chunker = FixedSizeChunker::new(5, 2);
+        let chunks = chunker.process_document("abcdefghij").await.unwrap();
+
+        assert!(chunks.len() > 1);
+        for window in chunks.windows(2) {
+            assert_eq!(window[0].end_offset - window[1].start_offset, 2);
+        }
+        assert_eq!(chunks.last().unwrap().end_offset, 10);
+    }
+
+    #[tokio::test]
+    async fn every_character_is_covered_by_at_least_one_chunk() {
+        let chunker = FixedSizeChunker::new(4, 1);
+        let content = "the quick brown fox jumps over the lazy dog";
+        let chunks = chunker.process_document(content).await.unwrap();
+
+        let chars: Vec<char> = content.chars().collect();
+        let mut covered = vec![false; chars.len()];
+        for chunk in &chunks {
+            for covered_flag in &mut covered[chunk.start_offset..chunk.end_offset] {
+                *covered_flag = true;
+            }
+        }
+        assert!(covered.iter().all(|&c| c));
+    }
+
+    #[tokio::test]
+    async fn surrounding_whitespace_is_trimmed_before_chunking() {
+        let chunker = FixedSizeChunker::new(100, 10);
+        let chunks = chunker.process_document("  hello world  \n").await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "hello world");
+    }
+
+    #[test]
+    fn overlap_is_capped_below_chunk_size() {
+        let chunker = FixedSizeChunker::new(5, 10);
+        let ChunkingStrategy::FixedSize { size, overlap } = chunker.chunking_strategy().clone() else {
+            unreachable!()
+        };
+        assert_eq!(size, 5);
+        assert_eq!(overlap, 4);
+    }
+}
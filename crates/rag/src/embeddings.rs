@@ -1,30 +1,187 @@
 //! Embedding model implementation
 
 use async_trait::async_trait;
-use meta_ai_common::{error::{Error, Result}, types::Embedding};
+use candle_core::{DType, Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
+use meta_ai_common::{config::RagConfig, error::Error, error::Result, types::Embedding};
 use meta_ai_core::rag::EmbeddingModel;
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
 
-pub struct BGEEmbeddingModel;
+/// Maximum token sequence length BGE models were trained with; texts longer
+/// than this are truncated rather than rejected.
+const MAX_SEQUENCE_LENGTH: usize = 512;
+
+/// `EmbeddingModel` backed by a local BGE encoder, run through `candle`.
+/// Weights and tokenizer are pulled from the Hugging Face Hub the first time
+/// `BGEEmbeddingModel::new` runs for a given `RagConfig.embedding_model`, then
+/// cached on disk by `hf-hub` for subsequent runs.
+pub struct BGEEmbeddingModel {
+    model: BertModel,
+    tokenizer: Tokenizer,
+    device: Device,
+    dimension: usize,
+    model_name: String,
+}
 
 impl BGEEmbeddingModel {
-    pub fn new() -> Self { Self }
+    /// Download (or reuse a cached copy of) the BGE model named by
+    /// `config.embedding_model` and load it for CPU inference. Errors if the
+    /// model's native hidden size doesn't match `config.embedding_dimension`,
+    /// since every downstream consumer (Qdrant's collection vector size, in
+    /// particular) is built around that dimension.
+    pub async fn new(config: &RagConfig) -> Result<Self> {
+        let device = Device::Cpu;
+        let repo = hf_hub::api::tokio::Api::new()
+            .map_err(|e| Error::Rag(format!("failed to create Hugging Face Hub client: {e}")))?
+            .model(config.embedding_model.clone());
+
+        let config_path = repo
+            .get("config.json")
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch {}/config.json: {e}", config.embedding_model)))?;
+        let tokenizer_path = repo
+            .get("tokenizer.json")
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch {}/tokenizer.json: {e}", config.embedding_model)))?;
+        let weights_path = repo
+            .get("model.safetensors")
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch {}/model.safetensors: {e}", config.embedding_model)))?;
+
+        let config_bytes =
+            std::fs::read(&config_path).map_err(|e| Error::Rag(format!("failed to read model config: {e}")))?;
+        let bert_config: BertConfig = serde_json::from_slice(&config_bytes)
+            .map_err(|e| Error::Rag(format!("failed to parse model config: {e}")))?;
+
+        // `bert::Config`'s fields are private, so read `hidden_size` back out
+        // of the raw JSON to make sure it matches what `RagConfig` expects
+        // before we commit to loading the rest of the model.
+        let hidden_size = serde_json::from_slice::<serde_json::Value>(&config_bytes)
+            .ok()
+            .and_then(|v| v.get("hidden_size").and_then(|h| h.as_u64()).map(|h| h as usize));
+        if hidden_size != Some(config.embedding_dimension) {
+            return Err(Error::Rag(format!(
+                "model {} has hidden size {:?} but RagConfig.embedding_dimension is {}",
+                config.embedding_model, hidden_size, config.embedding_dimension
+            )));
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| Error::Rag(format!("failed to load tokenizer: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_SEQUENCE_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(|e| Error::Rag(format!("failed to configure tokenizer truncation: {e}")))?;
+
+        // `from_mmaped_safetensors` would avoid this copy but is `unsafe`
+        // (the caller has to guarantee the file isn't mutated from under the
+        // mmap), which this crate's `forbid(unsafe_code)` rules out.
+        let weights = std::fs::read(&weights_path)
+            .map_err(|e| Error::Rag(format!("failed to read model weights: {e}")))?;
+        let vb = VarBuilder::from_buffered_safetensors(weights, DTYPE, &device)
+            .map_err(|e| Error::Rag(format!("failed to load model weights: {e}")))?;
+        let model = BertModel::load(vb, &bert_config)
+            .map_err(|e| Error::Rag(format!("failed to build BERT model: {e}")))?;
+
+        Ok(Self {
+            model,
+            tokenizer,
+            device,
+            dimension: config.embedding_dimension,
+            model_name: config.embedding_model.clone(),
+        })
+    }
+
+    /// Run a batch of texts through the model and return L2-normalized mean
+    /// pooled embeddings, in the same order as `texts`.
+    fn infer(&self, texts: &[&str]) -> Result<Vec<Embedding>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| Error::Rag(format!("failed to tokenize input: {e}")))?;
+
+        let input_ids: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_ids().to_vec()).collect();
+        let token_type_ids: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_type_ids().to_vec()).collect();
+        let attention_mask: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_attention_mask().to_vec()).collect();
+
+        let input_ids = Tensor::new(input_ids, &self.device)
+            .map_err(|e| Error::Rag(format!("failed to build input tensor: {e}")))?;
+        let token_type_ids = Tensor::new(token_type_ids, &self.device)
+            .map_err(|e| Error::Rag(format!("failed to build token type tensor: {e}")))?;
+        let attention_mask = Tensor::new(attention_mask, &self.device)
+            .map_err(|e| Error::Rag(format!("failed to build attention mask tensor: {e}")))?
+            .to_dtype(DType::F32)
+            .map_err(|e| Error::Rag(format!("failed to cast attention mask: {e}")))?;
+
+        let hidden_states = self
+            .model
+            .forward(&input_ids, &token_type_ids)
+            .map_err(|e| Error::Rag(format!("model forward pass failed: {e}")))?;
+
+        // Mean pooling over the sequence dimension, weighted by the
+        // attention mask so padding tokens don't dilute the average.
+        let mask = attention_mask
+            .unsqueeze(2)
+            .map_err(|e| Error::Rag(format!("failed to expand attention mask: {e}")))?
+            .broadcast_as(hidden_states.shape())
+            .map_err(|e| Error::Rag(format!("failed to broadcast attention mask: {e}")))?;
+        let masked = hidden_states
+            .broadcast_mul(&mask)
+            .map_err(|e| Error::Rag(format!("failed to apply attention mask: {e}")))?;
+        let summed = masked
+            .sum(1)
+            .map_err(|e| Error::Rag(format!("failed to sum token embeddings: {e}")))?;
+        let counts = attention_mask
+            .sum_keepdim(1)
+            .map_err(|e| Error::Rag(format!("failed to sum attention mask: {e}")))?;
+        let pooled = summed
+            .broadcast_div(&counts)
+            .map_err(|e| Error::Rag(format!("failed to average token embeddings: {e}")))?;
+
+        // L2-normalize each embedding so downstream cosine-similarity search
+        // (see `QdrantRagEngine`, which creates collections with
+        // `Distance::Cosine`) operates on unit vectors.
+        let norm = pooled
+            .sqr()
+            .and_then(|t| t.sum_keepdim(1))
+            .and_then(|t| t.sqrt())
+            .map_err(|e| Error::Rag(format!("failed to compute embedding norm: {e}")))?;
+        let normalized = pooled
+            .broadcast_div(&norm)
+            .map_err(|e| Error::Rag(format!("failed to normalize embeddings: {e}")))?;
+
+        normalized
+            .to_vec2::<f32>()
+            .map_err(|e| Error::Rag(format!("failed to read embeddings off the model: {e}")))
+    }
 }
 
 #[async_trait]
 impl EmbeddingModel for BGEEmbeddingModel {
-    async fn embed(&self, _text: &str) -> Result<Embedding> {
-        Err(Error::Rag("Not implemented".to_string()))
+    async fn embed(&self, text: &str) -> Result<Embedding> {
+        Ok(self
+            .infer(&[text])?
+            .into_iter()
+            .next()
+            .expect("infer returns one embedding per input text"))
     }
-    
-    async fn embed_batch(&self, _texts: Vec<&str>) -> Result<Vec<Embedding>> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn embed_batch(&self, texts: Vec<&str>) -> Result<Vec<Embedding>> {
+        self.infer(&texts)
     }
-    
+
     fn dimension(&self) -> usize {
-        768
+        self.dimension
     }
-    
+
     fn model_name(&self) -> &str {
-        "BAAI/bge-base-en-v1.5"
+        &self.model_name
     }
-}
\ No newline at end of file
+}
@@ -1,50 +1,1085 @@
 //! RAG engine implementation
 
+use crate::bm25::{self, Bm25Index};
+use crate::cached_embeddings::CachedEmbeddingModel;
+use crate::chunking::FixedSizeChunker;
 use async_trait::async_trait;
-use meta_ai_common::{error::{Error, Result}, types::{Document, Embedding, SearchResult}};
-use meta_ai_core::rag::{RagEngine, IndexResult, CollectionStats};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use meta_ai_common::{
+    config::{ProductCompressionRatio, QuantizationConfig, RagConfig},
+    error::{Error, Result},
+    types::{Document, Embedding, LlmParameters, LlmRequest, Metadata, SearchResult},
+};
+use meta_ai_core::{
+    agent::Agent,
+    rag::{
+        CollectionSnapshot, CollectionStats, DocumentProcessor, EmbeddingModel, FilterValue, IndexError,
+        IndexProgress, IndexResult, MetadataFilter, QueryExpansionMode, RagEngine, Reranker, SearchResultStream,
+    },
+};
+use parking_lot::Mutex;
+use qdrant_client::{
+    qdrant::{
+        quantization_config::Quantization, vector_output::Vector, CompressionRatio, Condition,
+        CreateCollectionBuilder, DeletePointsBuilder, Distance, Filter, GetPointsBuilder, PointId, PointStruct,
+        PointsIdsList, ProductQuantizationBuilder, QueryPointsBuilder, Range, ScalarQuantizationBuilder, ScoredPoint,
+        ScrollPointsBuilder, UpsertPointsBuilder, Value as QdrantValue, VectorParamsBuilder, VectorsOutput,
+    },
+    Payload, Qdrant,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::warn;
+use uuid::Uuid;
 
-pub struct QdrantRagEngine;
+/// Payload key the document's original (caller-assigned) ID is stored under,
+/// since Qdrant point IDs must be an unsigned integer or a UUID and
+/// `Document::id` is an arbitrary string (see `QdrantRagEngine::point_id`).
+const DOCUMENT_ID_KEY: &str = "document_id";
+/// Payload key the document's text content is stored under, so search results
+/// can be reconstructed without a separate lookup.
+const CONTENT_KEY: &str = "content";
+/// Payload key the document's creation timestamp is stored under.
+const CREATED_AT_KEY: &str = "created_at";
+/// Payload key a document's expiry timestamp is stored under, read by
+/// `purge_expired` to find points to sweep. Absent entirely for documents
+/// that never expire, since a Qdrant range filter never matches a missing
+/// field.
+const EXPIRES_AT_KEY: &str = "expires_at";
+
+/// How many points go into a single `upsert_points` call when indexing a
+/// batch of documents.
+const UPSERT_CHUNK_SIZE: usize = 128;
+
+/// Payload key a chunk's parent document ID is stored under.
+const PARENT_DOCUMENT_ID_KEY: &str = "parent_document_id";
+/// Payload key a chunk's position within its parent document is stored under.
+const CHUNK_INDEX_KEY: &str = "chunk_index";
+
+/// How many documents `index_documents` chunks, embeds, and upserts
+/// concurrently.
+const INDEX_CONCURRENCY: usize = 8;
+/// How many times `index_documents` retries a single document's pipeline
+/// before giving up and recording it as an `IndexError`.
+const INDEX_RETRY_ATTEMPTS: u32 = 3;
+
+/// How many points `purge_expired` scrolls through per page while looking
+/// for expired ones.
+const EXPIRY_SWEEP_PAGE_SIZE: u32 = 256;
+
+/// How many points `snapshot` scrolls through per page while exporting the
+/// whole collection.
+const SNAPSHOT_PAGE_SIZE: u32 = 256;
+
+/// Capacity of the bounded channel `search_stream` sends batches over. Kept
+/// small so a slow consumer actually exerts backpressure rather than letting
+/// production run far ahead of consumption.
+const SEARCH_STREAM_CHANNEL_CAPACITY: usize = 2;
+
+/// `RagEngine` backed by a Qdrant collection, with an in-process BM25 index
+/// kept in sync alongside it for hybrid search. Documents passed to
+/// `index_document`/`index_documents` must already carry an `embedding`
+/// (generating one from raw text is `EmbeddingModel`'s job); `search` itself
+/// can generate a query embedding too, but only once one has been supplied
+/// via `with_embedding_model`.
+pub struct QdrantRagEngine {
+    client: Qdrant,
+    collection_name: String,
+    embedding_dimension: usize,
+    /// Minimum similarity score a hit must clear to be returned, applied on
+    /// top of `top_k`. `None` (the default) applies no threshold, matching
+    /// `VectorStore::search_similar`'s own `score_threshold: Option<f32>`.
+    score_threshold: Option<f32>,
+    /// Keyword index kept in sync with every document this engine indexes,
+    /// used by `search` to fuse BM25 keyword matches with vector similarity.
+    bm25: Mutex<Bm25Index>,
+    /// How `search` combines `bm25`'s ranking with the vector ranking.
+    fusion_strategy: meta_ai_common::config::FusionStrategy,
+    /// Generates the query embedding `search` needs for its vector half.
+    /// `None` means `search` falls back to BM25-only ranking.
+    embedding_model: Option<Arc<dyn EmbeddingModel>>,
+    /// Rescores `search`'s fused candidates before they're truncated to
+    /// `top_k`. `None` means `search` returns the fused ranking as-is.
+    reranker: Option<Arc<dyn Reranker>>,
+    /// Splits a document's content into chunks for `index_documents`.
+    /// Defaults to a `FixedSizeChunker` seeded from `RagConfig.chunk_size`/
+    /// `chunk_overlap`.
+    document_processor: Arc<dyn DocumentProcessor>,
+    /// Size/TTL `with_embedding_model` gives the `CachedEmbeddingModel` it
+    /// wraps every configured embedding model in.
+    embedding_cache_size: usize,
+    embedding_cache_ttl: Option<std::time::Duration>,
+    embedding_cache_f16: bool,
+    /// Minimum cosine similarity to an already-indexed chunk for a new chunk
+    /// to be skipped as a near-duplicate during `index_documents`. `None`
+    /// (the default) disables dedup entirely.
+    dedup_threshold: Option<f32>,
+    /// Agent `search` asks to rewrite a query when called with a
+    /// `QueryExpansionMode`. `None` means expansion requests are ignored and
+    /// the literal query is embedded, since there's nothing to rewrite with.
+    query_expander: Option<Arc<dyn Agent>>,
+}
+
+/// How many more candidates than `top_k` each half of a hybrid search fetches
+/// before fusion, so a document ranked outside `top_k` on one side but well
+/// inside it on the other still has a chance to make the fused top_k.
+const HYBRID_CANDIDATE_MULTIPLIER: usize = 4;
 
 impl QdrantRagEngine {
-    pub fn new() -> Self { Self }
+    /// Connect to the Qdrant instance described by `config` and ensure the
+    /// configured collection exists, creating it with cosine distance and
+    /// `config.embedding_dimension` if it doesn't.
+    pub async fn new(config: &RagConfig) -> Result<Self> {
+        let client = Qdrant::from_url(&config.qdrant_url)
+            .build()
+            .map_err(|e| Error::Rag(format!("failed to connect to Qdrant: {e}")))?;
+
+        let exists = client
+            .collection_exists(&config.collection_name)
+            .await
+            .map_err(|e| Error::Rag(format!("failed to check collection existence: {e}")))?;
+
+        if !exists {
+            let mut builder = CreateCollectionBuilder::new(&config.collection_name).vectors_config(
+                VectorParamsBuilder::new(config.embedding_dimension as u64, Distance::Cosine),
+            );
+            if let Some(quantization) = &config.quantization {
+                builder = builder.quantization_config(Self::qdrant_quantization(quantization));
+            }
+            client
+                .create_collection(builder)
+                .await
+                .map_err(|e| Error::Rag(format!("failed to create collection: {e}")))?;
+        }
+
+        Ok(Self {
+            client,
+            collection_name: config.collection_name.clone(),
+            embedding_dimension: config.embedding_dimension,
+            score_threshold: None,
+            bm25: Mutex::new(Bm25Index::new()),
+            fusion_strategy: config.fusion_strategy.clone(),
+            embedding_model: None,
+            reranker: None,
+            document_processor: Arc::new(FixedSizeChunker::new(config.chunk_size, config.chunk_overlap)),
+            embedding_cache_size: config.embedding_cache_size,
+            embedding_cache_ttl: config.embedding_cache_ttl_secs.map(std::time::Duration::from_secs),
+            embedding_cache_f16: config.embedding_cache_f16,
+            dedup_threshold: None,
+            query_expander: None,
+        })
+    }
+
+    /// Apply a minimum similarity score to every subsequent `search`/
+    /// `search_by_embedding` call.
+    pub fn with_score_threshold(mut self, threshold: f32) -> Self {
+        self.score_threshold = Some(threshold);
+        self
+    }
+
+    /// Let `search` generate its own query embedding, so it can fuse a
+    /// vector-similarity ranking with the BM25 keyword ranking instead of
+    /// falling back to keyword-only search. `model` is wrapped in a
+    /// `CachedEmbeddingModel` (sized and TTL'd from the `RagConfig` passed to
+    /// `new`) so texts that were already embedded aren't re-embedded.
+    pub fn with_embedding_model(mut self, model: Arc<dyn EmbeddingModel>) -> Self {
+        self.embedding_model = Some(Arc::new(CachedEmbeddingModel::new(
+            model,
+            self.embedding_cache_size,
+            self.embedding_cache_ttl,
+            self.embedding_cache_f16,
+        )));
+        self
+    }
+
+    /// Rescore `search`'s fused candidates with `reranker` before truncating
+    /// to `top_k`, trading the extra compute for precision on the set of
+    /// chunks that will actually go into a prompt.
+    pub fn with_reranker(mut self, reranker: Arc<dyn Reranker>) -> Self {
+        self.reranker = Some(reranker);
+        self
+    }
+
+    /// Use `processor` to chunk documents in `index_documents` instead of the
+    /// default `FixedSizeChunker`.
+    pub fn with_document_processor(mut self, processor: Arc<dyn DocumentProcessor>) -> Self {
+        self.document_processor = processor;
+        self
+    }
+
+    /// Skip indexing a chunk whose nearest existing neighbor already scores
+    /// at or above `threshold`, so large corpora don't get flooded with
+    /// near-identical chunks. Requires an embedding model (see
+    /// `with_embedding_model`); skipped chunks are counted in
+    /// `IndexResult.duplicates_skipped`.
+    pub fn with_dedup_threshold(mut self, threshold: f32) -> Self {
+        self.dedup_threshold = Some(threshold);
+        self
+    }
+
+    /// Let `search` rewrite its query through `agent` when called with a
+    /// `QueryExpansionMode`, e.g. to expand a short query with synonyms or
+    /// generate a HyDE pseudo-document before embedding it.
+    pub fn with_query_expander(mut self, agent: Arc<dyn Agent>) -> Self {
+        self.query_expander = Some(agent);
+        self
+    }
+
+    /// Build the prompt asking `query_expander` to rewrite `query` per `mode`.
+    fn expansion_prompt(query: &str, mode: QueryExpansionMode) -> String {
+        match mode {
+            QueryExpansionMode::Expand => format!(
+                "Rewrite the following search query to include likely \
+                 synonyms and related terms, to improve recall against a \
+                 document search index. Respond with only the rewritten \
+                 query, on one line.\n\nQuery: {query}"
+            ),
+            QueryExpansionMode::Hyde => format!(
+                "Write a short hypothetical passage that would directly \
+                 answer the following query, as if it were an excerpt from \
+                 the ideal matching document. Respond with only the \
+                 passage.\n\nQuery: {query}"
+            ),
+        }
+    }
+
+    /// Rewrite `query` per `mode` through `query_expander`, falling back to
+    /// the literal `query` when no expander is configured.
+    async fn expand_query(&self, query: &str, mode: QueryExpansionMode) -> Result<String> {
+        let Some(agent) = &self.query_expander else {
+            return Ok(query.to_string());
+        };
+
+        let request = LlmRequest {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            provider: agent.provider(),
+            prompt: Self::expansion_prompt(query, mode),
+            parameters: LlmParameters::default(),
+            timeout_ms: None,
+            attachments: Vec::new(),
+            metadata: Metadata::new(),
+            session_id: None,
+        };
+        let response = agent.submit(request).await?;
+        let rewritten = response.content.trim();
+        if rewritten.is_empty() {
+            Ok(query.to_string())
+        } else {
+            Ok(rewritten.to_string())
+        }
+    }
+
+    /// Delete every point whose `expires_at` is at or before now, removing
+    /// it from both Qdrant and the BM25 index, and return how many were
+    /// swept. Points with no `expires_at` are never matched, since a Qdrant
+    /// range filter never matches a missing field.
+    pub async fn purge_expired(&self) -> Result<usize> {
+        let expired_filter = Self::qdrant_filter(&MetadataFilter::Lte(
+            EXPIRES_AT_KEY.to_string(),
+            Utc::now().timestamp() as f64,
+        ));
+
+        let mut ids = Vec::new();
+        let mut offset = None;
+        loop {
+            let mut builder = ScrollPointsBuilder::new(&self.collection_name)
+                .filter(expired_filter.clone())
+                .with_payload(true)
+                .limit(EXPIRY_SWEEP_PAGE_SIZE);
+            if let Some(offset) = offset.take() {
+                builder = builder.offset(offset);
+            }
+
+            let response = self
+                .client
+                .scroll(builder)
+                .await
+                .map_err(|e| Error::Rag(format!("failed to scroll expired points: {e}")))?;
+
+            for point in response.result {
+                let document_id = Self::document_from_payload(point.payload).id;
+                ids.push((point.id, document_id));
+            }
+
+            offset = response.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        self.client
+            .delete_points(
+                DeletePointsBuilder::new(&self.collection_name).points(PointsIdsList {
+                    ids: ids.iter().filter_map(|(id, _)| id.clone()).collect(),
+                }),
+            )
+            .await
+            .map_err(|e| Error::Rag(format!("failed to delete expired points: {e}")))?;
+
+        {
+            let mut bm25 = self.bm25.lock();
+            for (_, document_id) in &ids {
+                bm25.remove_document(document_id);
+            }
+        }
+
+        Ok(ids.len())
+    }
+
+    /// Spawn a background loop that calls `purge_expired` every `interval`
+    /// until the returned handle is dropped or aborted, so ephemeral content
+    /// (chat transcripts, temporary docs) stops polluting retrieval results
+    /// once it expires.
+    pub fn spawn_expiry_sweep(self: Arc<Self>, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match self.purge_expired().await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("expiry sweep removed {count} expired point(s)"),
+                    Err(e) => warn!("expiry sweep failed: {e}"),
+                }
+            }
+        })
+    }
+
+    /// Derive a Qdrant point ID deterministically from a document ID, since
+    /// Qdrant requires point IDs to be an unsigned integer or a UUID string
+    /// while `Document::id` is an arbitrary caller-assigned string. The same
+    /// document ID always maps to the same point ID, so re-indexing a
+    /// document overwrites its previous point rather than duplicating it.
+    fn point_id(document_id: &str) -> PointId {
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, document_id.as_bytes())
+            .to_string()
+            .into()
+    }
+
+    /// Build the Qdrant payload for `document`, merging its metadata with the
+    /// reserved keys this engine needs to reconstruct a `Document` on read.
+    fn build_payload(document: &Document) -> Payload {
+        let mut fields = document.metadata.clone();
+        fields.insert(DOCUMENT_ID_KEY.to_string(), document.id.clone().into());
+        fields.insert(CONTENT_KEY.to_string(), document.content.clone().into());
+        fields.insert(
+            CREATED_AT_KEY.to_string(),
+            document.created_at.to_rfc3339().into(),
+        );
+        if let Some(expires_at) = document.expires_at {
+            fields.insert(EXPIRES_AT_KEY.to_string(), expires_at.timestamp().into());
+        }
+        Payload::from(fields)
+    }
+
+    /// Reconstruct a `Document` (minus its embedding, which Qdrant doesn't
+    /// return unless explicitly requested) from a point's payload.
+    fn document_from_payload(payload: HashMap<String, QdrantValue>) -> Document {
+        let fields: serde_json::Map<String, serde_json::Value> = Payload::from(payload).into();
+        let mut metadata: Metadata = fields.into_iter().collect();
+
+        let id = metadata
+            .remove(DOCUMENT_ID_KEY)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let content = metadata
+            .remove(CONTENT_KEY)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let created_at = metadata
+            .remove(CREATED_AT_KEY)
+            .and_then(|v| v.as_str().and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok()))
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now);
+        let expires_at = metadata
+            .remove(EXPIRES_AT_KEY)
+            .and_then(|v| v.as_i64())
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+
+        Document {
+            id,
+            content,
+            embedding: None,
+            metadata,
+            created_at,
+            expires_at,
+        }
+    }
+
+    /// Turn a document with an embedding into the point Qdrant upserts. Errs
+    /// if the document has no embedding, since generating one isn't this
+    /// engine's job (see `RagEngine::generate_embedding`'s doc comment).
+    fn point_for(document: &Document) -> Result<PointStruct> {
+        let embedding = document.embedding.clone().ok_or_else(|| {
+            Error::Rag(format!(
+                "document {} has no embedding; generate one before indexing",
+                document.id
+            ))
+        })?;
+        Ok(PointStruct::new(
+            Self::point_id(&document.id),
+            embedding,
+            Self::build_payload(document),
+        ))
+    }
+
+    /// Pull the plain `Embedding` out of a point's `vectors` field, as
+    /// returned when a query is built `with_vectors(true)`. `None` if the
+    /// point has no default (unnamed) vector, which shouldn't happen for
+    /// anything this engine itself wrote.
+    fn vector_from_output(vectors: Option<VectorsOutput>) -> Option<Embedding> {
+        match vectors?.get_vector()? {
+            Vector::Dense(dense) => Some(dense.data),
+            Vector::Sparse(_) | Vector::MultiDense(_) => None,
+        }
+    }
+
+    /// Turn a `ScoredPoint` from a query response into a `SearchResult`.
+    fn search_result_from(point: ScoredPoint) -> SearchResult {
+        let document = Self::document_from_payload(point.payload);
+        SearchResult {
+            metadata: document.metadata.clone(),
+            document,
+            score: point.score,
+        }
+    }
+
+    /// Translate a `QuantizationConfig` into the Qdrant quantization config
+    /// `CreateCollectionBuilder::quantization_config` expects.
+    fn qdrant_quantization(quantization: &QuantizationConfig) -> Quantization {
+        match quantization {
+            QuantizationConfig::Scalar { quantile, always_ram } => {
+                // `ScalarQuantizationBuilder::default()` already sets the
+                // (currently only) quantization type, Int8.
+                ScalarQuantizationBuilder::default().quantile(*quantile).always_ram(*always_ram).into()
+            }
+            QuantizationConfig::Product { compression, always_ram } => {
+                ProductQuantizationBuilder::new(Self::qdrant_compression_ratio(*compression) as i32)
+                    .always_ram(*always_ram)
+                    .into()
+            }
+        }
+    }
+
+    fn qdrant_compression_ratio(ratio: ProductCompressionRatio) -> CompressionRatio {
+        match ratio {
+            ProductCompressionRatio::X4 => CompressionRatio::X4,
+            ProductCompressionRatio::X8 => CompressionRatio::X8,
+            ProductCompressionRatio::X16 => CompressionRatio::X16,
+            ProductCompressionRatio::X32 => CompressionRatio::X32,
+            ProductCompressionRatio::X64 => CompressionRatio::X64,
+        }
+    }
+
+    /// Translate a `MetadataFilter` into the equivalent Qdrant payload
+    /// filter, pushing the filter down into the query instead of fetching
+    /// unfiltered results and filtering them after the fact.
+    fn qdrant_filter(filter: &MetadataFilter) -> Filter {
+        match filter {
+            MetadataFilter::Eq(key, value) => Filter::must([Condition::matches(
+                key.clone(),
+                Self::match_value(value),
+            )]),
+            MetadataFilter::In(key, values) => {
+                Filter::should(values.iter().map(|value| {
+                    Condition::matches(key.clone(), Self::match_value(value))
+                }))
+            }
+            MetadataFilter::Gte(key, threshold) => Filter::must([Condition::range(
+                key.clone(),
+                Range {
+                    gte: Some(*threshold),
+                    ..Default::default()
+                },
+            )]),
+            MetadataFilter::Lte(key, threshold) => Filter::must([Condition::range(
+                key.clone(),
+                Range {
+                    lte: Some(*threshold),
+                    ..Default::default()
+                },
+            )]),
+            MetadataFilter::And(filters) => {
+                Filter::must(filters.iter().map(|f| Condition::from(Self::qdrant_filter(f))))
+            }
+            MetadataFilter::Or(filters) => {
+                Filter::should(filters.iter().map(|f| Condition::from(Self::qdrant_filter(f))))
+            }
+            MetadataFilter::Not(filter) => {
+                Filter::must_not([Condition::from(Self::qdrant_filter(filter))])
+            }
+        }
+    }
+
+    fn match_value(value: &FilterValue) -> qdrant_client::qdrant::r#match::MatchValue {
+        match value {
+            FilterValue::String(s) => s.clone().into(),
+            FilterValue::Integer(i) => (*i).into(),
+            FilterValue::Bool(b) => (*b).into(),
+        }
+    }
+
+    /// Point ID for chunk `index` of document `document_id`, distinct from
+    /// the parent document's own point id (see `Self::point_id`).
+    fn chunk_point_id(document_id: &str, index: usize) -> PointId {
+        Self::point_id(&format!("{document_id}-chunk-{index}"))
+    }
+
+    /// Build the Qdrant payload for one chunk of `document_id`, merging the
+    /// chunk's own metadata with the reserved keys needed to trace it back to
+    /// its parent document.
+    fn chunk_payload(
+        document_id: &str,
+        index: usize,
+        content: &str,
+        metadata: &Metadata,
+        expires_at: Option<chrono::DateTime<Utc>>,
+    ) -> Payload {
+        let mut fields = metadata.clone();
+        fields.insert(DOCUMENT_ID_KEY.to_string(), format!("{document_id}-chunk-{index}").into());
+        fields.insert(CONTENT_KEY.to_string(), content.to_string().into());
+        fields.insert(PARENT_DOCUMENT_ID_KEY.to_string(), document_id.to_string().into());
+        fields.insert(CHUNK_INDEX_KEY.to_string(), index.to_string().into());
+        fields.insert(CREATED_AT_KEY.to_string(), Utc::now().to_rfc3339().into());
+        if let Some(expires_at) = expires_at {
+            fields.insert(EXPIRES_AT_KEY.to_string(), expires_at.timestamp().into());
+        }
+        Payload::from(fields)
+    }
+
+    /// Chunk `document`, embed each chunk, and upsert/index the resulting
+    /// points, skipping any chunk that's a near-duplicate of an
+    /// already-indexed one when `dedup_threshold` is set. Errs if no
+    /// `EmbeddingModel` has been configured, since chunks can't be upserted
+    /// without an embedding. Returns `(chunks indexed, chunks skipped as
+    /// duplicates)`.
+    async fn index_one_document(&self, document: &Document) -> Result<(usize, usize)> {
+        let embedding_model = self.embedding_model.as_ref().ok_or_else(|| {
+            Error::Rag(
+                "QdrantRagEngine has no embedding model configured; call with_embedding_model \
+                 before index_documents"
+                    .to_string(),
+            )
+        })?;
+
+        let chunks = self.document_processor.process_document(&document.content).await?;
+        if chunks.is_empty() {
+            return Ok((0, 0));
+        }
+
+        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+        let embeddings = embedding_model.embed_batch(texts).await?;
+
+        let mut points = Vec::with_capacity(chunks.len());
+        let mut kept_chunks = Vec::with_capacity(chunks.len());
+        let mut duplicates = 0;
+        for (index, (chunk, embedding)) in chunks.iter().zip(embeddings).enumerate() {
+            if let Some(threshold) = self.dedup_threshold {
+                let nearest = self.search_by_embedding(&embedding, 1, None).await?;
+                if nearest.iter().any(|hit| hit.score >= threshold) {
+                    duplicates += 1;
+                    continue;
+                }
+            }
+
+            points.push(PointStruct::new(
+                Self::chunk_point_id(&document.id, index),
+                embedding,
+                Self::chunk_payload(&document.id, index, &chunk.content, &chunk.metadata, document.expires_at),
+            ));
+            kept_chunks.push((index, chunk));
+        }
+
+        let chunk_count = points.len();
+        if !points.is_empty() {
+            self.client
+                .upsert_points_chunked(UpsertPointsBuilder::new(&self.collection_name, points), UPSERT_CHUNK_SIZE)
+                .await
+                .map_err(|e| Error::Rag(format!("failed to upsert chunks for document {}: {e}", document.id)))?;
+
+            let mut bm25 = self.bm25.lock();
+            for (index, chunk) in &kept_chunks {
+                bm25.index_document(
+                    &format!("{}-chunk-{index}", document.id),
+                    &chunk.content,
+                    chunk.metadata.clone(),
+                );
+            }
+        }
+
+        Ok((chunk_count, duplicates))
+    }
+
+    /// Retry `index_one_document` up to `INDEX_RETRY_ATTEMPTS` times, logging
+    /// each failed attempt, before giving up on `document`.
+    async fn index_one_document_with_retry(&self, document: &Document) -> Result<(usize, usize)> {
+        let mut last_err = None;
+        for attempt in 1..=INDEX_RETRY_ATTEMPTS {
+            match self.index_one_document(document).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    warn!(
+                        "indexing document {} failed (attempt {attempt}/{INDEX_RETRY_ATTEMPTS}): {e}",
+                        document.id
+                    );
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.expect("loop runs at least once"))
+    }
+
+    /// Fetch the full documents for a set of document IDs, e.g. BM25 hits a
+    /// vector search didn't also surface.
+    async fn documents_by_id(&self, ids: &[String]) -> Result<HashMap<String, Document>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let point_ids: Vec<PointId> = ids.iter().map(|id| Self::point_id(id)).collect();
+        let response = self
+            .client
+            .get_points(
+                GetPointsBuilder::new(&self.collection_name, point_ids).with_payload(true),
+            )
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch documents by id: {e}")))?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .map(|point| {
+                let document = Self::document_from_payload(point.payload);
+                (document.id.clone(), document)
+            })
+            .collect())
+    }
 }
 
 #[async_trait]
 impl RagEngine for QdrantRagEngine {
-    async fn index_document(&self, _document: Document) -> Result<()> {
-        Err(Error::Rag("Not implemented".to_string()))
+    async fn index_document(&self, document: Document) -> Result<()> {
+        let point = Self::point_for(&document)?;
+        self.client
+            .upsert_points(UpsertPointsBuilder::new(&self.collection_name, vec![point]))
+            .await
+            .map_err(|e| Error::Rag(format!("failed to upsert document {}: {e}", document.id)))?;
+        self.bm25
+            .lock()
+            .index_document(&document.id, &document.content, document.metadata.clone());
+        Ok(())
     }
-    
-    async fn index_documents(&self, _documents: Vec<Document>) -> Result<IndexResult> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn index_documents(
+        &self,
+        documents: Vec<Document>,
+        progress: Option<mpsc::UnboundedSender<IndexProgress>>,
+    ) -> Result<IndexResult> {
+        let start = std::time::Instant::now();
+        let total = documents.len();
+        let completed = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(AtomicUsize::new(0));
+        let duplicates = Arc::new(AtomicUsize::new(0));
+
+        let errors: Vec<IndexError> = stream::iter(documents)
+            .map(|document| {
+                let completed = completed.clone();
+                let failed = failed.clone();
+                let duplicates = duplicates.clone();
+                let progress = progress.clone();
+                async move {
+                    let document_id = document.id.clone();
+                    let error = match self.index_one_document_with_retry(&document).await {
+                        Ok((_, skipped)) => {
+                            duplicates.fetch_add(skipped, Ordering::SeqCst);
+                            None
+                        }
+                        Err(e) => {
+                            failed.fetch_add(1, Ordering::SeqCst);
+                            Some(IndexError { document_id: document_id.clone(), error: e.to_string() })
+                        }
+                    };
+
+                    if let Some(sender) = &progress {
+                        let _ = sender.send(IndexProgress {
+                            total,
+                            completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                            failed: failed.load(Ordering::SeqCst),
+                            last_document_id: document_id,
+                        });
+                    } else {
+                        completed.fetch_add(1, Ordering::SeqCst);
+                    }
+
+                    error
+                }
+            })
+            .buffer_unordered(INDEX_CONCURRENCY)
+            .filter_map(|error| async move { error })
+            .collect()
+            .await;
+
+        Ok(IndexResult {
+            total_documents: total,
+            successful: total - errors.len(),
+            failed: errors.len(),
+            duration_ms: start.elapsed().as_millis() as u64,
+            duplicates_skipped: duplicates.load(Ordering::SeqCst),
+            errors,
+        })
     }
-    
-    async fn search(&self, _query: &str, _top_k: usize) -> Result<Vec<SearchResult>> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    /// Hybrid search: ranks documents by BM25 keyword match and, when an
+    /// `EmbeddingModel` has been configured via `with_embedding_model`, also
+    /// by vector similarity, then fuses the two rankings per
+    /// `fusion_strategy`. Falls back to BM25-only ranking when no embedding
+    /// model is configured. `filter`, if given, excludes any document whose
+    /// metadata doesn't match it from both rankings. When a `Reranker` has
+    /// been configured via `with_reranker`, the fused candidates are
+    /// rescored by it before being truncated to `top_k`. `expansion`, if
+    /// given and a query expander has been configured via
+    /// `with_query_expander`, rewrites the query before it's embedded; the
+    /// BM25 half always matches the literal query.
+    async fn search(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+        expansion: Option<QueryExpansionMode>,
+    ) -> Result<Vec<SearchResult>> {
+        let candidates = top_k * HYBRID_CANDIDATE_MULTIPLIER;
+        let bm25_hits = self.bm25.lock().search(query, candidates, filter);
+
+        let results = if let Some(embedding_model) = &self.embedding_model {
+            let embedding_query = match expansion {
+                Some(mode) => self.expand_query(query, mode).await?,
+                None => query.to_string(),
+            };
+            let embedding = embedding_model.embed(&embedding_query).await?;
+            let vector_results = self.search_by_embedding(&embedding, candidates, filter).await?;
+            let vector_hits: Vec<(String, f32)> = vector_results
+                .iter()
+                .map(|result| (result.document.id.clone(), result.score))
+                .collect();
+            let mut documents: HashMap<String, Document> = vector_results
+                .into_iter()
+                .map(|result| (result.document.id.clone(), result.document))
+                .collect();
+
+            let missing: Vec<String> = bm25_hits
+                .iter()
+                .map(|(id, _)| id.clone())
+                .filter(|id| !documents.contains_key(id))
+                .collect();
+            documents.extend(self.documents_by_id(&missing).await?);
+
+            bm25::fuse(&bm25_hits, &vector_hits, &self.fusion_strategy)
+                .into_iter()
+                .filter_map(|(id, score)| {
+                    documents.remove(&id).map(|document| SearchResult {
+                        metadata: document.metadata.clone(),
+                        document,
+                        score,
+                    })
+                })
+                .collect()
+        } else {
+            let ids: Vec<String> = bm25_hits.iter().map(|(id, _)| id.clone()).collect();
+            let mut documents = self.documents_by_id(&ids).await?;
+            bm25_hits
+                .into_iter()
+                .filter_map(|(id, score)| {
+                    documents.remove(&id).map(|document| SearchResult {
+                        metadata: document.metadata.clone(),
+                        document,
+                        score,
+                    })
+                })
+                .collect()
+        };
+
+        match &self.reranker {
+            Some(reranker) => reranker.rerank(query, results, top_k).await,
+            None => Ok(results.into_iter().take(top_k).collect()),
+        }
     }
-    
-    async fn search_by_embedding(&self, _embedding: &Embedding, _top_k: usize) -> Result<Vec<SearchResult>> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn search_by_embedding(
+        &self,
+        embedding: &Embedding,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+    ) -> Result<Vec<SearchResult>> {
+        let mut builder = QueryPointsBuilder::new(&self.collection_name)
+            .query(embedding.clone())
+            .limit(top_k as u64)
+            .with_payload(true);
+        if let Some(threshold) = self.score_threshold {
+            builder = builder.score_threshold(threshold);
+        }
+        if let Some(filter) = filter {
+            builder = builder.filter(Self::qdrant_filter(filter));
+        }
+
+        let response = self
+            .client
+            .query(builder)
+            .await
+            .map_err(|e| Error::Rag(format!("failed to query collection: {e}")))?;
+
+        Ok(response
+            .result
+            .into_iter()
+            .map(Self::search_result_from)
+            .collect())
     }
-    
-    async fn generate_embedding(&self, _text: &str) -> Result<Embedding> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn generate_embedding(&self, text: &str) -> Result<Embedding> {
+        match &self.embedding_model {
+            Some(embedding_model) => embedding_model.embed(text).await,
+            None => Err(Error::Rag(
+                "QdrantRagEngine has no embedding model configured; call with_embedding_model \
+                 or generate the embedding with an EmbeddingModel directly"
+                    .to_string(),
+            )),
+        }
     }
-    
-    async fn delete_document(&self, _document_id: &str) -> Result<()> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn delete_document(&self, document_id: &str) -> Result<()> {
+        self.client
+            .delete_points(
+                DeletePointsBuilder::new(&self.collection_name).points(PointsIdsList {
+                    ids: vec![Self::point_id(document_id)],
+                }),
+            )
+            .await
+            .map_err(|e| Error::Rag(format!("failed to delete document {document_id}: {e}")))?;
+        self.bm25.lock().remove_document(document_id);
+        Ok(())
     }
-    
-    async fn update_document(&self, _document: Document) -> Result<()> {
-        Err(Error::Rag("Not implemented".to_string()))
+
+    async fn update_document(&self, document: Document) -> Result<()> {
+        // Upserting by the same deterministic point ID overwrites the
+        // existing point in place, so update is just index_document again.
+        self.index_document(document).await
     }
-    
+
     async fn get_stats(&self) -> Result<CollectionStats> {
-        Err(Error::Rag("Not implemented".to_string()))
+        let info = self
+            .client
+            .collection_info(&self.collection_name)
+            .await
+            .map_err(|e| Error::Rag(format!("failed to get collection info: {e}")))?;
+
+        let result = info.result.ok_or_else(|| {
+            Error::Rag(format!("no collection info returned for {}", self.collection_name))
+        })?;
+        let points_count = result.points_count.unwrap_or(0) as usize;
+
+        Ok(CollectionStats {
+            total_documents: points_count,
+            total_embeddings: points_count,
+            embedding_dimension: self.embedding_dimension,
+            // Qdrant's CollectionInfo doesn't report on-disk size; approximate
+            // it from point count and configured dimension instead of a
+            // number we have no way to obtain.
+            index_size_bytes: (points_count * self.embedding_dimension * std::mem::size_of::<f32>())
+                as u64,
+            last_updated: Utc::now(),
+        })
     }
-    
+
     async fn clear_collection(&self) -> Result<()> {
-        Err(Error::Rag("Not implemented".to_string()))
+        self.client
+            .delete_collection(&self.collection_name)
+            .await
+            .map_err(|e| Error::Rag(format!("failed to delete collection: {e}")))?;
+        self.client
+            .create_collection(
+                CreateCollectionBuilder::new(&self.collection_name).vectors_config(
+                    VectorParamsBuilder::new(self.embedding_dimension as u64, Distance::Cosine),
+                ),
+            )
+            .await
+            .map_err(|e| Error::Rag(format!("failed to recreate collection: {e}")))?;
+        self.bm25.lock().clear();
+        Ok(())
     }
-}
\ No newline at end of file
+
+    async fn snapshot(&self) -> Result<CollectionSnapshot> {
+        let mut documents = Vec::new();
+        let mut offset = None;
+        loop {
+            let mut builder = ScrollPointsBuilder::new(&self.collection_name)
+                .with_payload(true)
+                .with_vectors(true)
+                .limit(SNAPSHOT_PAGE_SIZE);
+            if let Some(offset) = offset.take() {
+                builder = builder.offset(offset);
+            }
+
+            let response = self
+                .client
+                .scroll(builder)
+                .await
+                .map_err(|e| Error::Rag(format!("failed to scroll collection for snapshot: {e}")))?;
+
+            for point in response.result {
+                let mut document = Self::document_from_payload(point.payload);
+                document.embedding = Self::vector_from_output(point.vectors);
+                documents.push(document);
+            }
+
+            offset = response.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok(CollectionSnapshot { documents })
+    }
+
+    async fn restore(&self, snapshot: CollectionSnapshot) -> Result<()> {
+        if snapshot.documents.is_empty() {
+            return Ok(());
+        }
+
+        let points = snapshot.documents.iter().map(Self::point_for).collect::<Result<Vec<_>>>()?;
+
+        self.client
+            .upsert_points_chunked(UpsertPointsBuilder::new(&self.collection_name, points), UPSERT_CHUNK_SIZE)
+            .await
+            .map_err(|e| Error::Rag(format!("failed to upsert snapshot points: {e}")))?;
+
+        let mut bm25 = self.bm25.lock();
+        for document in &snapshot.documents {
+            bm25.index_document(&document.id, &document.content, document.metadata.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn search_stream(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&MetadataFilter>,
+        expansion: Option<QueryExpansionMode>,
+        batch_size: usize,
+    ) -> Result<SearchResultStream> {
+        let results = self.search(query, top_k, filter, expansion).await?;
+        let batch_size = batch_size.max(1);
+
+        let (tx, rx) = mpsc::channel(SEARCH_STREAM_CHANNEL_CAPACITY);
+        let batches: Vec<Vec<SearchResult>> =
+            results.chunks(batch_size).map(<[SearchResult]>::to_vec).collect();
+        tokio::spawn(async move {
+            for batch in batches {
+                if tx.send(batch).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(id: &str) -> Document {
+        Document {
+            id: id.to_string(),
+            content: "hello world".to_string(),
+            embedding: Some(vec![0.1, 0.2, 0.3]),
+            metadata: Metadata::new(),
+            created_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn point_id_is_deterministic_for_the_same_document_id() {
+        assert_eq!(QdrantRagEngine::point_id("doc-1"), QdrantRagEngine::point_id("doc-1"));
+        assert_ne!(QdrantRagEngine::point_id("doc-1"), QdrantRagEngine::point_id("doc-2"));
+    }
+
+    #[test]
+    fn chunk_point_id_differs_from_the_parent_documents_point_id() {
+        let parent = QdrantRagEngine::point_id("doc-1");
+        let chunk = QdrantRagEngine::chunk_point_id("doc-1", 0);
+        assert_ne!(parent, chunk);
+    }
+
+    #[test]
+    fn point_for_errs_when_the_document_has_no_embedding() {
+        let mut doc = document("doc-1");
+        doc.embedding = None;
+        assert!(QdrantRagEngine::point_for(&doc).is_err());
+    }
+
+    #[test]
+    fn point_for_succeeds_when_the_document_has_an_embedding() {
+        assert!(QdrantRagEngine::point_for(&document("doc-1")).is_ok());
+    }
+
+    #[test]
+    fn build_payload_and_document_from_payload_round_trip() {
+        let mut original = document("doc-1");
+        original.metadata.insert("lang".to_string(), serde_json::json!("en"));
+
+        let payload: HashMap<String, QdrantValue> = QdrantRagEngine::build_payload(&original).into();
+        let reconstructed = QdrantRagEngine::document_from_payload(payload);
+
+        assert_eq!(reconstructed.id, original.id);
+        assert_eq!(reconstructed.content, original.content);
+        assert_eq!(reconstructed.metadata.get("lang"), original.metadata.get("lang"));
+        assert_eq!(reconstructed.embedding, None);
+    }
+
+    #[test]
+    fn document_from_payload_reconstructs_expiry_when_present() {
+        let mut original = document("doc-1");
+        original.expires_at = Some(Utc::now());
+
+        let payload: HashMap<String, QdrantValue> = QdrantRagEngine::build_payload(&original).into();
+        let reconstructed = QdrantRagEngine::document_from_payload(payload);
+
+        assert_eq!(
+            reconstructed.expires_at.map(|dt| dt.timestamp()),
+            original.expires_at.map(|dt| dt.timestamp())
+        );
+    }
+
+    #[test]
+    fn expansion_prompt_mentions_the_query_for_every_mode() {
+        let query = "how do I reset my password";
+        assert!(QdrantRagEngine::expansion_prompt(query, QueryExpansionMode::Expand).contains(query));
+        assert!(QdrantRagEngine::expansion_prompt(query, QueryExpansionMode::Hyde).contains(query));
+    }
+
+    #[test]
+    fn qdrant_compression_ratio_maps_every_variant() {
+        assert_eq!(QdrantRagEngine::qdrant_compression_ratio(ProductCompressionRatio::X4), CompressionRatio::X4);
+        assert_eq!(QdrantRagEngine::qdrant_compression_ratio(ProductCompressionRatio::X64), CompressionRatio::X64);
+    }
+
+    #[test]
+    fn chunk_payload_carries_parent_and_index_metadata() {
+        let payload: HashMap<String, QdrantValue> =
+            QdrantRagEngine::chunk_payload("doc-1", 2, "chunk text", &Metadata::new(), None).into();
+        let reconstructed = QdrantRagEngine::document_from_payload(payload);
+
+        assert_eq!(reconstructed.content, "chunk text");
+        assert_eq!(reconstructed.id, "doc-1-chunk-2");
+    }
+}
@@ -3,10 +3,22 @@
 
 //! RAG (Retrieval-Augmented Generation) implementation
 
+pub mod bm25;
+pub mod cached_embeddings;
+pub mod chunking;
 pub mod engine;
 pub mod embeddings;
+pub mod loaders;
+pub mod openai_embeddings;
+pub mod rerank;
 pub mod vector_store;
 
+pub use bm25::Bm25Index;
+pub use cached_embeddings::CachedEmbeddingModel;
+pub use chunking::FixedSizeChunker;
 pub use engine::QdrantRagEngine;
 pub use embeddings::BGEEmbeddingModel;
+pub use loaders::{CodeLoader, DirectoryLoader, DocumentLoader, HtmlLoader, MarkdownLoader, PdfLoader};
+pub use openai_embeddings::OpenAIEmbeddingModel;
+pub use rerank::{CrossEncoderReranker, LlmReranker};
 pub use vector_store::QdrantVectorStore;
\ No newline at end of file
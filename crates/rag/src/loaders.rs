@@ -0,0 +1,372 @@
+//! File loaders that turn raw files on disk into `Document`s ready for
+//! `RagEngine::index_document`/`index_documents`.
+
+use chrono::Utc;
+use glob::Pattern;
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{Document, Metadata},
+};
+use regex::Regex;
+use std::path::Path;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// Converts a file into one or more `Document`s. One loader handles one
+/// family of file extensions (see `DocumentLoader::extensions`); a loader
+/// that splits content into multiple chunks (see `CodeLoader`) returns one
+/// document per chunk.
+pub trait DocumentLoader: Send + Sync {
+    /// Lowercase file extensions (without the leading dot) this loader handles.
+    fn extensions(&self) -> &[&str];
+
+    /// Load `path`'s contents into one or more documents.
+    fn load(&self, path: &Path) -> Result<Vec<Document>>;
+}
+
+/// Build a fresh `Document` for a loaded chunk of `path`, tagging it with its
+/// source path and (for multi-chunk loaders) its position within the file.
+fn new_document(path: &Path, content: String, chunk_index: Option<usize>) -> Document {
+    let mut metadata = Metadata::new();
+    metadata.insert("source_path".to_string(), path.display().to_string().into());
+    if let Some(index) = chunk_index {
+        metadata.insert("chunk_index".to_string(), index.into());
+    }
+    Document {
+        id: Uuid::new_v4().to_string(),
+        content,
+        embedding: None,
+        metadata,
+        created_at: Utc::now(),
+        expires_at: None,
+    }
+}
+
+/// Loads Markdown files, stripping formatting down to plain text. Uses
+/// targeted regexes for the handful of constructs that matter for search
+/// (fenced code, links, headings, emphasis) rather than a full CommonMark
+/// parse, since the output only needs to read as plain prose.
+pub struct MarkdownLoader;
+
+impl DocumentLoader for MarkdownLoader {
+    fn extensions(&self) -> &[&str] {
+        &["md", "markdown"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<Document>> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| Error::Rag(format!("failed to read {}: {e}", path.display())))?;
+        Ok(vec![new_document(path, strip_markdown(&raw), None)])
+    }
+}
+
+fn strip_markdown(input: &str) -> String {
+    let fence = Regex::new(r"(?m)^```[^\n]*\n").expect("static regex");
+    let without_fences = fence.replace_all(input, "");
+    let link = Regex::new(r"\[([^\]]*)\]\([^)]*\)").expect("static regex");
+    let without_links = link.replace_all(&without_fences, "$1");
+    let heading = Regex::new(r"(?m)^#{1,6}\s*").expect("static regex");
+    let without_headings = heading.replace_all(&without_links, "");
+    let emphasis = Regex::new(r"(\*{1,3}|_{1,3}|`)").expect("static regex");
+    emphasis.replace_all(&without_headings, "").trim().to_string()
+}
+
+/// Loads HTML files, dropping script/style blocks and tags and decoding the
+/// handful of entities common in hand-written pages.
+pub struct HtmlLoader;
+
+impl DocumentLoader for HtmlLoader {
+    fn extensions(&self) -> &[&str] {
+        &["html", "htm"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<Document>> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| Error::Rag(format!("failed to read {}: {e}", path.display())))?;
+        Ok(vec![new_document(path, strip_html(&raw), None)])
+    }
+}
+
+fn strip_html(input: &str) -> String {
+    let script_or_style = Regex::new(r"(?is)<(script|style)[^>]*>.*?</\1>").expect("static regex");
+    let without_scripts = script_or_style.replace_all(input, "");
+    let tag = Regex::new(r"(?s)<[^>]+>").expect("static regex");
+    let text = tag.replace_all(&without_scripts, " ");
+    decode_entities(&text).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(input: &str) -> String {
+    input
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+}
+
+/// Loads PDF files by extracting their embedded text layer.
+pub struct PdfLoader;
+
+impl DocumentLoader for PdfLoader {
+    fn extensions(&self) -> &[&str] {
+        &["pdf"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<Document>> {
+        let text = pdf_extract::extract_text(path)
+            .map_err(|e| Error::Rag(format!("failed to extract text from {}: {e}", path.display())))?;
+        Ok(vec![new_document(path, text, None)])
+    }
+}
+
+/// Loads source files, splitting each into one document per top-level
+/// function/struct/class so a search hit points at a single definition
+/// instead of an entire file. Languages without a boundary pattern below
+/// (see `boundary_pattern`) fall back to one document for the whole file.
+pub struct CodeLoader;
+
+impl DocumentLoader for CodeLoader {
+    fn extensions(&self) -> &[&str] {
+        &["rs", "py", "go", "java", "kt", "js", "ts", "jsx", "tsx", "c", "cpp", "h", "hpp", "cc"]
+    }
+
+    fn load(&self, path: &Path) -> Result<Vec<Document>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::Rag(format!("failed to read {}: {e}", path.display())))?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+
+        let chunks = match boundary_pattern(extension) {
+            Some(boundary) => split_on_boundaries(&content, &boundary),
+            None => vec![content],
+        };
+
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| new_document(path, chunk, Some(index)))
+            .collect())
+    }
+}
+
+/// Regex matching the start of a top-level declaration for `extension`'s
+/// language, anchored to the start of a (possibly indented) line so nested
+/// declarations inside a chunk don't themselves start a new one. `None` means
+/// this extension has no reliable boundary without a real language parser.
+fn boundary_pattern(extension: &str) -> Option<Regex> {
+    let pattern = match extension {
+        "rs" => r"(?m)^\s*(pub(\(\w+\))?\s+)?(async\s+)?(fn|struct|enum|trait|impl)\s",
+        "py" => r"(?m)^\s*(async\s+)?(def|class)\s",
+        "go" => r"(?m)^\s*func\s",
+        "java" | "kt" => r"(?m)^\s*(public|private|protected)?\s*(static\s+)?(class|interface|enum)\s",
+        "js" | "ts" | "jsx" | "tsx" => r"(?m)^\s*(export\s+)?(default\s+)?(async\s+)?(function|class)\s",
+        _ => return None,
+    };
+    Regex::new(pattern).ok()
+}
+
+/// Split `content` at each match of `boundary`. Content before the first
+/// boundary (module doc comments, imports) becomes its own leading chunk;
+/// everything from one boundary up to the next stays together.
+fn split_on_boundaries(content: &str, boundary: &Regex) -> Vec<String> {
+    let starts: Vec<usize> = boundary.find_iter(content).map(|m| m.start()).collect();
+    let Some(&first) = starts.first() else {
+        return vec![content.to_string()];
+    };
+
+    let mut chunks = Vec::new();
+    if first > 0 {
+        chunks.push(content[..first].to_string());
+    }
+    for window in starts.windows(2) {
+        chunks.push(content[window[0]..window[1]].to_string());
+    }
+    chunks.push(content[*starts.last().expect("starts is non-empty")..].to_string());
+    chunks.into_iter().filter(|chunk| !chunk.trim().is_empty()).collect()
+}
+
+/// Walks a directory, dispatching each file to the loader registered for its
+/// extension and applying `include`/`exclude` glob filters (evaluated
+/// against the path relative to the walked root) before loading it.
+pub struct DirectoryLoader {
+    loaders: Vec<Box<dyn DocumentLoader>>,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl DirectoryLoader {
+    /// Starts with the built-in Markdown/HTML/PDF/code loaders registered
+    /// and no filters (everything matches).
+    pub fn new() -> Self {
+        Self {
+            loaders: vec![Box::new(MarkdownLoader), Box::new(HtmlLoader), Box::new(PdfLoader), Box::new(CodeLoader)],
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Register an additional loader, e.g. for an extension not covered by
+    /// the built-ins. Later registrations take priority for extensions that
+    /// overlap with an earlier one.
+    pub fn with_loader(mut self, loader: Box<dyn DocumentLoader>) -> Self {
+        self.loaders.insert(0, loader);
+        self
+    }
+
+    /// Only load files whose path (relative to the walked root) matches at
+    /// least one include glob. Unset means every path matches.
+    pub fn with_include(mut self, pattern: &str) -> Result<Self> {
+        self.include.push(
+            Pattern::new(pattern).map_err(|e| Error::Config(format!("invalid include glob {pattern:?}: {e}")))?,
+        );
+        Ok(self)
+    }
+
+    /// Skip files whose path (relative to the walked root) matches any
+    /// exclude glob.
+    pub fn with_exclude(mut self, pattern: &str) -> Result<Self> {
+        self.exclude.push(
+            Pattern::new(pattern).map_err(|e| Error::Config(format!("invalid exclude glob {pattern:?}: {e}")))?,
+        );
+        Ok(self)
+    }
+
+    fn passes_filters(&self, relative: &Path) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| p.matches_path(relative));
+        let excluded = self.exclude.iter().any(|p| p.matches_path(relative));
+        included && !excluded
+    }
+
+    fn loader_for(&self, extension: &str) -> Option<&dyn DocumentLoader> {
+        self.loaders
+            .iter()
+            .find(|loader| loader.extensions().contains(&extension))
+            .map(|loader| loader.as_ref())
+    }
+
+    /// Walk `root`, loading every file that passes the include/exclude
+    /// filters and has a registered loader for its extension. Per-file
+    /// failures are logged and skipped rather than aborting the whole walk,
+    /// since one unreadable file shouldn't block indexing the rest of a tree.
+    pub fn load_directory(&self, root: &Path) -> Result<Vec<Document>> {
+        let mut documents = Vec::new();
+
+        for entry in WalkDir::new(root).into_iter().filter_map(std::result::Result::ok) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            if !self.passes_filters(relative) {
+                continue;
+            }
+            let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+                continue;
+            };
+            let Some(loader) = self.loader_for(extension) else {
+                continue;
+            };
+
+            match loader.load(path) {
+                Ok(docs) => documents.extend(docs),
+                Err(e) => tracing::warn!("skipping {}: {e}", path.display()),
+            }
+        }
+
+        Ok(documents)
+    }
+}
+
+impl Default for DirectoryLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_markdown_removes_fences_links_headings_and_emphasis() {
+        let input = "# Heading\n\nSee [docs](https://example.com) for **bold** and `code`.\n\n```rust\nfn f() {}\n```\n";
+        let stripped = strip_markdown(input);
+        assert!(!stripped.contains('#'));
+        assert!(!stripped.contains("](https://example.com)"));
+        assert!(stripped.contains("docs"));
+        assert!(!stripped.contains("**"));
+        assert!(!stripped.contains("fn f() {}"));
+    }
+
+    #[test]
+    fn strip_html_removes_tags_and_script_style_blocks() {
+        let input = "<html><head><style>body{color:red}</style></head><body><p>Hello &amp; welcome</p><script>alert(1)</script></body></html>";
+        let stripped = strip_html(input);
+        assert_eq!(stripped, "Hello & welcome");
+    }
+
+    #[test]
+    fn decode_entities_handles_common_entities() {
+        assert_eq!(decode_entities("&lt;a&gt; &amp; &quot;b&quot; &#39;c&#39;"), "<a> & \"b\" 'c'");
+    }
+
+    #[test]
+    fn boundary_pattern_is_some_for_known_languages_and_none_otherwise() {
+        assert!(boundary_pattern("rs").is_some());
+        assert!(boundary_pattern("py").is_some());
+        assert!(boundary_pattern("md").is_none());
+    }
+
+    #[test]
+    fn split_on_boundaries_splits_rust_source_into_one_chunk_per_item() {
+        let content = "// module doc\nuse std::fmt;\n\nfn one() {}\n\nfn two() {}\n";
+        let boundary = boundary_pattern("rs").unwrap();
+        let chunks = split_on_boundaries(content, &boundary);
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].contains("module doc"));
+        assert!(chunks[1].contains("fn one"));
+        assert!(chunks[2].contains("fn two"));
+    }
+
+    #[test]
+    fn split_on_boundaries_with_no_match_returns_one_chunk() {
+        let content = "just plain text, no declarations here";
+        let boundary = boundary_pattern("rs").unwrap();
+        assert_eq!(split_on_boundaries(content, &boundary), vec![content.to_string()]);
+    }
+
+    #[test]
+    fn directory_loader_include_and_exclude_filters_compose() {
+        let loader = DirectoryLoader::new().with_include("*.rs").unwrap().with_exclude("*_test.rs").unwrap();
+
+        assert!(loader.passes_filters(Path::new("src/lib.rs")));
+        assert!(!loader.passes_filters(Path::new("src/lib_test.rs")));
+        assert!(!loader.passes_filters(Path::new("src/lib.py")));
+    }
+
+    #[test]
+    fn directory_loader_has_a_loader_for_every_built_in_extension() {
+        let loader = DirectoryLoader::new();
+        for extension in ["md", "html", "pdf", "rs", "py"] {
+            assert!(loader.loader_for(extension).is_some(), "missing loader for {extension}");
+        }
+        assert!(loader.loader_for("bin").is_none());
+    }
+
+    #[test]
+    fn markdown_loader_load_reads_and_strips_a_file_on_disk() {
+        let dir = std::env::temp_dir().join(format!("meta-ai-rag-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("doc.md");
+        std::fs::write(&path, "# Title\n\nSome **text**.\n").unwrap();
+
+        let docs = MarkdownLoader.load(&path).unwrap();
+
+        assert_eq!(docs.len(), 1);
+        assert!(!docs[0].content.contains('#'));
+        assert!(docs[0].content.contains("Some text."));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
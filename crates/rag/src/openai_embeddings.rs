@@ -0,0 +1,215 @@
+//! OpenAI-hosted embedding model implementation
+
+use async_trait::async_trait;
+use meta_ai_common::{
+    error::{Error, Result},
+    types::Embedding,
+};
+use meta_ai_core::rag::EmbeddingModel;
+use std::time::Duration;
+
+/// `EmbeddingModel` backed by OpenAI's `/embeddings` endpoint, for
+/// `RagConfig.embedding_model` values like `text-embedding-3-small`/
+/// `text-embedding-3-large` — for deployments that would rather call out to
+/// OpenAI than run `BGEEmbeddingModel` locally.
+pub struct OpenAIEmbeddingModel {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    dimension: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl OpenAIEmbeddingModel {
+    /// `dimension` is sent as the request's `dimensions` field, which
+    /// `text-embedding-3-*` models use to truncate their native embedding
+    /// down to the size the rest of the pipeline (in particular, the Qdrant
+    /// collection's configured vector size) expects.
+    pub fn new(api_key: String, model: String, dimension: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            base_url: "https://api.openai.com/v1".to_string(),
+            model,
+            dimension,
+            max_retries: 3,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Embed `inputs` in a single request (OpenAI's embeddings endpoint
+    /// accepts a batch of strings per call), retrying retryable failures
+    /// (see `Error::is_retryable`) up to `max_retries` times.
+    async fn embed_request(&self, inputs: &[&str]) -> Result<Vec<Embedding>> {
+        #[derive(serde::Serialize)]
+        struct EmbeddingRequest<'a> {
+            model: &'a str,
+            input: &'a [&'a str],
+            dimensions: usize,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbeddingData {
+            index: usize,
+            embedding: Vec<f32>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbeddingResponse {
+            data: Vec<EmbeddingData>,
+        }
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result: Result<Vec<Embedding>> = async {
+                let response = self
+                    .client
+                    .post(format!("{}/embeddings", self.base_url))
+                    .bearer_auth(&self.api_key)
+                    .json(&EmbeddingRequest { model: &self.model, input: inputs, dimensions: self.dimension })
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<EmbeddingResponse>()
+                    .await?;
+
+                let mut data = response.data;
+                data.sort_by_key(|d| d.index);
+                Ok(data.into_iter().map(|d| d.embedding).collect())
+            }
+            .await;
+
+            match result {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(e) if e.is_retryable() && attempts <= self.max_retries => {
+                    tracing::warn!(
+                        "OpenAI embeddings request failed (attempt {attempts}/{}): {e}",
+                        self.max_retries
+                    );
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingModel for OpenAIEmbeddingModel {
+    async fn embed(&self, text: &str) -> Result<Embedding> {
+        self.embed_request(&[text])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Rag("OpenAI embeddings response contained no data".to_string()))
+    }
+
+    async fn embed_batch(&self, texts: Vec<&str>) -> Result<Vec<Embedding>> {
+        self.embed_request(&texts).await
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn model_for(server: &MockServer) -> OpenAIEmbeddingModel {
+        OpenAIEmbeddingModel {
+            client: reqwest::Client::new(),
+            api_key: "test-key".to_string(),
+            base_url: server.uri(),
+            model: "text-embedding-3-small".to_string(),
+            dimension: 3,
+            max_retries: 2,
+            retry_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn embed_returns_the_embedding_for_a_single_input() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"index": 0, "embedding": [0.1, 0.2, 0.3]}]
+            })))
+            .mount(&server)
+            .await;
+
+        let model = model_for(&server);
+        let embedding = model.embed("hello world").await.unwrap();
+
+        assert_eq!(embedding, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[tokio::test]
+    async fn embed_batch_returns_embeddings_sorted_by_input_index() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"index": 1, "embedding": [1.0, 0.0, 0.0]},
+                    {"index": 0, "embedding": [0.0, 1.0, 0.0]}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let model = model_for(&server);
+        let embeddings = model.embed_batch(vec!["first", "second"]).await.unwrap();
+
+        assert_eq!(embeddings, vec![vec![0.0, 1.0, 0.0], vec![1.0, 0.0, 0.0]]);
+    }
+
+    #[tokio::test]
+    async fn embed_request_retries_a_retryable_failure_before_succeeding() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"index": 0, "embedding": [0.5, 0.5]}]
+            })))
+            .mount(&server)
+            .await;
+
+        let model = model_for(&server);
+        let embedding = model.embed("retry me").await.unwrap();
+
+        assert_eq!(embedding, vec![0.5, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn embed_request_gives_up_after_exhausting_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let model = model_for(&server);
+        let result = model.embed("always fails").await;
+
+        assert!(result.is_err());
+    }
+}
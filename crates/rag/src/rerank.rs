@@ -0,0 +1,340 @@
+//! Rerankers for the optional second pass after `RagEngine::search`: rescore
+//! a small set of already-retrieved candidates for precision, trading more
+//! compute per candidate than the first-pass retrieval could afford.
+
+use async_trait::async_trait;
+use candle_core::{Device, Tensor};
+use candle_nn::{ops::sigmoid, Linear, Module, VarBuilder};
+use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
+use meta_ai_common::{
+    error::{Error, Result},
+    types::{LlmParameters, LlmRequest, SearchResult},
+};
+use meta_ai_core::{agent::Agent, rag::Reranker};
+use std::sync::Arc;
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
+use uuid::Uuid;
+
+/// Maximum token sequence length a (query, document) pair is truncated to.
+const MAX_SEQUENCE_LENGTH: usize = 512;
+
+/// `Reranker` backed by a local cross-encoder (a `BertForSequenceClassification`
+/// checkpoint, e.g. `cross-encoder/ms-marco-MiniLM-L-6-v2`), run through
+/// `candle`. Unlike `BGEEmbeddingModel`, a cross-encoder scores a (query,
+/// document) pair jointly rather than embedding each side independently,
+/// which is more accurate but too slow to run over a whole collection —
+/// hence using it only to rerank retrieval's already-narrowed candidates.
+pub struct CrossEncoderReranker {
+    model: BertModel,
+    classifier: Linear,
+    tokenizer: Tokenizer,
+    device: Device,
+}
+
+impl CrossEncoderReranker {
+    /// Download (or reuse a cached copy of) the cross-encoder named
+    /// `model_repo` and load it for CPU inference.
+    pub async fn new(model_repo: &str) -> Result<Self> {
+        let device = Device::Cpu;
+        let repo = hf_hub::api::tokio::Api::new()
+            .map_err(|e| Error::Rag(format!("failed to create Hugging Face Hub client: {e}")))?
+            .model(model_repo.to_string());
+
+        let config_path = repo
+            .get("config.json")
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch {model_repo}/config.json: {e}")))?;
+        let tokenizer_path = repo
+            .get("tokenizer.json")
+            .await
+            .map_err(|e| Error::Rag(format!("failed to fetch {model_repo}/tokenizer.json: {e}")))?;
+        let weights_path = repo.get("model.safetensors").await.map_err(|e| {
+            Error::Rag(format!(
+                "failed to fetch {model_repo}/model.safetensors: {e}"
+            ))
+        })?;
+
+        let config_bytes = std::fs::read(&config_path)
+            .map_err(|e| Error::Rag(format!("failed to read model config: {e}")))?;
+        let bert_config: BertConfig = serde_json::from_slice(&config_bytes)
+            .map_err(|e| Error::Rag(format!("failed to parse model config: {e}")))?;
+        let hidden_size = serde_json::from_slice::<serde_json::Value>(&config_bytes)
+            .ok()
+            .and_then(|v| v.get("hidden_size").and_then(|h| h.as_u64()))
+            .ok_or_else(|| Error::Rag(format!("model {model_repo} config has no hidden_size")))?
+            as usize;
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| Error::Rag(format!("failed to load tokenizer: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: MAX_SEQUENCE_LENGTH,
+                ..Default::default()
+            }))
+            .map_err(|e| Error::Rag(format!("failed to configure tokenizer truncation: {e}")))?;
+
+        // `from_mmaped_safetensors` would avoid this copy but is `unsafe`,
+        // which this crate's `forbid(unsafe_code)` rules out.
+        let weights = std::fs::read(&weights_path)
+            .map_err(|e| Error::Rag(format!("failed to read model weights: {e}")))?;
+        let vb = VarBuilder::from_buffered_safetensors(weights, DTYPE, &device)
+            .map_err(|e| Error::Rag(format!("failed to load model weights: {e}")))?;
+
+        // Cross-encoders are published as `BertForSequenceClassification`
+        // checkpoints: the base encoder's weights live under a `bert.`
+        // prefix, with a `classifier` head alongside it.
+        let model = BertModel::load(vb.pp("bert"), &bert_config)
+            .map_err(|e| Error::Rag(format!("failed to build BERT model: {e}")))?;
+        let classifier = candle_nn::linear(hidden_size, 1, vb.pp("classifier"))
+            .map_err(|e| Error::Rag(format!("failed to load classifier head: {e}")))?;
+
+        Ok(Self {
+            model,
+            classifier,
+            tokenizer,
+            device,
+        })
+    }
+
+    /// Score each (query, document) pair; higher is more relevant.
+    fn score(&self, query: &str, documents: &[&str]) -> Result<Vec<f32>> {
+        let pairs: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|document| (query, *document))
+            .collect();
+        let encodings = self
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(|e| Error::Rag(format!("failed to tokenize input: {e}")))?;
+
+        let input_ids: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_ids().to_vec()).collect();
+        let token_type_ids: Vec<Vec<u32>> = encodings
+            .iter()
+            .map(|e| e.get_type_ids().to_vec())
+            .collect();
+
+        let input_ids = Tensor::new(input_ids, &self.device)
+            .map_err(|e| Error::Rag(format!("failed to build input tensor: {e}")))?;
+        let token_type_ids = Tensor::new(token_type_ids, &self.device)
+            .map_err(|e| Error::Rag(format!("failed to build token type tensor: {e}")))?;
+
+        let hidden_states = self
+            .model
+            .forward(&input_ids, &token_type_ids)
+            .map_err(|e| Error::Rag(format!("model forward pass failed: {e}")))?;
+
+        // The classification head reads the [CLS] token's hidden state
+        // (sequence position 0), following `BertForSequenceClassification`'s
+        // pooling convention.
+        let cls = hidden_states
+            .narrow(1, 0, 1)
+            .and_then(|t| t.squeeze(1))
+            .map_err(|e| Error::Rag(format!("failed to extract [CLS] hidden state: {e}")))?;
+        let logits = self
+            .classifier
+            .forward(&cls)
+            .map_err(|e| Error::Rag(format!("classifier forward pass failed: {e}")))?;
+        let scores =
+            sigmoid(&logits).map_err(|e| Error::Rag(format!("failed to apply sigmoid: {e}")))?;
+
+        scores
+            .squeeze(1)
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| Error::Rag(format!("failed to read scores off the model: {e}")))
+    }
+}
+
+#[async_trait]
+impl Reranker for CrossEncoderReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        mut results: Vec<SearchResult>,
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        if results.is_empty() {
+            return Ok(results);
+        }
+
+        let documents: Vec<&str> = results
+            .iter()
+            .map(|r| r.document.content.as_str())
+            .collect();
+        let scores = self.score(query, &documents)?;
+
+        for (result, score) in results.iter_mut().zip(scores) {
+            result.score = score;
+        }
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(top_k);
+        Ok(results)
+    }
+}
+
+/// `Reranker` that asks an `Agent` (any configured LLM) to judge relevance,
+/// for deployments without local GPU/CPU budget for a cross-encoder, or that
+/// would rather spend an LLM call on higher-quality judgment than a small
+/// local model can give.
+pub struct LlmReranker {
+    agent: Arc<dyn Agent>,
+}
+
+impl LlmReranker {
+    pub fn new(agent: Arc<dyn Agent>) -> Self {
+        Self { agent }
+    }
+
+    /// Build the prompt asking the agent to score one candidate's relevance
+    /// to `query` from 0 to 100.
+    fn prompt_for(query: &str, document: &str) -> String {
+        format!(
+            "Rate how relevant the following passage is to the query on a \
+             scale from 0 (irrelevant) to 100 (perfectly relevant). Respond \
+             with only the number.\n\nQuery: {query}\n\nPassage: {document}"
+        )
+    }
+
+    fn parse_score(content: &str) -> f32 {
+        content
+            .trim()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect::<String>()
+            .parse()
+            .unwrap_or(0.0)
+    }
+}
+
+#[async_trait]
+impl Reranker for LlmReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        mut results: Vec<SearchResult>,
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        for result in &mut results {
+            let request = LlmRequest {
+                id: Uuid::new_v4(),
+                task_id: Uuid::new_v4(),
+                provider: self.agent.provider(),
+                prompt: Self::prompt_for(query, &result.document.content),
+                parameters: LlmParameters::default(),
+                timeout_ms: None,
+                attachments: Vec::new(),
+                metadata: result.metadata.clone(),
+                session_id: None,
+            };
+            let response = self.agent.submit(request).await?;
+            result.score = Self::parse_score(&response.content);
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(top_k);
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meta_ai_common::types::{Document, LlmProvider, LlmResponse, Metadata, TokenUsage};
+    use meta_ai_core::agent::{AgentCapabilities, AgentHealth, RateLimitInfo};
+    use mockall::mock;
+
+    mock! {
+        TestAgent {}
+
+        #[async_trait::async_trait]
+        impl Agent for TestAgent {
+            fn name(&self) -> &str;
+            fn provider(&self) -> LlmProvider;
+            async fn is_available(&self) -> bool;
+            async fn submit(&self, request: LlmRequest) -> Result<LlmResponse>;
+            fn capabilities(&self) -> AgentCapabilities;
+            async fn health_check(&self) -> Result<AgentHealth>;
+            async fn rate_limit_info(&self) -> Result<RateLimitInfo>;
+        }
+    }
+
+    fn result(id: &str, content: &str) -> SearchResult {
+        SearchResult {
+            document: Document {
+                id: id.to_string(),
+                content: content.to_string(),
+                embedding: None,
+                metadata: Metadata::new(),
+                created_at: chrono::Utc::now(),
+                expires_at: None,
+            },
+            score: 0.0,
+            metadata: Metadata::new(),
+        }
+    }
+
+    fn response_for(request: &LlmRequest, content: &str) -> LlmResponse {
+        LlmResponse {
+            request_id: request.id,
+            content: content.to_string(),
+            usage: TokenUsage::default(),
+            latency_ms: 1,
+            provider: request.provider,
+            metadata: Metadata::new(),
+        }
+    }
+
+    #[test]
+    fn parse_score_reads_leading_digits() {
+        assert_eq!(LlmReranker::parse_score("87"), 87.0);
+        assert_eq!(LlmReranker::parse_score("  42.5 out of 100"), 42.5);
+    }
+
+    #[test]
+    fn parse_score_defaults_to_zero_for_non_numeric_content() {
+        assert_eq!(LlmReranker::parse_score("not sure"), 0.0);
+        assert_eq!(LlmReranker::parse_score(""), 0.0);
+    }
+
+    #[tokio::test]
+    async fn rerank_sorts_by_agent_assigned_score_descending() {
+        let mut mock = MockTestAgent::new();
+        mock.expect_provider().returning(|| LlmProvider::OpenAI);
+        mock.expect_submit().returning(|request: LlmRequest| {
+            let score = if request.prompt.contains("Passage: very relevant") { "95" } else { "10" };
+            Ok(response_for(&request, score))
+        });
+        let reranker = LlmReranker::new(Arc::new(mock));
+
+        let results = vec![result("a", "not relevant"), result("b", "very relevant")];
+        let reranked = reranker.rerank("query", results, 10).await.unwrap();
+
+        assert_eq!(reranked.len(), 2);
+        assert_eq!(reranked[0].document.id, "b");
+        assert_eq!(reranked[0].score, 95.0);
+    }
+
+    #[tokio::test]
+    async fn rerank_truncates_to_top_k() {
+        let mut mock = MockTestAgent::new();
+        mock.expect_provider().returning(|| LlmProvider::OpenAI);
+        mock.expect_submit().returning(|request: LlmRequest| Ok(response_for(&request, "50")));
+        let reranker = LlmReranker::new(Arc::new(mock));
+
+        let results = vec![result("a", "one"), result("b", "two"), result("c", "three")];
+        let reranked = reranker.rerank("query", results, 1).await.unwrap();
+
+        assert_eq!(reranked.len(), 1);
+    }
+}
@@ -1,8 +1,12 @@
 //! Meta-AI Orchestrator main entry point
 
-use meta_ai_common::{Config, telemetry};
+use meta_ai_common::{Config, metrics, metrics_server, telemetry};
+use meta_ai_orchestrator_engine::{admin_api, dispatcher::TaskDispatcher};
 use anyhow::Result;
-use tracing::info;
+use secrecy::Secret;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -20,10 +24,34 @@ async fn main() -> Result<()> {
     
     // Initialize telemetry
     telemetry::init_telemetry(&config.observability)?;
-    
+
     info!("Starting Meta-AI Orchestrator v{}", env!("CARGO_PKG_VERSION"));
     info!("Configuration loaded successfully");
-    
+
+    if config.observability.metrics_enabled {
+        metrics::init_metrics().map_err(|e| anyhow::anyhow!("Failed to initialize metrics: {}", e))?;
+        let metrics_port = config.observability.metrics_port;
+        tokio::spawn(async move {
+            if let Err(e) = metrics_server::serve_metrics(metrics_port).await {
+                error!("Metrics exporter on port {} stopped: {}", metrics_port, e);
+            }
+        });
+        info!("Serving Prometheus metrics on 0.0.0.0:{}/metrics", metrics_port);
+    }
+
+    if config.security.admin_api_enabled {
+        let admin_port = config.security.admin_api_port;
+        let admin_token = config.security.admin_token.clone().map(Secret::new);
+        let dispatcher = Arc::new(TaskDispatcher::new(config.orchestrator.max_concurrent_tasks));
+        let admin_state = admin_api::AdminState::new(Arc::new(RwLock::new(config.clone())), dispatcher, admin_token);
+        tokio::spawn(async move {
+            if let Err(e) = admin_api::serve_admin(admin_state, admin_port).await {
+                error!("Admin API on port {} stopped: {}", admin_port, e);
+            }
+        });
+        info!("Serving admin API on 0.0.0.0:{}/admin/config", admin_port);
+    }
+
     // TODO: Initialize and start orchestrator
     println!("🤖 Meta-AI Orchestrator is starting...");
     println!("✅ Configuration validated");